@@ -0,0 +1,158 @@
+use crate::Api;
+
+/// DHL's "Shipment Tracking - Unified" API.
+pub const SHIPMENT_TRACKING: Api = Api {
+    spec: "openapi/shipment-tracking.json",
+    output: "src/api/shipment_tracking/models.rs",
+    timestamp: Some("super::timestamp"),
+    requests: &[("/shipments", "GetShipmentTracking")],
+    names: &[
+        (
+            "supermodelIoLogisticsTrackingShipments",
+            "GetShipmentTrackingResponse",
+        ),
+        ("supermodelIoLogisticsTrackingShipment", "Shipment"),
+        (
+            "supermodelIoLogisticsTrackingShipment.estimatedDeliveryTimeFrame",
+            "EstimatedDeliveryTimeFrame",
+        ),
+        ("supermodelIoLogisticsTrackingPlace", "ShipmentPathPoint"),
+        (
+            "supermodelIoLogisticsTrackingPlace.servicePoint",
+            "SimpleServicePoint",
+        ),
+        (
+            "supermodelIoLogisticsTrackingShipmentStatus",
+            "ShipmentStatus",
+        ),
+        ("statusCode", "ShipmentStatusCode"),
+        (
+            "supermodelIoLogisticsTrackingShipmentEvent",
+            "ShipmentEvent",
+        ),
+        (
+            "supermodelIoLogisticsTrackingShipmentDetails",
+            "ShipmentDetail",
+        ),
+        (
+            "supermodelIoLogisticsTrackingShipmentDetails.product",
+            "ShipmentProduct",
+        ),
+        (
+            "supermodelIoLogisticsTrackingShipmentDetails.dimensions",
+            "ShipmentDimension",
+        ),
+        (
+            "supermodelIoLogisticsTrackingShipmentDetails.references",
+            "ShipmentDetailReference",
+        ),
+        (
+            "supermodelIoLogisticsTrackingShipmentDetails.references.type",
+            "ShipmentDetailReferenceType",
+        ),
+        (
+            "supermodelIoLogisticsSupportingOrganization",
+            "ShipmentCarrier",
+        ),
+        ("supermodelIoLogisticsSupportingParty", "ShipmentParty"),
+        ("supermodelIoLogisticsSupportingPerson", "ShipmentSigned"),
+        (
+            "supermodelIoLogisticsTrackingProofOfDelivery",
+            "ShipmentProofOfDelivery",
+        ),
+        (
+            "supermodelIoLogisticsSupportingQuantitativeValue",
+            "ShipmentFloatWithUnit",
+        ),
+        ("dgfRoute", "ShipmentDgfRoute"),
+        ("dgfAirport", "ShipmentDgfLocation"),
+        ("dgfLocation", "ShipmentDgfSimpleLocation"),
+    ],
+    externs: &[
+        ("service", "crate::api::Division"),
+        ("countryCode", "crate::api::CountryCode"),
+        ("/shipments?language", "crate::api::LanguageCode"),
+        (
+            "supermodelIoLogisticsTrackingPlace.address",
+            "crate::api::Address",
+        ),
+    ],
+    variants: &[],
+    skip: &[],
+    required: &["supermodelIoLogisticsTrackingShipmentStatus.location"],
+    extra_fields: &[(
+        "GetShipmentTrackingResponse",
+        "super::DecodeWarning",
+        "/// Shipments and events skipped by a [lenient](super::ShipmentTrackingApi::lenient) API.
+        #[serde(skip)]
+        pub warnings: Vec<DecodeWarning>,",
+    )],
+    pii: &[
+        ("ShipmentParty", "organization_name"),
+        ("ShipmentParty", "family_name"),
+        ("ShipmentParty", "given_name"),
+        ("ShipmentParty", "name"),
+        ("ShipmentSigned", "family_name"),
+        ("ShipmentSigned", "given_name"),
+        ("ShipmentSigned", "name"),
+        ("ShipmentProofOfDelivery", "signature_url"),
+        ("ShipmentProofOfDelivery", "document_url"),
+        ("ShipmentStatus", "description"),
+        ("ShipmentStatus", "remark"),
+        ("ShipmentStatus", "next_steps"),
+        ("ShipmentEvent", "description"),
+        ("ShipmentEvent", "remark"),
+        ("ShipmentEvent", "next_steps"),
+    ],
+};
+
+/// DHL's "Location Finder - Unified" API.
+pub const LOCATION_FINDER: Api = Api {
+    spec: "openapi/location-finder.json",
+    output: "src/api/location_finder/models.rs",
+    timestamp: None,
+    requests: &[
+        ("/find-by-address", "GetLocationsByAddress"),
+        ("/find-by-geo", "GetLocationsByGeo"),
+        ("/find-by-keyword-id", "GetLocationByKeywordId"),
+        ("/locations/{id}", "GetLocationById"),
+    ],
+    names: &[
+        ("locations", "GetLocationsResponse"),
+        ("location", "GetLocationResponse"),
+        ("location.location", "ServicePointLocation"),
+        ("location.location.ids", "ServicePointLocationId"),
+        ("location.location.type", "ServicePointLocationType"),
+        ("location.place", "Place"),
+        ("location.place.geo", "Geo"),
+        ("location.place.containedInPlace", "ContainedInPlace"),
+        ("location.openingHours", "OpeningHours"),
+        ("location.closurePeriods", "ClosurePeriod"),
+        ("location.averageCapacityDayOfWeek", "WeekdayCapacity"),
+        ("dayOfWeek", "Weekday"),
+        ("capacity", "Capacity"),
+        ("providerType", "ProviderType"),
+        ("locationType", "LocationType"),
+    ],
+    externs: &[
+        ("countryCode", "crate::api::CountryCode"),
+        ("serviceType", "crate::api::ServiceType"),
+        ("location.place.address", "crate::api::Address"),
+    ],
+    variants: &[
+        ("http://schema.org/Monday", "Mon", Some("Monday")),
+        ("http://schema.org/Tuesday", "Tue", Some("Tuesday")),
+        ("http://schema.org/Wednesday", "Wed", Some("Wednesday")),
+        ("http://schema.org/Thursday", "Thu", Some("Thursday")),
+        ("http://schema.org/Friday", "Fri", Some("Friday")),
+        ("http://schema.org/Saturday", "Sat", Some("Saturday")),
+        ("http://schema.org/Sunday", "Sun", Some("Sunday")),
+    ],
+    skip: &[],
+    required: &[],
+    extra_fields: &[],
+    pii: &[],
+};
+
+/// Every API with generated models.
+pub const APIS: &[Api] = &[SHIPMENT_TRACKING, LOCATION_FINDER];
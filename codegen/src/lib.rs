@@ -0,0 +1,919 @@
+//! Generates the request and response types of `dhl_wrapper` from the OpenAPI specs in `openapi/`.
+//!
+//! Run `cargo run -p dhl_codegen` after changing a spec or an [Api](Api).
+//! To add another DHL API, vendor its spec and add an [Api](Api) to [APIS](APIS).
+//!
+//! Specs are vendored as DHL publishes them. Which parts of them the crate models is
+//! configured per [Api](Api): operations missing from `requests` are left out, as are
+//! the properties and parameters listed in `skip`.
+
+mod apis;
+mod json;
+
+pub use apis::*;
+use json::Json;
+use std::collections::BTreeSet;
+use std::fmt::Write as _;
+use std::io::Write as _;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+/// How to generate the models of one API.
+pub struct Api {
+    /// Path of the OpenAPI spec, relative to the workspace root.
+    pub spec: &'static str,
+    /// Path of the generated module, relative to the workspace root.
+    pub output: &'static str,
+    /// Module (de)serializing `date-time` strings as `NaiveDateTime`, if the API has any.
+    pub timestamp: Option<&'static str>,
+    /// Names of the request structs by path. Only these operations are generated.
+    pub requests: &'static [(&'static str, &'static str)],
+    /// Names of the object and enum schemas. Components are keyed by their name,
+    /// inline schemas by the key of their parent and the property, like `location.place`.
+    pub names: &'static [(&'static str, &'static str)],
+    /// Schemas modelled by hand-written types, keyed like `names`.
+    /// Parameters are keyed by path and name, like `/shipments?language`.
+    pub externs: &'static [(&'static str, &'static str)],
+    /// Enum values whose variant name does not follow from the value, with an optional alias.
+    pub variants: &'static [(&'static str, &'static str, Option<&'static str>)],
+    /// Properties and parameters of the spec the crate does not model, keyed like `externs`.
+    pub skip: &'static [&'static str],
+    /// Properties the spec leaves optional which the models require anyway, keyed like `names`.
+    pub required: &'static [&'static str],
+    /// Fields of a struct which are not part of the spec, with the import they need.
+    pub extra_fields: &'static [(&'static str, &'static str, &'static str)],
+    /// Optional or string fields holding personal data by struct, hidden in `Debug` output
+    /// unless it is wrapped in a `dhl_wrapper::redact::Unredacted`.
+    pub pii: &'static [(&'static str, &'static str)],
+}
+
+impl Api {
+    /// Generates the formatted module.
+    pub fn generate(&self, root: &Path) -> Result<String, String> {
+        let json = std::fs::read_to_string(root.join(self.spec))
+            .map_err(|e| format!("{}: {}", self.spec, e))?;
+        let spec =
+            serde_json::from_str::<Json>(&json).map_err(|e| format!("{}: {}", self.spec, e))?;
+        let code = Generator::new(self, &spec)
+            .run()
+            .map_err(|e| format!("{}: {}", self.spec, e))?;
+
+        rustfmt(&code)
+    }
+}
+
+enum Ty {
+    Named(String),
+    Vec(Box<Ty>),
+    String,
+    U32,
+    F64,
+    Bool,
+    DateTime,
+    Date,
+    Time,
+}
+
+impl Ty {
+    fn render(&self) -> String {
+        match self {
+            Ty::Named(v) => v.clone(),
+            Ty::Vec(v) => format!("Vec<{}>", v.render()),
+            Ty::String => "String".to_string(),
+            Ty::U32 => "u32".to_string(),
+            Ty::F64 => "f64".to_string(),
+            Ty::Bool => "bool".to_string(),
+            Ty::DateTime => "NaiveDateTime".to_string(),
+            Ty::Date => "NaiveDate".to_string(),
+            Ty::Time => "NaiveTime".to_string(),
+        }
+    }
+
+    fn named(&self) -> Option<&str> {
+        match self {
+            Ty::Named(v) => Some(v),
+            Ty::Vec(v) => v.named(),
+            _ => None,
+        }
+    }
+}
+
+#[derive(PartialEq)]
+enum Presence {
+    Required,
+    Default,
+    Optional,
+}
+
+struct Field {
+    name: String,
+    wire: String,
+    ty: Ty,
+    presence: Presence,
+    doc: Option<String>,
+}
+
+enum Item {
+    Struct {
+        name: String,
+        doc: Option<String>,
+        fields: Vec<Field>,
+    },
+    Enum {
+        name: String,
+        doc: Option<String>,
+        values: Vec<String>,
+    },
+}
+
+impl Item {
+    fn name(&self) -> &str {
+        match self {
+            Item::Struct { name, .. } | Item::Enum { name, .. } => name,
+        }
+    }
+}
+
+struct Request {
+    name: String,
+    path: String,
+    doc: Option<String>,
+    fields: Vec<Field>,
+}
+
+struct Generator<'a> {
+    api: &'a Api,
+    spec: &'a Json,
+    items: Vec<Item>,
+    requests: Vec<Request>,
+    imports: BTreeSet<String>,
+    used: BTreeSet<String>,
+}
+
+impl<'a> Generator<'a> {
+    fn new(api: &'a Api, spec: &'a Json) -> Self {
+        Generator {
+            api,
+            spec,
+            items: Vec::new(),
+            requests: Vec::new(),
+            imports: BTreeSet::new(),
+            used: BTreeSet::new(),
+        }
+    }
+
+    fn run(mut self) -> Result<String, String> {
+        let mut operations = Vec::new();
+        for (path, _) in self.api.requests {
+            let operation = self
+                .spec
+                .get("paths")
+                .and_then(|v| v.get(path))
+                .and_then(|v| v.get("get"))
+                .ok_or_else(|| format!("no operation `GET {}`", path))?;
+            operations.push((*path, operation));
+        }
+        for (path, operation) in &operations {
+            let schema = operation
+                .get("responses")
+                .and_then(|v| v.get("200"))
+                .and_then(|v| v.get("content"))
+                .and_then(|v| v.get("application/json"))
+                .and_then(|v| v.get("schema"))
+                .ok_or_else(|| format!("`GET {}` has no JSON response", path))?;
+            self.ty(path, schema)?;
+        }
+        for (path, operation) in operations {
+            self.request(path, operation)?;
+        }
+
+        let keys = self.api.names.iter().chain(self.api.externs);
+        if let Some((key, _)) = keys.clone().find(|(k, _)| !self.used.contains(*k)) {
+            return Err(format!("schema `{}` is named but not used", key));
+        }
+        let keys = self.api.skip.iter().chain(self.api.required);
+        if let Some(key) = keys.clone().find(|v| !self.used.contains(**v)) {
+            return Err(format!("`{}` is configured but not in the spec", key));
+        }
+        for (name, field) in self.api.pii {
+            let redactable = self.items.iter().any(|v| match v {
+                Item::Struct {
+                    name: v, fields, ..
+                } if v == name => fields.iter().any(|v| {
+                    v.name == *field
+                        && (v.presence == Presence::Optional || matches!(v.ty, Ty::String))
+                }),
+                _ => false,
+            });
+            if !redactable {
+                return Err(format!(
+                    "`{}.{}` is neither an optional nor a string field",
+                    name, field
+                ));
+            }
+        }
+
+        Ok(self.render())
+    }
+
+    fn component(&self, name: &str) -> Result<&'a Json, String> {
+        self.spec
+            .get("components")
+            .and_then(|v| v.get("schemas"))
+            .and_then(|v| v.get(name))
+            .ok_or_else(|| format!("no component schema `{}`", name))
+    }
+
+    /// Whether the property or parameter keyed `key` is configured to be left out.
+    fn is_skipped(&mut self, key: &str) -> bool {
+        let skipped = self.api.skip.contains(&key);
+        if skipped {
+            self.used.insert(key.to_string());
+        }
+
+        skipped
+    }
+
+    /// Whether the property keyed `key` is configured to be required.
+    fn is_required(&mut self, key: &str) -> bool {
+        let required = self.api.required.contains(&key);
+        if required {
+            self.used.insert(key.to_string());
+        }
+
+        required
+    }
+
+    fn name(&mut self, key: &str) -> Result<String, String> {
+        self.used.insert(key.to_string());
+        self.api
+            .names
+            .iter()
+            .find(|(k, _)| *k == key)
+            .map(|(_, v)| v.to_string())
+            .ok_or_else(|| format!("no Rust name for schema `{}`", key))
+    }
+
+    /// Type of `schema`, generating it if it is an object or enum.
+    fn ty(&mut self, key: &str, schema: &'a Json) -> Result<Ty, String> {
+        let (key, schema) = match schema.str("$ref") {
+            Some(v) => {
+                let name = v.trim_start_matches("#/components/schemas/");
+                (name.to_string(), self.component(name)?)
+            }
+            None => (key.to_string(), schema),
+        };
+        if let Some((_, path)) = self.api.externs.iter().find(|(k, _)| *k == key) {
+            self.used.insert(key);
+            self.imports.insert(path.to_string());
+            return Ok(Ty::Named(path.rsplit("::").next().unwrap().to_string()));
+        }
+
+        if !schema.array("enum").is_empty() {
+            let name = self.name(&key)?;
+            let values = schema
+                .array("enum")
+                .iter()
+                .map(|v| v.as_str().map(String::from))
+                .collect::<Option<Vec<_>>>()
+                .ok_or_else(|| format!("enum `{}` has values which are not strings", key))?;
+            match self.items.iter().find(|v| v.name() == name) {
+                Some(Item::Enum { values: v, .. }) if *v == values => {}
+                Some(_) => return Err(format!("`{}` is generated from different schemas", name)),
+                None => self.items.push(Item::Enum {
+                    name: name.clone(),
+                    doc: schema.str("description").map(String::from),
+                    values,
+                }),
+            }
+
+            return Ok(Ty::Named(name));
+        }
+
+        let ty = match schema.str("type") {
+            Some("object") => Ty::Named(self.object(&key, schema)?),
+            Some("array") => {
+                let items = schema
+                    .get("items")
+                    .ok_or_else(|| format!("array `{}` has no items", key))?;
+                Ty::Vec(Box::new(self.ty(&key, items)?))
+            }
+            Some("string") => match schema.str("format") {
+                Some("date-time") => Ty::DateTime,
+                Some("date") => Ty::Date,
+                Some("time") => Ty::Time,
+                _ => Ty::String,
+            },
+            Some("integer") => Ty::U32,
+            Some("number") => Ty::F64,
+            Some("boolean") => Ty::Bool,
+            v => return Err(format!("schema `{}` has unsupported type {:?}", key, v)),
+        };
+
+        Ok(ty)
+    }
+
+    fn object(&mut self, key: &str, schema: &'a Json) -> Result<String, String> {
+        let name = self.name(key)?;
+        if self.items.iter().any(|v| v.name() == name) {
+            return Ok(name);
+        }
+
+        let index = self.items.len();
+        self.items.push(Item::Struct {
+            name: name.clone(),
+            doc: schema.str("description").map(String::from),
+            fields: Vec::new(),
+        });
+        let required = schema
+            .array("required")
+            .iter()
+            .filter_map(Json::as_str)
+            .collect::<Vec<_>>();
+        let mut res = Vec::new();
+        for (wire, property) in schema.entries("properties") {
+            let property_key = format!("{}.{}", key, wire);
+            if self.is_skipped(&property_key) {
+                continue;
+            }
+            let ty = self.ty(&property_key, property)?;
+            let presence = if required.contains(&wire.as_str()) || self.is_required(&property_key) {
+                Presence::Required
+            } else if property.get("default").is_some() {
+                Presence::Default
+            } else {
+                Presence::Optional
+            };
+            res.push(Field {
+                name: field_name(wire),
+                wire: wire.clone(),
+                doc: match ty.named() {
+                    Some(_) => None,
+                    None => property.str("description").map(String::from),
+                },
+                ty,
+                presence,
+            });
+        }
+        if let Item::Struct { fields, .. } = &mut self.items[index] {
+            *fields = res;
+        }
+
+        Ok(name)
+    }
+
+    fn request(&mut self, path: &str, operation: &'a Json) -> Result<(), String> {
+        let name = self
+            .api
+            .requests
+            .iter()
+            .find(|(k, _)| *k == path)
+            .map(|(_, v)| v.to_string())
+            .ok_or_else(|| format!("no Rust name for the request to `{}`", path))?;
+
+        let mut fields = Vec::new();
+        for parameter in operation.array("parameters") {
+            let parameter = match parameter.str("$ref") {
+                Some(v) => {
+                    let name = v.trim_start_matches("#/components/parameters/");
+                    self.spec
+                        .get("components")
+                        .and_then(|v| v.get("parameters"))
+                        .and_then(|v| v.get(name))
+                        .ok_or_else(|| format!("no component parameter `{}`", name))?
+                }
+                None => parameter,
+            };
+            let wire = parameter
+                .str("name")
+                .ok_or_else(|| format!("parameter of `{}` without a name", path))?;
+            if self.is_skipped(&format!("{}?{}", path, wire)) {
+                continue;
+            }
+            let schema = parameter
+                .get("schema")
+                .ok_or_else(|| format!("parameter `{}` of `{}` has no schema", wire, path))?;
+            let required = parameter.get("required") == Some(&Json::Bool(true));
+            fields.push(Field {
+                name: field_name(wire),
+                wire: wire.to_string(),
+                ty: self.ty(&format!("{}?{}", path, wire), schema)?,
+                presence: match required {
+                    true => Presence::Required,
+                    false => Presence::Optional,
+                },
+                doc: parameter.str("description").map(String::from),
+            });
+        }
+        self.requests.push(Request {
+            name,
+            path: path.to_string(),
+            doc: operation.str("description").map(String::from),
+            fields,
+        });
+
+        Ok(())
+    }
+
+    /// Whether `ty` can derive `Eq`. Hand-written types are expected to.
+    fn is_eq(&self, ty: &Ty) -> bool {
+        match ty {
+            Ty::F64 => false,
+            Ty::Vec(v) => self.is_eq(v),
+            Ty::Named(name) => match self.items.iter().find(|v| v.name() == name) {
+                Some(Item::Struct { fields, .. }) => fields.iter().all(|v| self.is_eq(&v.ty)),
+                _ => true,
+            },
+            _ => true,
+        }
+    }
+
+    /// Whether some field defaults to a `name`.
+    fn is_defaulted(&self, name: &str) -> bool {
+        self.items.iter().any(|v| match v {
+            Item::Struct { fields, .. } => fields.iter().any(|v| {
+                v.presence == Presence::Default && matches!(&v.ty, Ty::Named(v) if v == name)
+            }),
+            _ => false,
+        })
+    }
+
+    fn render(mut self) -> String {
+        for item in &self.items {
+            if let Item::Struct { name, fields, .. } = item {
+                for field in fields {
+                    import_chrono(&mut self.imports, self.api.timestamp, &field.ty);
+                }
+                for (_, import, _) in self.api.extra_fields.iter().filter(|(v, _, _)| v == name) {
+                    self.imports.insert(import.to_string());
+                }
+                if self.api.pii.iter().any(|(v, _)| v == name) {
+                    self.imports.insert("crate::redact::Pii".to_string());
+                    self.imports.insert("std::fmt".to_string());
+                }
+            }
+        }
+
+        let mut body = String::new();
+        let requests = std::mem::take(&mut self.requests);
+        for request in &requests {
+            self.render_request(&mut body, request);
+        }
+        for item in &self.items {
+            match item {
+                Item::Struct { name, doc, fields } => {
+                    self.render_struct(&mut body, name, doc, fields)
+                }
+                Item::Enum { name, doc, values } => self.render_enum(&mut body, name, doc, values),
+            }
+        }
+
+        let title = self
+            .spec
+            .get("info")
+            .and_then(|v| v.str("title"))
+            .unwrap_or_default();
+        let mut out = format!(
+            "// @generated by `cargo run -p dhl_codegen` from `{}`. Do not edit by hand.\n\n\
+             //! Requests and responses of DHL's \"{}\" API.\n\n",
+            self.api.spec, title
+        );
+        for import in &self.imports {
+            let _ = writeln!(out, "use {};", import);
+        }
+        out.push_str("use serde::{Deserialize, Serialize};\n");
+        out.push_str(&body);
+
+        out
+    }
+
+    fn render_request(&mut self, out: &mut String, request: &Request) {
+        let eq = request.fields.iter().all(|v| self.is_eq(&v.ty));
+        let _ = writeln!(
+            out,
+            "\n/// Parameters of the `GET {}` request.",
+            request.path
+        );
+        if let Some(doc) = &request.doc {
+            out.push_str("///\n");
+            render_doc(out, doc);
+        }
+        let _ = writeln!(
+            out,
+            "#[derive(Debug, Clone, PartialEq, {}Serialize, Deserialize)]",
+            if eq { "Eq, " } else { "" }
+        );
+        out.push_str("#[serde(rename_all = \"camelCase\")]\n");
+        let _ = writeln!(out, "pub struct {} {{", request.name);
+        for field in &request.fields {
+            if camel_case(&field.name) != field.wire {
+                let _ = writeln!(out, "#[serde(rename = {:?})]", field.wire);
+            }
+            let ty = match field.presence {
+                Presence::Required => field.ty.render(),
+                _ => format!("Option<{}>", field.ty.render()),
+            };
+            let _ = writeln!(out, "pub(crate) {}: {},", field.name, ty);
+        }
+        out.push_str("}\n\n");
+
+        let _ = writeln!(out, "impl {} {{", request.name);
+        let required = request
+            .fields
+            .iter()
+            .filter(|v| v.presence == Presence::Required)
+            .collect::<Vec<_>>();
+        let generics = ["T", "U", "V", "W"];
+        let mut strings = 0;
+        let mut args = Vec::new();
+        let mut bounds = Vec::new();
+        for field in &required {
+            if matches!(field.ty, Ty::String) {
+                let generic = generics[strings];
+                strings += 1;
+                bounds.push(format!("{}: Into<String>", generic));
+                args.push(format!("{}: {}", field.name, generic));
+            } else {
+                args.push(format!("{}: {}", field.name, field.ty.render()));
+            }
+        }
+        let bounds = match bounds.is_empty() {
+            true => String::new(),
+            false => format!("<{}>", bounds.join(", ")),
+        };
+        let _ = writeln!(
+            out,
+            "pub fn new{}({}) -> Self {{\n{} {{",
+            bounds,
+            args.join(", "),
+            request.name
+        );
+        for field in &request.fields {
+            match (&field.presence, &field.ty) {
+                (Presence::Required, Ty::String) => {
+                    let _ = writeln!(out, "{0}: {0}.into(),", field.name);
+                }
+                (Presence::Required, _) => {
+                    let _ = writeln!(out, "{},", field.name);
+                }
+                _ => {
+                    let _ = writeln!(out, "{}: None,", field.name);
+                }
+            }
+        }
+        out.push_str("}\n}\n");
+
+        for field in request
+            .fields
+            .iter()
+            .filter(|v| v.presence != Presence::Required)
+        {
+            let name = &field.name;
+            match &field.ty {
+                Ty::String => {
+                    render_field_doc(out, &field.doc);
+                    let _ = writeln!(
+                        out,
+                        "pub fn {0}<T: Into<String>>(mut self, {0}: Option<T>) -> Self {{\n\
+                         self.{0} = {0}.map(|v| v.into());\n\nself\n}}",
+                        name
+                    );
+                }
+                Ty::Vec(item) => {
+                    self.imports.insert("crate::api::distinct".to_string());
+                    let item = item.render();
+                    let _ = writeln!(
+                        out,
+                        "\npub fn {0}(mut self, {0}: Option<{1}>) -> Self {{\n\
+                         self.{0} = {0}.map(|v| vec![v]);\n\nself\n}}",
+                        name, item
+                    );
+                    render_field_doc(out, &field.doc);
+                    let _ = writeln!(
+                        out,
+                        "pub fn {0}s(mut self, {0}s: Option<Vec<{1}>>) -> Self {{\n\
+                         self.{0} = {0}s.and_then(distinct);\n\nself\n}}",
+                        name, item
+                    );
+                }
+                ty => {
+                    render_field_doc(out, &field.doc);
+                    let _ = writeln!(
+                        out,
+                        "pub fn {0}(mut self, {0}: Option<{1}>) -> Self {{\n\
+                         self.{0} = {0};\n\nself\n}}",
+                        name,
+                        ty.render()
+                    );
+                }
+            }
+        }
+        out.push_str("}\n");
+        for field in &request.fields {
+            import_chrono(&mut self.imports, self.api.timestamp, &field.ty);
+        }
+    }
+
+    fn render_struct(&self, out: &mut String, name: &str, doc: &Option<String>, fields: &[Field]) {
+        let pii = self
+            .api
+            .pii
+            .iter()
+            .filter(|(v, _)| *v == name)
+            .map(|(_, v)| *v)
+            .collect::<Vec<&str>>();
+        let mut derives = vec!["Debug", "Clone"];
+        if !pii.is_empty() {
+            derives.remove(0);
+        }
+        if self.is_defaulted(name) {
+            derives.push("Default");
+        }
+        derives.push("PartialEq");
+        if self.is_eq(&Ty::Named(name.to_string())) {
+            derives.push("Eq");
+        }
+        derives.extend(["Serialize", "Deserialize"]);
+
+        out.push('\n');
+        if let Some(doc) = doc {
+            render_doc(out, doc);
+        }
+        let _ = writeln!(out, "#[derive({})]", derives.join(", "));
+        out.push_str("#[serde(rename_all = \"camelCase\")]\n");
+        let _ = writeln!(out, "pub struct {} {{", name);
+        for field in fields {
+            if let Some(doc) = &field.doc {
+                render_doc(out, doc);
+            }
+            let mut attributes = Vec::new();
+            if camel_case(&field.name) != field.wire {
+                attributes.push(format!("rename = {:?}", field.wire));
+            }
+            let timestamp = self
+                .api
+                .timestamp
+                .map(|v| v.rsplit("::").next().unwrap())
+                .unwrap_or("timestamp");
+            match (&field.presence, &field.ty) {
+                (Presence::Required, Ty::DateTime) => {
+                    attributes.push(format!("with = \"{}\"", timestamp));
+                }
+                (Presence::Optional, Ty::DateTime) => {
+                    attributes.push("default".to_string());
+                    attributes.push(format!("with = \"{}::option\"", timestamp));
+                }
+                (Presence::Default, _) => attributes.push("default".to_string()),
+                _ => {}
+            }
+            if !attributes.is_empty() {
+                let _ = writeln!(out, "#[serde({})]", attributes.join(", "));
+            }
+            let ty = match field.presence {
+                Presence::Optional => format!("Option<{}>", field.ty.render()),
+                _ => field.ty.render(),
+            };
+            let _ = writeln!(out, "pub {}: {},", field.name, ty);
+        }
+        for (_, _, code) in self.api.extra_fields.iter().filter(|(v, _, _)| *v == name) {
+            let _ = writeln!(out, "{}", code);
+        }
+        out.push_str("}\n");
+        if !pii.is_empty() {
+            self.render_debug(out, name, fields, &pii);
+        }
+    }
+
+    /// `Debug` hiding the `pii` fields.
+    fn render_debug(&self, out: &mut String, name: &str, fields: &[Field], pii: &[&str]) {
+        let _ = writeln!(
+            out,
+            "\nimpl fmt::Debug for {} {{\n\
+             fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {{\n\
+             f.debug_struct({:?})",
+            name, name
+        );
+        for field in fields {
+            let label = field.name.trim_start_matches("r#");
+            match pii.contains(&field.name.as_str()) {
+                true => {
+                    let _ = writeln!(out, ".field({:?}, &Pii(&self.{}))", label, field.name);
+                }
+                false => {
+                    let _ = writeln!(out, ".field({:?}, &self.{})", label, field.name);
+                }
+            }
+        }
+        out.push_str(".finish()\n}\n}\n");
+    }
+
+    fn render_enum(&self, out: &mut String, name: &str, doc: &Option<String>, values: &[String]) {
+        let variants = values
+            .iter()
+            .map(
+                |value| match self.api.variants.iter().find(|(v, _, _)| v == value) {
+                    Some((_, variant, alias)) => (value, variant.to_string(), *alias),
+                    None => (value, pascal_case(value), None),
+                },
+            )
+            .collect::<Vec<_>>();
+        let rule = RENAME_RULES
+            .iter()
+            .map(|rule| {
+                let matches = variants
+                    .iter()
+                    .filter(|(value, variant, _)| rename_variant(rule, variant) == **value)
+                    .count();
+                (rule, matches)
+            })
+            .fold(None, |best: Option<(&&str, usize)>, v| match best {
+                Some(best) if best.1 >= v.1 => Some(best),
+                _ if v.1 > 0 => Some(v),
+                _ => best,
+            })
+            .map(|(v, _)| *v);
+
+        out.push('\n');
+        if let Some(doc) = doc {
+            render_doc(out, doc);
+        }
+        out.push_str(
+            "#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]\n",
+        );
+        if let Some(rule) = rule {
+            let _ = writeln!(out, "#[serde(rename_all = \"{}\")]", rule);
+        }
+        let _ = writeln!(out, "pub enum {} {{", name);
+        for (value, variant, alias) in &variants {
+            let mut attributes = Vec::new();
+            if rule.map(|v| rename_variant(v, variant)).as_ref() != Some(*value) {
+                attributes.push(format!("rename = {:?}", value));
+            }
+            if let Some(alias) = alias {
+                attributes.push(format!("alias = {:?}", alias));
+            }
+            if *value == "unknown" {
+                out.push_str("/// Also used for values this crate does not know yet.\n");
+                attributes.push("other".to_string());
+            }
+            if !attributes.is_empty() {
+                let _ = writeln!(out, "#[serde({})]", attributes.join(", "));
+            }
+            let _ = writeln!(out, "{},", variant);
+        }
+        out.push_str("}\n");
+    }
+}
+
+/// Adds the imports `ty` needs.
+fn import_chrono(imports: &mut BTreeSet<String>, timestamp: Option<&str>, ty: &Ty) {
+    match ty {
+        Ty::DateTime => {
+            imports.insert("chrono::NaiveDateTime".to_string());
+            if let Some(v) = timestamp {
+                imports.insert(v.to_string());
+            }
+        }
+        Ty::Date => {
+            imports.insert("chrono::NaiveDate".to_string());
+        }
+        Ty::Time => {
+            imports.insert("chrono::NaiveTime".to_string());
+        }
+        Ty::Vec(v) => import_chrono(imports, timestamp, v),
+        _ => {}
+    }
+}
+
+/// Rules of `#[serde(rename_all)]`, in order of preference.
+const RENAME_RULES: [&str; 7] = [
+    "lowercase",
+    "UPPERCASE",
+    "PascalCase",
+    "camelCase",
+    "snake_case",
+    "kebab-case",
+    "SCREAMING_SNAKE_CASE",
+];
+
+/// Name serde gives to `variant` under `rule`.
+fn rename_variant(rule: &str, variant: &str) -> String {
+    let snake = || {
+        let mut res = String::new();
+        for (i, c) in variant.char_indices() {
+            if i > 0 && c.is_uppercase() {
+                res.push('_');
+            }
+            res.push(c.to_ascii_lowercase());
+        }
+        res
+    };
+
+    match rule {
+        "lowercase" => variant.to_ascii_lowercase(),
+        "UPPERCASE" => variant.to_ascii_uppercase(),
+        "PascalCase" => variant.to_string(),
+        "camelCase" => variant[..1].to_ascii_lowercase() + &variant[1..],
+        "snake_case" => snake(),
+        "kebab-case" => snake().replace('_', "-"),
+        "SCREAMING_SNAKE_CASE" => snake().to_ascii_uppercase(),
+        _ => unreachable!(),
+    }
+}
+
+/// Name serde gives to `field` under `#[serde(rename_all = "camelCase")]`.
+fn camel_case(field: &str) -> String {
+    let mut res = String::new();
+    let mut upper = false;
+    for c in field.trim_start_matches("r#").chars() {
+        match c {
+            '_' => upper = true,
+            c if upper => {
+                res.push(c.to_ascii_uppercase());
+                upper = false;
+            }
+            c => res.push(c),
+        }
+    }
+
+    res
+}
+
+/// Rust field name of a property like `dgf:vesselName` or `@type`.
+fn field_name(wire: &str) -> String {
+    let mut res = String::new();
+    for word in wire
+        .split(|v: char| !v.is_ascii_alphanumeric())
+        .filter(|v| !v.is_empty())
+    {
+        if !res.is_empty() {
+            res.push('_');
+        }
+        for (i, c) in word.char_indices() {
+            if i > 0 && c.is_ascii_uppercase() {
+                res.push('_');
+            }
+            res.push(c.to_ascii_lowercase());
+        }
+    }
+
+    match res.as_str() {
+        "type" | "ref" | "match" | "move" | "static" | "use" | "in" | "fn" | "mod" | "impl" => {
+            format!("r#{}", res)
+        }
+        _ => res,
+    }
+}
+
+/// Variant name of an enum value like `parcel:pick-up`.
+fn pascal_case(value: &str) -> String {
+    value
+        .split(|v: char| !v.is_ascii_alphanumeric())
+        .filter(|v| !v.is_empty())
+        .map(|v| v[..1].to_ascii_uppercase() + &v[1..])
+        .collect()
+}
+
+fn render_doc(out: &mut String, doc: &str) {
+    for line in doc.lines() {
+        let _ = writeln!(out, "/// {}", line);
+    }
+}
+
+fn render_field_doc(out: &mut String, doc: &Option<String>) {
+    out.push('\n');
+    if let Some(doc) = doc {
+        render_doc(out, doc);
+    }
+}
+
+/// Formats `code` with rustfmt, like `cargo fmt` would.
+fn rustfmt(code: &str) -> Result<String, String> {
+    let mut child = Command::new("rustfmt")
+        .args(["--edition", "2021"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("failed to run rustfmt: {}", e))?;
+
+    let mut stdin = child.stdin.take().unwrap();
+    let code = code.to_string();
+    let writer = std::thread::spawn(move || stdin.write_all(code.as_bytes()));
+    let output = child
+        .wait_with_output()
+        .map_err(|e| format!("failed to run rustfmt: {}", e))?;
+    writer
+        .join()
+        .unwrap()
+        .map_err(|e| format!("failed to write to rustfmt: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "rustfmt failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
+
+    String::from_utf8(output.stdout).map_err(|e| e.to_string())
+}
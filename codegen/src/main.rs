@@ -0,0 +1,14 @@
+use dhl_codegen::APIS;
+use std::path::Path;
+
+fn main() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+    for api in APIS {
+        let code = api.generate(root).unwrap_or_else(|e| {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        });
+        std::fs::write(root.join(api.output), code).unwrap();
+        println!("Generated {} from {}", api.output, api.spec);
+    }
+}
@@ -0,0 +1,114 @@
+use dhl_codegen::Api;
+use serde_json::json;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Spec as DHL could publish it, with an operation and properties the crate does not model.
+fn spec() -> serde_json::Value {
+    json!({
+        "info": { "title": "Things" },
+        "paths": {
+            "/things": {
+                "get": {
+                    "parameters": [
+                        { "name": "id", "in": "query", "required": true, "schema": { "type": "string" } },
+                        { "name": "internal", "in": "query", "schema": { "type": "string" } },
+                    ],
+                    "responses": {
+                        "200": {
+                            "content": {
+                                "application/json": {
+                                    "schema": { "$ref": "#/components/schemas/thing" }
+                                }
+                            }
+                        }
+                    }
+                }
+            },
+            "/other": {
+                "post": {}
+            }
+        },
+        "components": {
+            "schemas": {
+                "thing": {
+                    "type": "object",
+                    "required": ["id"],
+                    "properties": {
+                        "id": { "type": "string" },
+                        "name": { "type": "string" },
+                        "internal": { "type": "string" },
+                    }
+                }
+            }
+        }
+    })
+}
+
+const API: Api = Api {
+    spec: "things.json",
+    output: "things.rs",
+    timestamp: None,
+    requests: &[("/things", "GetThings")],
+    names: &[("thing", "Thing")],
+    externs: &[],
+    variants: &[],
+    skip: &["thing.internal", "/things?internal"],
+    required: &["thing.name"],
+    extra_fields: &[],
+    pii: &[],
+};
+
+fn generate(api: &Api, spec: &serde_json::Value) -> Result<String, String> {
+    static RUNS: AtomicUsize = AtomicUsize::new(0);
+    let root = std::env::temp_dir().join(format!(
+        "dhl-codegen-{}-{}",
+        std::process::id(),
+        RUNS.fetch_add(1, Ordering::SeqCst)
+    ));
+    std::fs::create_dir_all(&root).unwrap();
+    let path: PathBuf = root.join(api.spec);
+    std::fs::write(&path, spec.to_string()).unwrap();
+
+    let generated = api.generate(&root);
+    std::fs::remove_dir_all(root).unwrap();
+
+    generated
+}
+
+#[test]
+fn generate_what_the_api_configures() {
+    let generated = generate(&API, &spec()).unwrap();
+
+    assert!(generated.contains("pub struct GetThings"));
+    assert!(generated.contains("pub name: String,"));
+    assert!(!generated.contains("internal"));
+    assert!(!generated.contains("other"));
+}
+
+#[test]
+fn notice_upstream_changes() {
+    let generated = generate(&API, &spec()).unwrap();
+
+    // a new property shows up in the generated code, failing the up to date check
+    let mut changed = spec();
+    changed["components"]["schemas"]["thing"]["properties"]["color"] = json!({ "type": "string" });
+    let regenerated = generate(&API, &changed).unwrap();
+    assert_ne!(regenerated, generated);
+    assert!(regenerated.contains("pub color: Option<String>,"));
+
+    // so does a skipped property which is gone
+    let mut changed = spec();
+    changed["components"]["schemas"]["thing"]["properties"]
+        .as_object_mut()
+        .unwrap()
+        .remove("internal");
+    let error = generate(&API, &changed).unwrap_err();
+    assert!(error.contains("`thing.internal` is configured but not in the spec"));
+
+    // and a modelled operation which is gone
+    let mut changed = spec();
+    changed["paths"].as_object_mut().unwrap().remove("/things");
+    let error = generate(&API, &changed).unwrap_err();
+    assert!(error.contains("no operation `GET /things`"));
+}
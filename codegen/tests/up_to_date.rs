@@ -0,0 +1,17 @@
+use dhl_codegen::APIS;
+use std::path::Path;
+
+#[test]
+fn generated_models_are_up_to_date() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+    for api in APIS {
+        let generated = api.generate(root).unwrap();
+        let checked_in = std::fs::read_to_string(root.join(api.output)).unwrap();
+        assert!(
+            generated == checked_in,
+            "{} is out of date with {}, run `cargo run -p dhl_codegen`",
+            api.output,
+            api.spec
+        );
+    }
+}
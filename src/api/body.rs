@@ -0,0 +1,180 @@
+use crate::error::DhlError;
+use serde::Serialize;
+
+/// Body of a request, sent with its `Content-Type`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Body {
+    pub content_type: String,
+    pub bytes: Vec<u8>,
+}
+
+impl Body {
+    pub fn new<T: Into<String>>(content_type: T, bytes: Vec<u8>) -> Self {
+        Body {
+            content_type: content_type.into(),
+            bytes,
+        }
+    }
+
+    /// Serializes `value` to an `application/json` body.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use dhl_wrapper::api::Body;
+    /// let body = Body::json(&serde_json::json!({ "pieces": 2 })).unwrap();
+    ///
+    /// assert_eq!(body.content_type, "application/json");
+    /// assert_eq!(body.bytes, br#"{"pieces":2}"#);
+    /// ```
+    pub fn json<T: Serialize>(value: &T) -> Result<Self, DhlError> {
+        Ok(Body::new("application/json", serde_json::to_vec(value)?))
+    }
+
+    /// Encodes `params` to an `application/x-www-form-urlencoded` body.
+    pub fn form<T: AsRef<str>, U: AsRef<str>>(params: &[(T, U)]) -> Self {
+        let bytes = form_urlencoded::Serializer::new(String::new())
+            .extend_pairs(params.iter().map(|(k, v)| (k.as_ref(), v.as_ref())))
+            .finish()
+            .into_bytes();
+
+        Body::new("application/x-www-form-urlencoded", bytes)
+    }
+
+    /// Encodes `form` to a `multipart/form-data` body.
+    pub fn multipart(form: Multipart) -> Self {
+        let boundary = form.boundary();
+
+        let mut bytes = Vec::new();
+        for part in &form.parts {
+            bytes.extend_from_slice(format!("--{}\r\n", boundary).as_bytes());
+            let mut disposition = format!(
+                "Content-Disposition: form-data; name=\"{}\"",
+                escape(&part.name)
+            );
+            if let Some(v) = &part.file_name {
+                disposition.push_str(&format!("; filename=\"{}\"", escape(v)));
+            }
+            bytes.extend_from_slice(disposition.as_bytes());
+            bytes.extend_from_slice(b"\r\n");
+            if let Some(v) = &part.content_type {
+                bytes.extend_from_slice(format!("Content-Type: {}\r\n", v).as_bytes());
+            }
+            bytes.extend_from_slice(b"\r\n");
+            bytes.extend_from_slice(&part.bytes);
+            bytes.extend_from_slice(b"\r\n");
+        }
+        bytes.extend_from_slice(format!("--{}--\r\n", boundary).as_bytes());
+
+        Body::new(format!("multipart/form-data; boundary={}", boundary), bytes)
+    }
+}
+
+/// Escapes a field or file name for the quoted strings of a `Content-Disposition` header,
+/// percent-encoding quotes and line breaks like browsers do, see RFC 7578, section 4.2.
+fn escape(name: &str) -> String {
+    name.replace('"', "%22")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// A `multipart/form-data` form, e.g. to upload documents.
+///
+/// # Example
+///
+/// ```
+/// # use dhl_wrapper::api::{Body, Multipart};
+/// let form = Multipart::new()
+///     .text("documentType", "INVOICE")
+///     .file("file", "invoice.pdf", "application/pdf", b"%PDF-1.7".to_vec());
+/// let body = Body::multipart(form);
+///
+/// assert!(body.content_type.starts_with("multipart/form-data; boundary="));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Multipart {
+    parts: Vec<Part>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Part {
+    name: String,
+    file_name: Option<String>,
+    content_type: Option<String>,
+    bytes: Vec<u8>,
+}
+
+impl Multipart {
+    pub fn new() -> Self {
+        Multipart::default()
+    }
+
+    /// Adds a plain text field.
+    pub fn text<T: Into<String>, U: Into<String>>(mut self, name: T, value: U) -> Self {
+        self.parts.push(Part {
+            name: name.into(),
+            file_name: None,
+            content_type: None,
+            bytes: value.into().into_bytes(),
+        });
+
+        self
+    }
+
+    /// Adds a file with its name and content type, like `application/pdf`.
+    pub fn file<T, U, V>(mut self, name: T, file_name: U, content_type: V, bytes: Vec<u8>) -> Self
+    where
+        T: Into<String>,
+        U: Into<String>,
+        V: Into<String>,
+    {
+        self.parts.push(Part {
+            name: name.into(),
+            file_name: Some(file_name.into()),
+            content_type: Some(content_type.into()),
+            bytes,
+        });
+
+        self
+    }
+
+    /// Adds a field holding `value` as JSON.
+    pub fn json<T: Into<String>, U: Serialize>(
+        mut self,
+        name: T,
+        value: &U,
+    ) -> Result<Self, DhlError> {
+        self.parts.push(Part {
+            name: name.into(),
+            file_name: None,
+            content_type: Some("application/json".to_string()),
+            bytes: serde_json::to_vec(value)?,
+        });
+
+        Ok(self)
+    }
+
+    /// A boundary which none of the parts contains.
+    fn boundary(&self) -> String {
+        loop {
+            let boundary = format!("dhl-wrapper-{}", random_hex());
+            let boundary_bytes = boundary.as_bytes();
+            let contained = self.parts.iter().any(|v| {
+                v.bytes
+                    .windows(boundary_bytes.len())
+                    .any(|v| v == boundary_bytes)
+            });
+            if !contained {
+                return boundary;
+            }
+        }
+    }
+}
+
+/// 128 random bits from the operating system, as hex.
+pub(crate) fn random_hex() -> String {
+    let mut bytes = [0u8; 16];
+    getrandom::getrandom(&mut bytes).expect("the operating system provides no random numbers");
+
+    bytes.iter().map(|v| format!("{:02x}", v)).collect()
+}
@@ -0,0 +1,356 @@
+use super::body::{random_hex, Body};
+use super::{execute, Decoder, Lenient};
+use crate::api::location_finder::{ApiMode, LOCATION_FINDER_BASE_URL, LOCATION_FINDER_SANDBOX_URL};
+use crate::api::shipment_tracking::{SHIPMENT_TRACKING_BASE_URL, SHIPMENT_TRACKING_SANDBOX_URL};
+use crate::auth::{Authenticator, Credentials};
+use crate::cache::{RequestKind, ResponseCache};
+use crate::drift::DriftObserver;
+use crate::error::DhlError;
+use crate::redact::Secret;
+use crate::transport::{HttpRequest, ReqwestTransport, Transport};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
+
+/// HTTP method of a request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Method {
+    Get,
+    Post,
+    Put,
+    Patch,
+    Delete,
+}
+
+impl Method {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Method::Get => "GET",
+            Method::Post => "POST",
+            Method::Put => "PUT",
+            Method::Patch => "PATCH",
+            Method::Delete => "DELETE",
+        }
+    }
+
+    /// Whether sending a request twice has the same effect as sending it once.
+    pub fn is_idempotent(&self) -> bool {
+        !matches!(self, Method::Post | Method::Patch)
+    }
+}
+
+impl FromStr for Method {
+    type Err = DhlError;
+
+    /// Parses a method name like `GET`, as in [HttpRequest](crate::transport::HttpRequest).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        [
+            Method::Get,
+            Method::Post,
+            Method::Put,
+            Method::Patch,
+            Method::Delete,
+        ]
+        .into_iter()
+        .find(|v| v.as_str() == s)
+        .ok_or_else(|| DhlError::Transport(format!("unknown HTTP method `{}`", s)))
+    }
+}
+
+/// Header DHL uses to recognize a repeated POST or PATCH request.
+pub const IDEMPOTENCY_KEY_HEADER: &str = "Idempotency-Key";
+
+/// A new random key for [DhlRequest::idempotency_key](DhlRequest::idempotency_key).
+///
+/// Create it once per operation, e.g. when building a booking request,
+/// so every retry of the operation sends the same key.
+pub fn new_idempotency_key() -> String {
+    random_hex()
+}
+
+/// How a request authenticates itself, with the client's [Credentials](crate::auth::Credentials).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AuthScheme {
+    /// The client's API key in the `DHL-API-Key` header.
+    ApiKey,
+    /// HTTP basic auth.
+    Basic,
+    /// A bearer token from the client's OAuth2 token endpoint.
+    OAuth2,
+    /// Nothing, for public endpoints.
+    None,
+}
+
+/// DHL API products, each with its own base URL.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ApiProduct {
+    ShipmentTracking,
+    LocationFinder,
+}
+
+impl ApiProduct {
+    /// Name of the product in credentials files, like `shipment_tracking`.
+    /// Environment variables use it in upper case, like `SHIPMENT_TRACKING_API_KEY`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            ApiProduct::ShipmentTracking => "shipment_tracking",
+            ApiProduct::LocationFinder => "location_finder",
+        }
+    }
+
+    /// Production base URL of the product.
+    pub fn base_url(&self) -> &'static str {
+        self.base_url_in(ApiMode::Production)
+    }
+
+    /// Base URL of the product in the environment `mode`.
+    pub fn base_url_in(&self, mode: ApiMode) -> &'static str {
+        match (self, mode) {
+            (ApiProduct::ShipmentTracking, ApiMode::Production) => SHIPMENT_TRACKING_BASE_URL,
+            (ApiProduct::ShipmentTracking, ApiMode::Sandbox) => SHIPMENT_TRACKING_SANDBOX_URL,
+            (ApiProduct::LocationFinder, ApiMode::Production) => LOCATION_FINDER_BASE_URL,
+            (ApiProduct::LocationFinder, ApiMode::Sandbox) => LOCATION_FINDER_SANDBOX_URL,
+        }
+    }
+}
+
+/// A request to any DHL API, sent by [DhlClient::send](DhlClient::send).
+///
+/// Adding an API only takes request and response types implementing this trait.
+pub trait DhlRequest {
+    type Response: DeserializeOwned + Serialize + 'static;
+
+    /// The API product the request belongs to, which decides its base URL.
+    fn product(&self) -> ApiProduct;
+
+    /// Defaults to [Method::Get](Method::Get).
+    fn method(&self) -> Method {
+        Method::Get
+    }
+
+    /// Path of the request relative to the product's base URL, like `/shipments`.
+    fn path(&self) -> String;
+
+    /// Query parameters, percent-encoded by [url](DhlRequest::url).
+    fn query(&self) -> Result<Vec<(String, String)>, DhlError> {
+        Ok(Vec::new())
+    }
+
+    /// Body of the request, if it has one, e.g. [Body::json(self)](Body::json).
+    fn body(&self) -> Result<Option<Body>, DhlError> {
+        Ok(None)
+    }
+
+    /// Sent in the `Idempotency-Key` header, so a POST or PATCH can be retried safely,
+    /// see [Retry](crate::transport::retry::Retry).
+    fn idempotency_key(&self) -> Option<String> {
+        None
+    }
+
+    /// Defaults to [AuthScheme::ApiKey](AuthScheme::ApiKey).
+    fn auth(&self) -> AuthScheme {
+        AuthScheme::ApiKey
+    }
+
+    /// Kind of the request, used to look up its TTL in a [CachePolicy](crate::cache::CachePolicy).
+    fn kind(&self) -> RequestKind {
+        RequestKind::Other
+    }
+
+    /// Checks the request against DHL's constraints, listing every violated one.
+    /// Run by [DhlClient::send](DhlClient::send) before anything is sent.
+    fn validate(&self) -> Result<(), DhlError> {
+        Ok(())
+    }
+
+    /// Whether a response will never change again, so it can be cached forever.
+    fn is_final(_response: &Self::Response) -> bool {
+        false
+    }
+
+    /// Decodes a response part by part, for [lenient](DhlClient::lenient) clients.
+    /// Returning `None` reports the error of decoding it as a whole.
+    fn decode_lenient(_value: Value) -> Option<Self::Response> {
+        None
+    }
+
+    /// Full URL of the request, with `base_url` in front of its path and
+    /// the `application/x-www-form-urlencoded` query.
+    fn url(&self, base_url: &str) -> Result<String, DhlError> {
+        let query = form_urlencoded::Serializer::new(String::new())
+            .extend_pairs(self.query()?)
+            .finish();
+
+        match query.is_empty() {
+            true => Ok(format!("{}{}", base_url, self.path())),
+            false => Ok(format!("{}{}?{}", base_url, self.path(), query)),
+        }
+    }
+}
+
+/// Client sending [DhlRequests](DhlRequest) of every API product.
+///
+/// # Example
+///
+/// ```
+/// # #[tokio::main]
+/// # async fn main() {
+/// # use dhl_wrapper::api::{location_finder::*, shipment_tracking::*, *};
+/// # use dhl_wrapper::transport::cassette::Cassette;
+/// let client = DhlClient::new("your_api_token");
+/// # let client = client.transport(Cassette::replay("tests/cassettes/shipment_tracking.json").unwrap());
+///
+/// let request = GetShipmentTracking::new("00340434161094042557");
+/// let response = client.send(request).await.unwrap();
+///
+/// assert_eq!(response.shipments.is_empty(), false);
+/// # }
+/// ```
+pub struct DhlClient {
+    authenticator: Authenticator,
+    base_urls: HashMap<ApiProduct, String>,
+    cache: Option<ResponseCache>,
+    transport: Arc<dyn Transport>,
+    drift_observer: Option<Arc<dyn DriftObserver>>,
+    lenient: bool,
+}
+
+impl DhlClient {
+    /// Creates a new client calling the production APIs with an API key.
+    pub fn new<T: Into<Secret>>(api_key: T) -> Self {
+        DhlClient::with_credentials(Credentials::api_key(api_key))
+    }
+
+    /// Creates a new client calling the production APIs with any kind of credentials.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use dhl_wrapper::api::DhlClient;
+    /// # use dhl_wrapper::auth::*;
+    /// let client = DhlClient::with_credentials(Credentials::api_key("your_api_token"))
+    ///     .credentials(Credentials::basic("your_user", "your_password"))
+    ///     .credentials(Credentials::OAuth2(OAuth2::client_credentials(
+    ///         "https://api-eu.dhl.com/parcel/de/account/auth/ropc/v1/token",
+    ///         "your_client_id",
+    ///         "your_client_secret",
+    ///     )));
+    /// ```
+    pub fn with_credentials(credentials: Credentials) -> Self {
+        let mut authenticator = Authenticator::default();
+        authenticator.add(credentials);
+
+        DhlClient {
+            authenticator,
+            base_urls: HashMap::new(),
+            cache: None,
+            transport: Arc::new(ReqwestTransport::new()),
+            drift_observer: None,
+            lenient: false,
+        }
+    }
+
+    /// Adds credentials for another [AuthScheme](AuthScheme),
+    /// replacing those for the same scheme.
+    pub fn credentials(mut self, credentials: Credentials) -> Self {
+        self.authenticator.add(credentials);
+
+        self
+    }
+
+    /// Overrides the base URL of `product`, e.g. to call a sandbox or a mock server.
+    pub fn base_url<T: Into<String>>(mut self, product: ApiProduct, base_url: T) -> Self {
+        let base_url = base_url.into().trim_end_matches('/').to_string();
+        self.base_urls.insert(product, base_url);
+
+        self
+    }
+
+    /// Caches responses of GET requests in the given [ResponseCache](crate::cache::ResponseCache).
+    pub fn cache(mut self, cache: ResponseCache) -> Self {
+        self.cache = Some(cache);
+
+        self
+    }
+
+    /// Sends requests through the given [Transport](crate::transport::Transport)
+    /// instead of a [ReqwestTransport](crate::transport::ReqwestTransport).
+    pub fn transport<T: Transport + 'static>(mut self, transport: T) -> Self {
+        self.transport = Arc::new(transport);
+
+        self
+    }
+
+    /// Tells `observer` about every difference between decoded responses and the models,
+    /// like unknown fields, see [SchemaDrift](crate::drift::SchemaDrift).
+    pub fn drift_observer<T: DriftObserver + 'static>(mut self, observer: T) -> Self {
+        self.drift_observer = Some(Arc::new(observer));
+
+        self
+    }
+
+    /// Decodes responses part by part if they fail to decode as a whole,
+    /// for requests supporting it, see [DhlRequest::decode_lenient](DhlRequest::decode_lenient).
+    pub fn lenient(mut self, lenient: bool) -> Self {
+        self.lenient = lenient;
+
+        self
+    }
+
+    /// Base URL requests of `product` are sent to.
+    pub fn base_url_of(&self, product: ApiProduct) -> &str {
+        self.base_urls
+            .get(&product)
+            .map_or(product.base_url(), |v| v.as_str())
+    }
+
+    /// Validates and sends a request of any API product.
+    pub async fn send<T: DhlRequest>(&self, request: T) -> Result<T::Response, DhlError> {
+        request.validate()?;
+
+        let mut headers = self
+            .authenticator
+            .headers(request.auth(), &self.transport)
+            .await?;
+        if let Some(v) = request.idempotency_key() {
+            headers.push((IDEMPOTENCY_KEY_HEADER.to_string(), v));
+        }
+        let body = request.body()?;
+        if let Some(v) = &body {
+            headers.push(("Content-Type".to_string(), v.content_type.clone()));
+        }
+        let method = request.method();
+        let http_request = HttpRequest {
+            method: method.as_str().to_string(),
+            url: request.url(self.base_url_of(request.product()))?,
+            headers,
+            body: body.map(|v| v.bytes),
+        };
+
+        let transport = self
+            .authenticator
+            .transport(request.auth(), &self.transport);
+        let cache = match method {
+            Method::Get => self.cache.as_ref(),
+            _ => None,
+        };
+
+        execute(
+            &transport,
+            http_request,
+            request.kind(),
+            cache,
+            Decoder {
+                is_final: T::is_final,
+                lenient: self
+                    .lenient
+                    .then_some(T::decode_lenient as Lenient<T::Response>),
+                drift_observer: self.drift_observer.clone(),
+            },
+        )
+        .await
+    }
+}
@@ -0,0 +1,66 @@
+use super::location_finder::ApiMode;
+
+/// Which of DHL's environments an API calls.
+///
+/// An [ApiMode](ApiMode) decides it at runtime. [Sandbox](Sandbox) and [Production](Production)
+/// decide it at compile time, so functions can require a client of one environment.
+///
+/// # Example
+///
+/// ```
+/// # use dhl_wrapper::api::location_finder::*;
+/// # use dhl_wrapper::api::*;
+/// fn production_only(api: &LocationFinderApi<Production>) {
+///     assert_eq!(api.api_mode(), ApiMode::Production);
+/// }
+///
+/// let api = LocationFinderApi::new(Production, "your_api_token");
+/// production_only(&api);
+/// ```
+///
+/// Handing it a client of the sandbox fails to compile:
+///
+/// ```compile_fail
+/// # use dhl_wrapper::api::location_finder::*;
+/// # use dhl_wrapper::api::*;
+/// # fn production_only(api: &LocationFinderApi<Production>) {}
+/// let api = LocationFinderApi::new(Sandbox, "your_sandbox_token");
+/// production_only(&api);
+/// ```
+///
+/// Neither can their base URL be changed, only that of clients choosing it at runtime:
+///
+/// ```compile_fail
+/// # use dhl_wrapper::api::location_finder::*;
+/// # use dhl_wrapper::api::*;
+/// let api = LocationFinderApi::new(Production, "your_api_token").base_url("http://localhost:8080");
+/// ```
+pub trait Environment: Copy + Send + Sync + 'static {
+    fn api_mode(&self) -> ApiMode;
+}
+
+/// DHL's sandbox, known at compile time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Sandbox;
+
+/// DHL's production environment, known at compile time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Production;
+
+impl Environment for ApiMode {
+    fn api_mode(&self) -> ApiMode {
+        *self
+    }
+}
+
+impl Environment for Sandbox {
+    fn api_mode(&self) -> ApiMode {
+        ApiMode::Sandbox
+    }
+}
+
+impl Environment for Production {
+    fn api_mode(&self) -> ApiMode {
+        ApiMode::Production
+    }
+}
@@ -0,0 +1,129 @@
+use super::{GetLocationById, LocationFinderApi, ServicePoint};
+use crate::api::Environment;
+use crate::error::DhlError;
+use futures::stream::{self, StreamExt};
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Result of resolving many location ids at once.
+/// Every requested id ends up in exactly one of the two maps.
+#[derive(Debug, Default)]
+pub struct BulkLocations {
+    /// Successfully resolved locations by id.
+    pub locations: HashMap<String, ServicePoint>,
+    /// Errors by id for locations that could not be resolved.
+    pub failures: HashMap<String, DhlError>,
+}
+
+/// Time-limited in-memory cache of locations by id.
+pub(crate) struct LocationCache {
+    ttl: Duration,
+    entries: Mutex<HashMap<String, (Instant, ServicePoint)>>,
+}
+
+impl LocationCache {
+    pub(crate) fn new(ttl: Duration) -> Self {
+        LocationCache {
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn get(&self, id: &str) -> Option<ServicePoint> {
+        let mut entries = self.entries.lock().expect("location cache lock poisoned");
+        match entries.get(id) {
+            Some((stored_at, location)) if stored_at.elapsed() < self.ttl => Some(location.clone()),
+            Some(_) => {
+                entries.remove(id);
+                None
+            }
+            None => None,
+        }
+    }
+
+    fn insert(&self, id: String, location: ServicePoint) {
+        if self.ttl.is_zero() {
+            return;
+        }
+
+        self.entries
+            .lock()
+            .expect("location cache lock poisoned")
+            .insert(id, (Instant::now(), location));
+    }
+}
+
+impl<E: Environment> LocationFinderApi<E> {
+    /// Resolves many location ids (see [ServicePointLocationId](super::ServicePointLocationId))
+    /// concurrently. Repeated ids are only requested once and locations are
+    /// served from a cache while they are younger than the
+    /// [configured TTL](LocationFinderApi::location_cache_ttl).
+    ///
+    /// Requests for the other locations go through the API's [cache](LocationFinderApi::cache), if it has one.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # use dhl_wrapper::api::location_finder::*;
+    /// # use std::time::Duration;
+    /// let api = LocationFinderApi::new(ApiMode::Production, "your_api_token")
+    ///     .location_cache_ttl(Duration::from_secs(60 * 60));
+    ///
+    /// let result = api
+    ///     .get_locations_by_ids(vec!["8003-4101479", "8003-4101479", "8003-0000000"])
+    ///     .await;
+    ///
+    /// for (id, location) in result.locations {
+    ///     println!("{}: {:?}", id, location.opening_hours);
+    /// }
+    /// for (id, error) in result.failures {
+    ///     println!("{} failed: {}", id, error);
+    /// }
+    /// # }
+    /// ```
+    pub async fn get_locations_by_ids<I, T>(&self, ids: I) -> BulkLocations
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        let mut seen = HashSet::new();
+        let mut result = BulkLocations::default();
+        let mut missing = Vec::new();
+
+        for id in ids.into_iter().map(|v| v.into()) {
+            if !seen.insert(id.clone()) {
+                continue;
+            }
+            match self.location_cache.get(&id) {
+                Some(location) => {
+                    result.locations.insert(id, location);
+                }
+                None => missing.push(id),
+            }
+        }
+
+        let mut responses = stream::iter(missing)
+            .map(|id| async move {
+                let response = self.send(GetLocationById::new(id.clone())).await;
+                (id, response)
+            })
+            .buffer_unordered(self.max_concurrent_requests);
+
+        while let Some((id, response)) = responses.next().await {
+            match response {
+                Ok(location) => {
+                    self.location_cache.insert(id.clone(), location.clone());
+                    result.locations.insert(id, location);
+                }
+                Err(e) => {
+                    result.failures.insert(id, e);
+                }
+            }
+        }
+
+        result
+    }
+}
@@ -0,0 +1,329 @@
+use super::{GetLocationByKeywordId, LocationFinderApi, ServicePoint};
+use crate::api::CountryCode;
+use crate::api::Environment;
+use crate::error::DhlError;
+use std::fmt;
+use std::str::FromStr;
+use thiserror::Error;
+
+/// Keywords DHL uses to name their service point locations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Keyword {
+    Packstation,
+    Postfiliale,
+    Paketshop,
+    Poststation,
+}
+
+impl Keyword {
+    /// The keyword as DHL spells it in [ServicePointLocation](super::ServicePointLocation)s.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Keyword::Packstation => "Packstation",
+            Keyword::Postfiliale => "Postfiliale",
+            Keyword::Paketshop => "Paketshop",
+            Keyword::Poststation => "Poststation",
+        }
+    }
+
+    /// Matches the spellings customers commonly use, e.g. `PACKSTATION`, `Pack-Station` or `Filiale`.
+    fn from_compact(value: &str) -> Option<Self> {
+        match value {
+            "packstation" | "paketstation" => Some(Keyword::Packstation),
+            "postfiliale" | "filiale" => Some(Keyword::Postfiliale),
+            "paketshop" | "postshop" => Some(Keyword::Paketshop),
+            "poststation" => Some(Keyword::Poststation),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Keyword {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Reasons why a human-written service point reference could not be parsed.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum KeywordReferenceError {
+    #[error("no known keyword like Packstation or Postfiliale found in {0:?}")]
+    MissingKeyword(String),
+    #[error("no keyword id found after the keyword in {0:?}")]
+    MissingKeywordId(String),
+    #[error("a postal code is required to look up a location by keyword id")]
+    MissingPostalCode,
+    #[error("{0:?} is not a valid postal code")]
+    InvalidPostalCode(String),
+}
+
+/// A reference to a service point location as customers write it into address forms,
+/// like `Packstation 433, 20357 Hamburg` or `Postfiliale 502`.
+///
+/// # Example
+///
+/// ```
+/// # use dhl_wrapper::api::location_finder::keyword::*;
+/// let reference: KeywordReference = "Packstation 433, 20357 Hamburg".parse().unwrap();
+///
+/// assert_eq!(reference.keyword, Keyword::Packstation);
+/// assert_eq!(reference.keyword_id, "433");
+/// assert_eq!(reference.postal_code.as_deref(), Some("20357"));
+/// assert_eq!(reference.address_locality.as_deref(), Some("Hamburg"));
+///
+/// let request = reference.to_request().unwrap();
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeywordReference {
+    pub keyword: Keyword,
+    pub keyword_id: String,
+    pub country_code: CountryCode,
+    pub postal_code: Option<String>,
+    pub address_locality: Option<String>,
+}
+
+impl KeywordReference {
+    /// Parses a reference to a location in Germany, where the keywords are used.
+    pub fn parse(input: &str) -> Result<Self, KeywordReferenceError> {
+        Self::parse_with_country(input, CountryCode::De)
+    }
+
+    /// Parses a reference to a location in the given country.
+    pub fn parse_with_country(
+        input: &str,
+        country_code: CountryCode,
+    ) -> Result<Self, KeywordReferenceError> {
+        // tokens remember which comma separated part of the input they are in
+        let tokens = input
+            .split([',', ';'])
+            .enumerate()
+            .flat_map(|(part, v)| v.split_whitespace().map(move |v| (part, v)))
+            .collect::<Vec<(usize, &str)>>();
+        let compact = tokens
+            .iter()
+            .map(|(_, v)| v.to_lowercase().replace(['-', '.'], ""))
+            .collect::<Vec<String>>();
+
+        // the keyword may be split across two tokens, as in "Pack Station"
+        let (keyword, keyword_start, keyword_end) = (0..compact.len())
+            .find_map(|i| {
+                if let Some(next) = compact.get(i + 1) {
+                    if let Some(keyword) = Keyword::from_compact(&format!("{}{}", compact[i], next))
+                    {
+                        return Some((keyword, i, i + 2));
+                    }
+                }
+                Keyword::from_compact(&compact[i]).map(|keyword| (keyword, i, i + 1))
+            })
+            .ok_or_else(|| KeywordReferenceError::MissingKeyword(input.to_string()))?;
+
+        let mut id_index = keyword_end;
+        while let Some(v) = compact.get(id_index) {
+            if matches!(v.as_str(), "nr" | "no" | "nummer" | "#") {
+                id_index += 1;
+            } else {
+                break;
+            }
+        }
+        let keyword_id = compact
+            .get(id_index)
+            .map(|v| v.trim_start_matches('#'))
+            .filter(|v| is_keyword_id(v))
+            .map(|v| v.to_string())
+            .ok_or_else(|| KeywordReferenceError::MissingKeywordId(input.to_string()))?;
+
+        let rest = tokens
+            .iter()
+            .enumerate()
+            .filter(|(i, (_, v))| {
+                (*i < keyword_start || *i > id_index) && !v.eq_ignore_ascii_case("dhl")
+            })
+            .map(|(_, v)| *v)
+            .collect::<Vec<(usize, &str)>>();
+
+        // street numbers contain digits too, so the postal code is the first valid one,
+        // which may span two tokens as in "1012 AB" or "SW1A 1AA"
+        let postal_code = (0..rest.len()).find_map(|i| {
+            let (part, first) = rest[i];
+            if !has_digit(first) {
+                return None;
+            }
+            let pair = rest
+                .get(i + 1)
+                .filter(|(next_part, _)| *next_part == part)
+                .map(|(_, next)| (2, format!("{} {}", first, next)));
+            pair.into_iter()
+                .chain(Some((1, first.to_string())))
+                .find(|(_, v)| country_code.is_valid_postal_code(v))
+                .map(|(len, v)| (i, len, v))
+        });
+
+        let address_locality = match &postal_code {
+            // the locality is written next to the postal code, usually after it
+            Some((i, len, _)) => {
+                let part = rest[*i].0;
+                let after = rest[i + len..]
+                    .iter()
+                    .take_while(|(v, _)| *v == part)
+                    .map(|(_, v)| *v)
+                    .collect::<Vec<&str>>();
+                if after.is_empty() {
+                    rest[..*i]
+                        .iter()
+                        .filter(|(v, word)| *v == part && !has_digit(word))
+                        .map(|(_, v)| *v)
+                        .collect::<Vec<&str>>()
+                } else {
+                    after
+                }
+            }
+            None => {
+                // a part starting with a number is taken for a malformed postal code,
+                // other parts with numbers for street addresses
+                if let Some((_, v)) = rest.iter().enumerate().find_map(|(i, (part, v))| {
+                    let starts_part = i == 0 || rest[i - 1].0 != *part;
+                    (starts_part && has_digit(v)).then_some(rest[i])
+                }) {
+                    return Err(KeywordReferenceError::InvalidPostalCode(v.to_string()));
+                }
+                rest.iter()
+                    .filter(|(part, _)| !rest.iter().any(|(p, v)| p == part && has_digit(v)))
+                    .map(|(_, v)| *v)
+                    .collect::<Vec<&str>>()
+            }
+        }
+        .join(" ");
+        let postal_code = postal_code.map(|(_, _, v)| v);
+
+        Ok(KeywordReference {
+            keyword,
+            keyword_id,
+            country_code,
+            postal_code,
+            address_locality: if address_locality.is_empty() {
+                None
+            } else {
+                Some(address_locality)
+            },
+        })
+    }
+
+    /// Sets the postal code, e.g. when customers enter it into a separate form field.
+    pub fn postal_code<T: Into<String>>(mut self, value: Option<T>) -> Self {
+        self.postal_code = value.map(|v| v.into());
+
+        self
+    }
+
+    /// Builds the request looking up the referenced location.
+    /// Fails if the reference has no postal code, since DHL requires one.
+    pub fn to_request(&self) -> Result<GetLocationByKeywordId, KeywordReferenceError> {
+        let postal_code = self
+            .postal_code
+            .clone()
+            .ok_or(KeywordReferenceError::MissingPostalCode)?;
+
+        Ok(GetLocationByKeywordId::new(
+            self.keyword_id.clone(),
+            self.country_code,
+            postal_code,
+        ))
+    }
+}
+
+impl FromStr for KeywordReference {
+    type Err = KeywordReferenceError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        KeywordReference::parse(s)
+    }
+}
+
+fn has_digit(value: &str) -> bool {
+    value.chars().any(|c| c.is_ascii_digit())
+}
+
+/// Keyword ids have up to four digits, which sets them apart from (most) postal codes.
+fn is_keyword_id(value: &str) -> bool {
+    (1..=4).contains(&value.len()) && value.chars().all(|c| c.is_ascii_digit())
+}
+
+/// Reasons why a [KeywordReference](KeywordReference) could not be matched to a service point location.
+#[derive(Error, Debug)]
+pub enum KeywordResolutionError {
+    #[error("invalid reference: {0}")]
+    InvalidReference(#[from] KeywordReferenceError),
+    #[error("DHL knows no location with this keyword id and postal code")]
+    NotFound,
+    #[error("expected a {expected} but found a {found}")]
+    KeywordMismatch { expected: Keyword, found: String },
+    #[error("expected keyword id {expected} but found {found}")]
+    KeywordIdMismatch { expected: String, found: String },
+    #[error("expected postal code {expected} but found {found:?}")]
+    PostalCodeMismatch {
+        expected: String,
+        found: Option<String>,
+    },
+    #[error("request failed: {0}")]
+    Request(DhlError),
+}
+
+impl<E: Environment> LocationFinderApi<E> {
+    /// Looks up the location a [KeywordReference](KeywordReference) refers to
+    /// and verifies that keyword, keyword id and postal code match.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # use dhl_wrapper::api::location_finder::{keyword::*, *};
+    /// let api = LocationFinderApi::new(ApiMode::Production, "your_api_token");
+    ///
+    /// let reference = KeywordReference::parse("Packstation 433, 20357 Hamburg").unwrap();
+    /// let service_point = api.resolve_keyword_reference(&reference).await.unwrap();
+    /// # }
+    /// ```
+    pub async fn resolve_keyword_reference(
+        &self,
+        reference: &KeywordReference,
+    ) -> Result<ServicePoint, KeywordResolutionError> {
+        let request = reference.to_request()?;
+
+        let service_point = match self.send(request).await {
+            Ok(v) => v,
+            Err(DhlError::ResponseNotOk { status: 404, .. }) => {
+                return Err(KeywordResolutionError::NotFound)
+            }
+            Err(e) => return Err(KeywordResolutionError::Request(e)),
+        };
+
+        let location = &service_point.location;
+        if !location
+            .keyword
+            .eq_ignore_ascii_case(reference.keyword.as_str())
+        {
+            return Err(KeywordResolutionError::KeywordMismatch {
+                expected: reference.keyword,
+                found: location.keyword.clone(),
+            });
+        }
+        if location.keyword_id != reference.keyword_id {
+            return Err(KeywordResolutionError::KeywordIdMismatch {
+                expected: reference.keyword_id.clone(),
+                found: location.keyword_id.clone(),
+            });
+        }
+        if let Some(expected) = &reference.postal_code {
+            let found = &service_point.place.address.postal_code;
+            if found.as_ref() != Some(expected) {
+                return Err(KeywordResolutionError::PostalCodeMismatch {
+                    expected: expected.clone(),
+                    found: found.clone(),
+                });
+            }
+        }
+
+        Ok(service_point)
+    }
+}
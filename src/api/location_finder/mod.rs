@@ -0,0 +1,424 @@
+use super::validation::Validator;
+use super::{query_params, ApiProduct, DhlClient, DhlRequest, Environment};
+use crate::auth::{CredentialsProvider, EnvCredentials};
+use crate::cache::{RequestKind, ResponseCache};
+use crate::drift::DriftObserver;
+use crate::error::DhlError;
+use crate::redact::Secret;
+use crate::transport::Transport;
+use async_trait::async_trait;
+use std::time::Duration;
+
+pub mod bulk;
+pub mod keyword;
+mod models;
+pub mod recommendation;
+
+pub use models::*;
+
+/// API struct for calling DHL's "Location Finder - Unified" API.
+///
+/// The [Environment](crate::api::Environment) it calls is an [ApiMode](ApiMode) chosen at runtime,
+/// or [Sandbox](crate::api::Sandbox) or [Production](crate::api::Production) if it is part of the type.
+pub struct LocationFinderApi<E: Environment = ApiMode> {
+    client: DhlClient,
+    location_cache: bulk::LocationCache,
+    max_concurrent_requests: usize,
+    environment: E,
+}
+
+impl<E: Environment> LocationFinderApi<E> {
+    /// Creates a new API.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use dhl_wrapper::api::location_finder::*;
+    /// # use dhl_wrapper::api::Production;
+    /// let api = LocationFinderApi::new(
+    ///     ApiMode::Production,
+    ///     "your_api_token"
+    /// );
+    ///
+    /// // a LocationFinderApi<Production>
+    /// let api = LocationFinderApi::new(Production, "your_api_token");
+    /// ```
+    pub fn new<T: Into<Secret>>(environment: E, api_key: T) -> Self {
+        LocationFinderApi {
+            client: DhlClient::new(api_key).base_url(
+                ApiProduct::LocationFinder,
+                ApiProduct::LocationFinder.base_url_in(environment.api_mode()),
+            ),
+            location_cache: bulk::LocationCache::new(Duration::from_secs(15 * 60)),
+            max_concurrent_requests: 4,
+            environment,
+        }
+    }
+
+    /// Creates a new API with the key in the `LOCATION_FINDER_API_KEY` environment variable,
+    /// or `SANDBOX_LOCATION_FINDER_API_KEY` for the sandbox, see [EnvCredentials](crate::auth::EnvCredentials).
+    pub fn from_env(environment: E) -> Result<Self, DhlError> {
+        LocationFinderApi::from_provider(environment, &EnvCredentials::new())
+    }
+
+    /// Creates a new API with the credentials `provider` has for
+    /// [ApiProduct::LocationFinder](crate::api::ApiProduct::LocationFinder) in `environment`.
+    pub fn from_provider<P: CredentialsProvider + ?Sized>(
+        environment: E,
+        provider: &P,
+    ) -> Result<Self, DhlError> {
+        let credentials =
+            provider.credentials(ApiProduct::LocationFinder, environment.api_mode())?;
+
+        Ok(LocationFinderApi {
+            client: DhlClient::with_credentials(credentials).base_url(
+                ApiProduct::LocationFinder,
+                ApiProduct::LocationFinder.base_url_in(environment.api_mode()),
+            ),
+            location_cache: bulk::LocationCache::new(Duration::from_secs(15 * 60)),
+            max_concurrent_requests: 4,
+            environment,
+        })
+    }
+
+    /// The environment the API calls, unless its [base URL](LocationFinderApi::base_url) was overridden.
+    pub fn api_mode(&self) -> ApiMode {
+        self.environment.api_mode()
+    }
+
+    /// Caches responses in the given [ResponseCache](crate::cache::ResponseCache).
+    pub fn cache(mut self, cache: ResponseCache) -> Self {
+        self.client = self.client.cache(cache);
+
+        self
+    }
+
+    /// Sends requests through the given [Transport](crate::transport::Transport)
+    /// instead of a [ReqwestTransport](crate::transport::ReqwestTransport).
+    pub fn transport<T: Transport + 'static>(mut self, transport: T) -> Self {
+        self.client = self.client.transport(transport);
+
+        self
+    }
+
+    /// Sets how long locations fetched by [get_locations_by_ids](LocationFinderApi::get_locations_by_ids)
+    /// are cached. Defaults to 15 minutes, `Duration::ZERO` disables the cache.
+    pub fn location_cache_ttl(mut self, ttl: Duration) -> Self {
+        self.location_cache = bulk::LocationCache::new(ttl);
+
+        self
+    }
+
+    /// Sets how many requests [get_locations_by_ids](LocationFinderApi::get_locations_by_ids)
+    /// sends at the same time. Defaults to 4.
+    pub fn max_concurrent_requests(mut self, max_concurrent_requests: usize) -> Self {
+        self.max_concurrent_requests = max_concurrent_requests.max(1);
+
+        self
+    }
+
+    /// Tells `observer` about every difference between decoded responses and the models,
+    /// like unknown fields, see [SchemaDrift](crate::drift::SchemaDrift).
+    pub fn drift_observer<T: DriftObserver + 'static>(mut self, observer: T) -> Self {
+        self.client = self.client.drift_observer(observer);
+
+        self
+    }
+
+    /// Uses the API to send a request.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let api_key = "your_api_token";
+    /// # use dhl_wrapper::api::{location_finder::*, *};
+    /// # use dhl_wrapper::transport::cassette::Cassette;
+    /// let api = LocationFinderApi::new(ApiMode::Production, api_key);
+    /// # let api = api.transport(Cassette::replay("tests/cassettes/location_finder.json").unwrap());
+    ///     
+    /// // Get service point locations by address
+    /// let request = GetLocationsByAddress::new(CountryCode::De)
+    ///         .address_locality(Some("Hamburg"))
+    ///         .postal_code(Some("20355"))
+    ///         .street_address(Some("Kohlhöfen 16"));
+    /// let response = api.send(request).await.unwrap();
+    ///
+    /// assert_eq!(response.locations.is_empty(), false);
+    ///     
+    /// // Get service point locations by coordinates
+    /// let request = GetLocationsByGeo::new(53.575264, 9.954053);
+    /// let response = api.send(request).await.unwrap();
+    ///
+    /// assert_eq!(response.locations.is_empty(), false);
+    ///     
+    /// // Get service point location by keyword id
+    /// let request = GetLocationByKeywordId::new(
+    ///     "433",
+    ///     CountryCode::De,
+    ///     "20357"
+    /// );
+    /// let response = api.send(request).await.unwrap();
+    ///
+    /// assert_eq!(response.opening_hours.is_empty(), false);
+    ///     
+    /// // Get service point location by id
+    /// let request = GetLocationById::new("8003-4101479");
+    /// let response = api.send(request).await.unwrap();
+    ///
+    /// assert_eq!(response.opening_hours.is_empty(), false);
+    /// # }
+    /// ```
+    pub async fn send<T: LocationFinderRequest>(
+        &self,
+        request: T,
+    ) -> Result<T::Response, DhlError> {
+        self.client.send(request).await
+    }
+}
+
+impl LocationFinderApi<ApiMode> {
+    /// Overrides the base URL derived from the [ApiMode](ApiMode), e.g. to call a mock server in tests.
+    /// APIs of a [Sandbox](crate::api::Sandbox) or [Production](crate::api::Production) type
+    /// always call that environment.
+    pub fn base_url<T: Into<String>>(mut self, base_url: T) -> Self {
+        self.client = self.client.base_url(ApiProduct::LocationFinder, base_url);
+
+        self
+    }
+}
+
+/// Largest search radius in meters DHL accepts.
+pub const MAX_RADIUS: u32 = 25_000;
+
+/// Largest number of locations DHL returns at once.
+pub const MAX_LIMIT: u32 = 50;
+
+/// Base URL of DHL's "Location Finder - Unified" API.
+pub const LOCATION_FINDER_BASE_URL: &str = "https://api.dhl.com/location-finder/v1";
+
+/// Base URL of the test environment of DHL's "Location Finder - Unified" API.
+pub const LOCATION_FINDER_SANDBOX_URL: &str = "https://api-sandbox.dhl.com/location-finder/v1";
+
+/// The ApiMode decides which of DHL's environments is called.
+/// DHL offers a production and a sandbox API
+/// for their "Location Finder - Unified" and "Shipment Tracking - Unified" APIs,
+/// see [ApiProduct::base_url_in](crate::api::ApiProduct::base_url_in) for their base URLs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ApiMode {
+    Sandbox,
+    Production,
+}
+
+/// Finding service point locations, as done by the [LocationFinderApi](LocationFinderApi).
+///
+/// Depend on this trait instead of the API struct to swap in a
+/// [FakeLocationFinder](crate::fake::FakeLocationFinder) in unit tests.
+#[async_trait]
+pub trait FindLocations: Send + Sync {
+    async fn find_by_address(
+        &self,
+        request: GetLocationsByAddress,
+    ) -> Result<GetLocationsResponse, DhlError>;
+
+    async fn find_by_geo(
+        &self,
+        request: GetLocationsByGeo,
+    ) -> Result<GetLocationsResponse, DhlError>;
+
+    async fn find_by_keyword_id(
+        &self,
+        request: GetLocationByKeywordId,
+    ) -> Result<GetLocationResponse, DhlError>;
+
+    async fn location_by_id(
+        &self,
+        request: GetLocationById,
+    ) -> Result<GetLocationResponse, DhlError>;
+}
+
+#[async_trait]
+impl<E: Environment> FindLocations for LocationFinderApi<E> {
+    async fn find_by_address(
+        &self,
+        request: GetLocationsByAddress,
+    ) -> Result<GetLocationsResponse, DhlError> {
+        self.send(request).await
+    }
+
+    async fn find_by_geo(
+        &self,
+        request: GetLocationsByGeo,
+    ) -> Result<GetLocationsResponse, DhlError> {
+        self.send(request).await
+    }
+
+    async fn find_by_keyword_id(
+        &self,
+        request: GetLocationByKeywordId,
+    ) -> Result<GetLocationResponse, DhlError> {
+        self.send(request).await
+    }
+
+    async fn location_by_id(
+        &self,
+        request: GetLocationById,
+    ) -> Result<GetLocationResponse, DhlError> {
+        self.send(request).await
+    }
+}
+
+/// A marker trait for the [DhlRequests](crate::api::DhlRequest)
+/// which can be sent via the [LocationFinderApi](LocationFinderApi).
+pub trait LocationFinderRequest: DhlRequest {}
+
+impl LocationFinderRequest for GetLocationsByAddress {}
+impl LocationFinderRequest for GetLocationsByGeo {}
+impl LocationFinderRequest for GetLocationByKeywordId {}
+impl LocationFinderRequest for GetLocationById {}
+
+impl DhlRequest for GetLocationsByAddress {
+    type Response = GetLocationsResponse;
+
+    fn product(&self) -> ApiProduct {
+        ApiProduct::LocationFinder
+    }
+
+    fn path(&self) -> String {
+        "/find-by-address".to_string()
+    }
+
+    fn query(&self) -> Result<Vec<(String, String)>, DhlError> {
+        query_params(self)
+    }
+
+    fn kind(&self) -> RequestKind {
+        RequestKind::LocationsByAddress
+    }
+
+    fn validate(&self) -> Result<(), DhlError> {
+        let mut validator = Validator::default();
+        validator.check(
+            self.address_locality.is_some() || self.postal_code.is_some(),
+            "address_locality",
+            "or postal_code is required",
+        );
+        if let Some(v) = &self.address_locality {
+            validator.not_blank("address_locality", v);
+        }
+        if let Some(v) = &self.postal_code {
+            validator.postal_code("postal_code", self.country_code, v);
+        }
+        if let Some(v) = &self.street_address {
+            validator.not_blank("street_address", v);
+        }
+        validator.range("radius", self.radius, 1, MAX_RADIUS);
+        validator.range("limit", self.limit, 1, MAX_LIMIT);
+
+        validator.finish()
+    }
+}
+
+impl DhlRequest for GetLocationsByGeo {
+    type Response = GetLocationsResponse;
+
+    fn product(&self) -> ApiProduct {
+        ApiProduct::LocationFinder
+    }
+
+    fn path(&self) -> String {
+        "/find-by-geo".to_string()
+    }
+
+    fn query(&self) -> Result<Vec<(String, String)>, DhlError> {
+        query_params(self)
+    }
+
+    fn kind(&self) -> RequestKind {
+        RequestKind::LocationsByGeo
+    }
+
+    fn validate(&self) -> Result<(), DhlError> {
+        let mut validator = Validator::default();
+        validator.coordinates(self.latitude, self.longitude);
+        validator.range("radius", self.radius, 1, MAX_RADIUS);
+        validator.range("limit", self.limit, 1, MAX_LIMIT);
+
+        validator.finish()
+    }
+}
+
+impl DhlRequest for GetLocationByKeywordId {
+    type Response = GetLocationResponse;
+
+    fn product(&self) -> ApiProduct {
+        ApiProduct::LocationFinder
+    }
+
+    fn path(&self) -> String {
+        "/find-by-keyword-id".to_string()
+    }
+
+    fn query(&self) -> Result<Vec<(String, String)>, DhlError> {
+        query_params(self)
+    }
+
+    fn kind(&self) -> RequestKind {
+        RequestKind::LocationByKeywordId
+    }
+
+    fn validate(&self) -> Result<(), DhlError> {
+        let mut validator = Validator::default();
+        validator.not_blank("keyword_id", &self.keyword_id);
+        validator.postal_code("postal_code", self.country_code, &self.postal_code);
+
+        validator.finish()
+    }
+}
+
+impl DhlRequest for GetLocationById {
+    type Response = GetLocationResponse;
+
+    fn product(&self) -> ApiProduct {
+        ApiProduct::LocationFinder
+    }
+
+    fn path(&self) -> String {
+        format!("/locations/{}", self.id)
+    }
+
+    fn kind(&self) -> RequestKind {
+        RequestKind::LocationById
+    }
+
+    fn validate(&self) -> Result<(), DhlError> {
+        let mut validator = Validator::default();
+        validator.not_blank("id", &self.id);
+        validator.check(
+            !self.id.contains(['/', '?', '#']),
+            "id",
+            "must not contain `/`, `?` or `#`",
+        );
+
+        validator.finish()
+    }
+}
+
+pub type ServicePoint = GetLocationResponse;
+
+impl Geo {
+    /// Distance to `other` in meters, using the haversine formula.
+    pub fn distance_to(&self, other: &Geo) -> f64 {
+        let earth_radius = 6_371_000.0;
+        let d_latitude = (other.latitude - self.latitude).to_radians();
+        let d_longitude = (other.longitude - self.longitude).to_radians();
+
+        let a = (d_latitude / 2.0).sin().powi(2)
+            + self.latitude.to_radians().cos()
+                * other.latitude.to_radians().cos()
+                * (d_longitude / 2.0).sin().powi(2);
+
+        earth_radius * 2.0 * a.sqrt().atan2((1.0 - a).sqrt())
+    }
+}
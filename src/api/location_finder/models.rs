@@ -0,0 +1,395 @@
+// @generated by `cargo run -p dhl_codegen` from `openapi/location-finder.json`. Do not edit by hand.
+
+//! Requests and responses of DHL's "Location Finder - Unified" API.
+
+use crate::api::distinct;
+use crate::api::Address;
+use crate::api::CountryCode;
+use crate::api::ServiceType;
+use chrono::NaiveDate;
+use chrono::NaiveTime;
+use serde::{Deserialize, Serialize};
+
+/// Parameters of the `GET /find-by-address` request.
+///
+/// Returns service point locations near an address.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetLocationsByAddress {
+    pub(crate) country_code: CountryCode,
+    pub(crate) address_locality: Option<String>,
+    pub(crate) postal_code: Option<String>,
+    pub(crate) street_address: Option<String>,
+    pub(crate) provider_type: Option<ProviderType>,
+    pub(crate) location_type: Option<Vec<LocationType>>,
+    pub(crate) service_type: Option<Vec<ServiceType>>,
+    pub(crate) radius: Option<u32>,
+    pub(crate) limit: Option<u32>,
+    pub(crate) hide_closed_locations: Option<bool>,
+}
+
+impl GetLocationsByAddress {
+    pub fn new(country_code: CountryCode) -> Self {
+        GetLocationsByAddress {
+            country_code,
+            address_locality: None,
+            postal_code: None,
+            street_address: None,
+            provider_type: None,
+            location_type: None,
+            service_type: None,
+            radius: None,
+            limit: None,
+            hide_closed_locations: None,
+        }
+    }
+
+    /// City or town of the address.
+    pub fn address_locality<T: Into<String>>(mut self, address_locality: Option<T>) -> Self {
+        self.address_locality = address_locality.map(|v| v.into());
+
+        self
+    }
+
+    /// Postal code of the address.
+    pub fn postal_code<T: Into<String>>(mut self, postal_code: Option<T>) -> Self {
+        self.postal_code = postal_code.map(|v| v.into());
+
+        self
+    }
+
+    /// Street and house number of the address.
+    pub fn street_address<T: Into<String>>(mut self, street_address: Option<T>) -> Self {
+        self.street_address = street_address.map(|v| v.into());
+
+        self
+    }
+
+    /// Only finds locations of this provider.
+    pub fn provider_type(mut self, provider_type: Option<ProviderType>) -> Self {
+        self.provider_type = provider_type;
+
+        self
+    }
+
+    pub fn location_type(mut self, location_type: Option<LocationType>) -> Self {
+        self.location_type = location_type.map(|v| vec![v]);
+
+        self
+    }
+
+    /// Finds locations of any of the given types.
+    pub fn location_types(mut self, location_types: Option<Vec<LocationType>>) -> Self {
+        self.location_type = location_types.and_then(distinct);
+
+        self
+    }
+
+    pub fn service_type(mut self, service_type: Option<ServiceType>) -> Self {
+        self.service_type = service_type.map(|v| vec![v]);
+
+        self
+    }
+
+    /// Finds locations offering all of the given services.
+    pub fn service_types(mut self, service_types: Option<Vec<ServiceType>>) -> Self {
+        self.service_type = service_types.and_then(distinct);
+
+        self
+    }
+
+    /// Search radius in meters.
+    pub fn radius(mut self, radius: Option<u32>) -> Self {
+        self.radius = radius;
+
+        self
+    }
+
+    /// Maximum number of locations returned.
+    pub fn limit(mut self, limit: Option<u32>) -> Self {
+        self.limit = limit;
+
+        self
+    }
+
+    /// Leaves out locations which are closed for longer.
+    pub fn hide_closed_locations(mut self, hide_closed_locations: Option<bool>) -> Self {
+        self.hide_closed_locations = hide_closed_locations;
+
+        self
+    }
+}
+
+/// Parameters of the `GET /find-by-geo` request.
+///
+/// Returns service point locations near coordinates.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetLocationsByGeo {
+    pub(crate) latitude: f64,
+    pub(crate) longitude: f64,
+    pub(crate) provider_type: Option<ProviderType>,
+    pub(crate) location_type: Option<Vec<LocationType>>,
+    pub(crate) service_type: Option<Vec<ServiceType>>,
+    pub(crate) radius: Option<u32>,
+    pub(crate) limit: Option<u32>,
+    pub(crate) hide_closed_locations: Option<bool>,
+}
+
+impl GetLocationsByGeo {
+    pub fn new(latitude: f64, longitude: f64) -> Self {
+        GetLocationsByGeo {
+            latitude,
+            longitude,
+            provider_type: None,
+            location_type: None,
+            service_type: None,
+            radius: None,
+            limit: None,
+            hide_closed_locations: None,
+        }
+    }
+
+    /// Only finds locations of this provider.
+    pub fn provider_type(mut self, provider_type: Option<ProviderType>) -> Self {
+        self.provider_type = provider_type;
+
+        self
+    }
+
+    pub fn location_type(mut self, location_type: Option<LocationType>) -> Self {
+        self.location_type = location_type.map(|v| vec![v]);
+
+        self
+    }
+
+    /// Finds locations of any of the given types.
+    pub fn location_types(mut self, location_types: Option<Vec<LocationType>>) -> Self {
+        self.location_type = location_types.and_then(distinct);
+
+        self
+    }
+
+    pub fn service_type(mut self, service_type: Option<ServiceType>) -> Self {
+        self.service_type = service_type.map(|v| vec![v]);
+
+        self
+    }
+
+    /// Finds locations offering all of the given services.
+    pub fn service_types(mut self, service_types: Option<Vec<ServiceType>>) -> Self {
+        self.service_type = service_types.and_then(distinct);
+
+        self
+    }
+
+    /// Search radius in meters.
+    pub fn radius(mut self, radius: Option<u32>) -> Self {
+        self.radius = radius;
+
+        self
+    }
+
+    /// Maximum number of locations returned.
+    pub fn limit(mut self, limit: Option<u32>) -> Self {
+        self.limit = limit;
+
+        self
+    }
+
+    /// Leaves out locations which are closed for longer.
+    pub fn hide_closed_locations(mut self, hide_closed_locations: Option<bool>) -> Self {
+        self.hide_closed_locations = hide_closed_locations;
+
+        self
+    }
+}
+
+/// Parameters of the `GET /find-by-keyword-id` request.
+///
+/// Returns the service point location with a keyword id, like a Packstation number.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetLocationByKeywordId {
+    pub(crate) keyword_id: String,
+    pub(crate) country_code: CountryCode,
+    pub(crate) postal_code: String,
+}
+
+impl GetLocationByKeywordId {
+    pub fn new<T: Into<String>, U: Into<String>>(
+        keyword_id: T,
+        country_code: CountryCode,
+        postal_code: U,
+    ) -> Self {
+        GetLocationByKeywordId {
+            keyword_id: keyword_id.into(),
+            country_code,
+            postal_code: postal_code.into(),
+        }
+    }
+}
+
+/// Parameters of the `GET /locations/{id}` request.
+///
+/// Returns the service point location with an id.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetLocationById {
+    pub(crate) id: String,
+}
+
+impl GetLocationById {
+    pub fn new<T: Into<String>>(id: T) -> Self {
+        GetLocationById { id: id.into() }
+    }
+}
+
+/// Successful response holding a list of service point locations.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetLocationsResponse {
+    pub locations: Vec<GetLocationResponse>,
+}
+
+/// Successful response holding one service point location.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetLocationResponse {
+    pub url: String,
+    pub location: ServicePointLocation,
+    pub name: String,
+    pub distance: Option<u32>,
+    pub place: Place,
+    #[serde(default)]
+    pub opening_hours: Vec<OpeningHours>,
+    #[serde(default)]
+    pub closure_periods: Vec<ClosurePeriod>,
+    #[serde(default)]
+    pub service_types: Vec<ServiceType>,
+    #[serde(default)]
+    pub average_capacity_day_of_week: Vec<WeekdayCapacity>,
+    pub available_capacity: Option<Capacity>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServicePointLocation {
+    pub ids: Vec<ServicePointLocationId>,
+    #[serde(default)]
+    pub keyword: String,
+    #[serde(default)]
+    pub keyword_id: String,
+    pub r#type: ServicePointLocationType,
+    pub lean_locker: Option<bool>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServicePointLocationId {
+    pub location_id: String,
+    pub provider: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ServicePointLocationType {
+    Servicepoint,
+    Locker,
+    Postoffice,
+    Postbank,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Place {
+    pub address: Address,
+    pub geo: Geo,
+    pub contained_in_place: Option<ContainedInPlace>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Geo {
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContainedInPlace {
+    pub name: String,
+}
+
+/// Opening hours of a service point.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpeningHours {
+    pub opens: NaiveTime,
+    pub closes: NaiveTime,
+    pub day_of_week: Weekday,
+}
+
+/// Weekday, as a link to schema.org.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Weekday {
+    #[serde(rename = "http://schema.org/Monday", alias = "Monday")]
+    Mon,
+    #[serde(rename = "http://schema.org/Tuesday", alias = "Tuesday")]
+    Tue,
+    #[serde(rename = "http://schema.org/Wednesday", alias = "Wednesday")]
+    Wed,
+    #[serde(rename = "http://schema.org/Thursday", alias = "Thursday")]
+    Thu,
+    #[serde(rename = "http://schema.org/Friday", alias = "Friday")]
+    Fri,
+    #[serde(rename = "http://schema.org/Saturday", alias = "Saturday")]
+    Sat,
+    #[serde(rename = "http://schema.org/Sunday", alias = "Sunday")]
+    Sun,
+}
+
+/// Closure period of a service point.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClosurePeriod {
+    pub r#type: String,
+    pub from_date: NaiveDate,
+    pub to_date: NaiveDate,
+}
+
+/// The capacity of a service point on a particular weekday.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WeekdayCapacity {
+    pub day_of_week: Weekday,
+    pub capacity: Capacity,
+}
+
+/// Capacity of a service point location.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Capacity {
+    VeryLow,
+    Low,
+    High,
+    /// Also used for values this crate does not know yet.
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProviderType {
+    Parcel,
+    Express,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LocationType {
+    Servicepoint,
+    Locker,
+    Postoffice,
+    Postbank,
+}
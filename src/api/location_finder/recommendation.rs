@@ -0,0 +1,233 @@
+use super::{
+    Capacity, FindLocations, GetLocationsByAddress, ServicePoint, ServicePointLocationType, Weekday,
+};
+use crate::api::ServiceType;
+use crate::error::DhlError;
+use std::cmp::Ordering;
+
+/// Weights of the individual criteria the [LocationRecommender](LocationRecommender)
+/// combines into a single score. Weights are relative to each other and do not have to add up to one.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScoreWeights {
+    pub distance: f64,
+    pub capacity: f64,
+    pub opening_hours: f64,
+    pub accessibility: f64,
+}
+
+impl Default for ScoreWeights {
+    fn default() -> Self {
+        ScoreWeights {
+            distance: 0.4,
+            capacity: 0.3,
+            opening_hours: 0.2,
+            accessibility: 0.1,
+        }
+    }
+}
+
+/// Scores of the individual criteria, each between `0.0` (worst) and `1.0` (best).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScoreBreakdown {
+    /// Closeness to the requested address, relative to the recommender's maximum distance.
+    pub distance: f64,
+    /// Expected capacity on the delivery weekday.
+    pub capacity: f64,
+    /// Share of the delivery weekday the location is open.
+    pub opening_hours: f64,
+    /// Share of accessibility services (`HandicappedAccess`, `Parking`) the location offers.
+    pub accessibility: f64,
+}
+
+/// A service point location along with the score it got from the [LocationRecommender](LocationRecommender).
+#[derive(Debug, Clone)]
+pub struct Recommendation {
+    pub location: ServicePoint,
+    pub score: f64,
+    pub breakdown: ScoreBreakdown,
+}
+
+/// Ranks lockers and service points for a delivery on a given weekday.
+///
+/// # Example
+///
+/// ```
+/// # use dhl_wrapper::api::location_finder::{recommendation::*, *};
+/// let recommender = LocationRecommender::new()
+///     .weights(ScoreWeights {
+///         distance: 0.5,
+///         capacity: 0.5,
+///         opening_hours: 0.0,
+///         accessibility: 0.0,
+///     })
+///     .max_distance(2000);
+///
+/// let recommendations = recommender.rank(Vec::new(), Weekday::Mon);
+///
+/// assert!(recommendations.is_empty());
+/// ```
+#[derive(Debug, Clone)]
+pub struct LocationRecommender {
+    weights: ScoreWeights,
+    max_distance: u32,
+    location_types: Vec<ServicePointLocationType>,
+}
+
+impl Default for LocationRecommender {
+    fn default() -> Self {
+        LocationRecommender::new()
+    }
+}
+
+impl LocationRecommender {
+    /// Creates a recommender with [default weights](ScoreWeights::default), a maximum distance
+    /// of 5000 meters, considering lockers and service points only.
+    pub fn new() -> Self {
+        LocationRecommender {
+            weights: ScoreWeights::default(),
+            max_distance: 5000,
+            location_types: vec![
+                ServicePointLocationType::Locker,
+                ServicePointLocationType::Servicepoint,
+            ],
+        }
+    }
+
+    pub fn weights(mut self, weights: ScoreWeights) -> Self {
+        self.weights = weights;
+
+        self
+    }
+
+    /// Distance in meters at which (and beyond which) a location gets a distance score of zero.
+    pub fn max_distance(mut self, max_distance: u32) -> Self {
+        self.max_distance = max_distance;
+
+        self
+    }
+
+    /// Location types taken into account. Locations of other types are dropped when ranking.
+    pub fn location_types(mut self, location_types: Vec<ServicePointLocationType>) -> Self {
+        self.location_types = location_types;
+
+        self
+    }
+
+    /// Scores the given locations for a delivery on `weekday` and returns them
+    /// ordered from best to worst.
+    pub fn rank(&self, locations: Vec<ServicePoint>, weekday: Weekday) -> Vec<Recommendation> {
+        let mut recommendations = locations
+            .into_iter()
+            .filter(|v| self.location_types.contains(&v.location.r#type))
+            .map(|location| {
+                let breakdown = self.breakdown(&location, weekday);
+                let score = self.score(&breakdown);
+
+                Recommendation {
+                    location,
+                    score,
+                    breakdown,
+                }
+            })
+            .collect::<Vec<Recommendation>>();
+
+        recommendations.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(Ordering::Equal));
+
+        recommendations
+    }
+
+    /// Looks up locations around the address described by `request` and ranks them
+    /// for a delivery on `weekday`.
+    pub async fn recommend<A: FindLocations + ?Sized>(
+        &self,
+        api: &A,
+        request: GetLocationsByAddress,
+        weekday: Weekday,
+    ) -> Result<Vec<Recommendation>, DhlError> {
+        let response = api.find_by_address(request).await?;
+
+        Ok(self.rank(response.locations, weekday))
+    }
+
+    fn breakdown(&self, location: &ServicePoint, weekday: Weekday) -> ScoreBreakdown {
+        ScoreBreakdown {
+            distance: self.distance_score(location.distance),
+            capacity: capacity_score(expected_capacity(location, weekday)),
+            opening_hours: opening_hours_score(location, weekday),
+            accessibility: accessibility_score(location),
+        }
+    }
+
+    fn score(&self, breakdown: &ScoreBreakdown) -> f64 {
+        let w = &self.weights;
+        let total_weight = w.distance + w.capacity + w.opening_hours + w.accessibility;
+        if total_weight <= 0.0 {
+            return 0.0;
+        }
+
+        (breakdown.distance * w.distance
+            + breakdown.capacity * w.capacity
+            + breakdown.opening_hours * w.opening_hours
+            + breakdown.accessibility * w.accessibility)
+            / total_weight
+    }
+
+    fn distance_score(&self, distance: Option<u32>) -> f64 {
+        match distance {
+            Some(_) if self.max_distance == 0 => 0.0,
+            Some(v) => 1.0 - f64::from(v.min(self.max_distance)) / f64::from(self.max_distance),
+            None => 0.0,
+        }
+    }
+}
+
+/// Returns the capacity a location is expected to have on `weekday`.
+/// Falls back to the currently available capacity if there is no average for that weekday.
+pub fn expected_capacity(location: &ServicePoint, weekday: Weekday) -> Capacity {
+    location
+        .average_capacity_day_of_week
+        .iter()
+        .find(|v| v.day_of_week == weekday)
+        .map(|v| v.capacity)
+        .or(location.available_capacity)
+        .unwrap_or(Capacity::Unknown)
+}
+
+fn capacity_score(capacity: Capacity) -> f64 {
+    match capacity {
+        Capacity::High => 1.0,
+        Capacity::Low => 0.4,
+        Capacity::VeryLow => 0.1,
+        Capacity::Unknown => 0.5,
+    }
+}
+
+fn opening_hours_score(location: &ServicePoint, weekday: Weekday) -> f64 {
+    let open_minutes = location
+        .opening_hours
+        .iter()
+        .filter(|v| v.day_of_week == weekday)
+        .map(|v| {
+            let minutes = (v.closes - v.opens).num_minutes();
+            // closing at or before opening time means the location is open past midnight
+            if minutes <= 0 {
+                minutes + 24 * 60
+            } else {
+                minutes
+            }
+        })
+        .sum::<i64>();
+
+    // lockers report 00:00 - 23:59, so a day counts as fully covered one minute early
+    (open_minutes as f64 / (24.0 * 60.0 - 1.0)).min(1.0)
+}
+
+fn accessibility_score(location: &ServicePoint) -> f64 {
+    let accessibility_services = [ServiceType::HandicappedAccess, ServiceType::Parking];
+    let offered = accessibility_services
+        .iter()
+        .filter(|v| location.service_types.contains(v))
+        .count();
+
+    offered as f64 / accessibility_services.len() as f64
+}
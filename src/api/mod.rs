@@ -1,53 +1,245 @@
+use crate::cache::{cache_key, CachedResponse, Lookup, RequestKind, ResponseCache};
+use crate::drift::{self, DriftObserver};
 use crate::error::DhlError;
+use crate::redact::Pii;
+use crate::transport::{redact_url, HttpRequest, Transport};
 use convert_case::{Case, Casing};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::fmt;
+use std::sync::Arc;
+use std::time::SystemTime;
 
+mod body;
+pub mod client;
+mod environment;
 pub mod location_finder;
 pub mod shipment_tracking;
+mod validation;
 
-/// Serializes a struct's fields into a string of url parameters.
-fn serializable_to_url_params<T: Serialize>(serializable: &T) -> Result<String, DhlError> {
-    let value = serde_json::to_value(serializable)?;
+pub use body::{Body, Multipart};
+pub use client::{
+    new_idempotency_key, ApiProduct, AuthScheme, DhlClient, DhlRequest, Method,
+    IDEMPOTENCY_KEY_HEADER,
+};
+pub use environment::{Environment, Production, Sandbox};
+
+/// Drops repeated values, keeping the first of each. `None` if nothing is left.
+pub(crate) fn distinct<T: PartialEq>(values: Vec<T>) -> Option<Vec<T>> {
+    let mut res = Vec::with_capacity(values.len());
+    for value in values {
+        if !res.contains(&value) {
+            res.push(value);
+        }
+    }
+
+    (!res.is_empty()).then_some(res)
+}
+
+/// Serializes a struct's fields into url parameters with camelCase names.
+/// Lists become comma-separated values, `None` and empty lists are left out.
+pub(crate) fn query_params<T: Serialize>(
+    serializable: &T,
+) -> Result<Vec<(String, String)>, DhlError> {
+    let value = match serde_json::to_value(serializable)? {
+        Value::Object(v) => v,
+        _ => return Ok(Vec::new()),
+    };
 
     let mut params = Vec::new();
+    for (name, value) in value {
+        let value = match value {
+            Value::Bool(v) => v.to_string(),
+            Value::Number(v) => v.to_string(),
+            Value::String(v) => v,
+            Value::Array(v) if !v.is_empty() => v
+                .iter()
+                .map(|v| match v {
+                    Value::String(v) => v.clone(),
+                    v => v.to_string(),
+                })
+                .collect::<Vec<String>>()
+                .join(","),
+            _ => continue,
+        };
+        params.push((name.to_case(Case::Camel), value));
+    }
 
-    if let Value::Object(v) = value {
-        let v = v.into_iter().collect::<Vec<(String, Value)>>();
-        let mut n = 0;
-        for value in v.iter() {
-            let prefix = if n > 0 { "&" } else { "" };
+    Ok(params)
+}
 
-            match &value.1 {
-                Value::Bool(v) => {
-                    params.push(format!("{}{}={}", prefix, value.0.to_case(Case::Camel), v));
-                    n += 1;
-                }
-                Value::Number(v) => {
-                    params.push(format!("{}{}={}", prefix, value.0.to_case(Case::Camel), v));
-                    n += 1;
-                }
-                Value::String(v) => {
-                    params.push(format!("{}{}={}", prefix, value.0.to_case(Case::Camel), v));
-                    n += 1;
-                }
-                _ => {}
-            }
+/// The string a unit enum variant is serialized to, e.g. `DE` for [CountryCode::De](CountryCode::De).
+pub(crate) fn serialized_name<T: Serialize>(value: &T) -> String {
+    match serde_json::to_value(value) {
+        Ok(Value::String(v)) => v,
+        _ => String::new(),
+    }
+}
+
+/// Decodes a response part by part, skipping malformed parts.
+/// Returns `None` if the response is not even shaped right.
+pub(crate) type Lenient<R> = fn(Value) -> Option<R>;
+
+/// How [execute](execute) decodes the responses of a request.
+pub(crate) struct Decoder<R> {
+    /// Tells whether a response will never change again.
+    pub(crate) is_final: fn(&R) -> bool,
+    /// Tried on bodies which fail to decode as a whole.
+    pub(crate) lenient: Option<Lenient<R>>,
+    /// Told about the drift of every response which decodes as a whole.
+    pub(crate) drift_observer: Option<Arc<dyn DriftObserver>>,
+}
+
+impl<R> Clone for Decoder<R> {
+    fn clone(&self) -> Self {
+        Decoder {
+            is_final: self.is_final,
+            lenient: self.lenient,
+            drift_observer: self.drift_observer.clone(),
         }
+    }
+}
 
-        let mut query = String::new();
-        if !params.is_empty() {
-            query.push('?');
+/// Sends a request to DHL and decodes the response.
+/// Goes through the `cache` if there is one and stores successful responses in it.
+async fn execute<R>(
+    transport: &Arc<dyn Transport>,
+    request: HttpRequest,
+    kind: RequestKind,
+    cache: Option<&ResponseCache>,
+    decoder: Decoder<R>,
+) -> Result<R, DhlError>
+where
+    R: DeserializeOwned + Serialize + 'static,
+{
+    let url = request.url.clone();
+    let cache = match cache {
+        Some(v) => v,
+        None => return decode(&url, &fetch(transport, &request).await?, &decoder),
+    };
+
+    let key = cache_key(&url);
+    match cache.lookup(&key) {
+        Lookup::Fresh(response) => return decode(&url, &response, &decoder),
+        Lookup::Stale(response) => {
+            if cache.start_refresh(&key) {
+                let cache = cache.clone();
+                let transport = transport.clone();
+                let request = request.clone();
+                let url = url.clone();
+                let decoder = Decoder {
+                    drift_observer: None,
+                    ..decoder.clone()
+                };
+                tokio::spawn(async move {
+                    if let Ok(response) = fetch(&transport, &request).await {
+                        store(&cache, &key, &url, kind, response, &decoder);
+                    }
+                    cache.finish_refresh(&key);
+                });
+            }
+
+            return decode(&url, &response, &decoder);
         }
+        Lookup::Miss if cache.is_offline_only() => return Err(DhlError::CacheMiss(url)),
+        Lookup::Miss => {}
+    }
+
+    let response = fetch(transport, &request).await?;
+    let res = decode::<R>(&url, &response, &decoder)?;
+    if (200..300).contains(&response.status) {
+        cache.store(&key, kind, response, (decoder.is_final)(&res));
+    }
 
-        for param in params {
-            query.push_str(&param);
+    Ok(res)
+}
+
+/// Stores a response fetched in the background, if it is a successful one.
+fn store<R: DeserializeOwned + Serialize>(
+    cache: &ResponseCache,
+    key: &str,
+    url: &str,
+    kind: RequestKind,
+    response: CachedResponse,
+    decoder: &Decoder<R>,
+) {
+    if !(200..300).contains(&response.status) {
+        return;
+    }
+    if let Ok(res) = decode::<R>(url, &response, decoder) {
+        cache.store(key, kind, response, (decoder.is_final)(&res));
+    }
+}
+
+async fn fetch(
+    transport: &Arc<dyn Transport>,
+    request: &HttpRequest,
+) -> Result<CachedResponse, DhlError> {
+    let response = transport.execute(request.clone()).await?;
+
+    Ok(CachedResponse {
+        status: response.status,
+        headers: response.headers,
+        body: response.body,
+        stored_at: SystemTime::now(),
+        expires_at: None,
+    })
+}
+
+/// Characters of the response body kept in [DhlError::Decode](DhlError::Decode).
+const BODY_SNIPPET_LEN: usize = 300;
+
+/// Decodes a response of `url`, reporting where and why parsing failed.
+fn decode<R: DeserializeOwned + Serialize>(
+    url: &str,
+    response: &CachedResponse,
+    decoder: &Decoder<R>,
+) -> Result<R, DhlError> {
+    let body = &response.body;
+    if let Ok(v) = serde_json::from_slice::<ResponseNotOk>(body) {
+        return Err(DhlError::ResponseNotOk {
+            status: v.status,
+            title: v.title,
+            detail: v.detail,
+        });
+    }
+
+    let deserializer = &mut serde_json::Deserializer::from_slice(body);
+    let error = match serde_path_to_error::deserialize::<_, R>(deserializer) {
+        Ok(v) => {
+            if let Some(observer) = &decoder.drift_observer {
+                let value = serde_json::from_slice::<Value>(body)?;
+                for drift in drift::detect(&value, &v) {
+                    observer.observe(&drift);
+                }
+            }
+            return Ok(v);
         }
+        Err(e) => e,
+    };
 
-        return Ok(query);
+    let value = serde_json::from_slice::<Value>(body).ok();
+    if let Some(res) = decoder.lenient.zip(value).and_then(|(f, v)| f(v)) {
+        return Ok(res);
     }
 
-    Ok(String::new())
+    Err(DhlError::Decode {
+        path: error.path().to_string(),
+        message: error.inner().to_string(),
+        url: redact_url(url),
+        status: response.status as u32,
+        snippet: snippet(body),
+    })
+}
+
+/// Start of a response body, cut on a char boundary.
+fn snippet(body: &[u8]) -> String {
+    let body = String::from_utf8_lossy(body);
+    match body.char_indices().nth(BODY_SNIPPET_LEN) {
+        Some((i, _)) => format!("{}…", &body[..i]),
+        None => body.into_owned(),
+    }
 }
 
 /// In case DHL responds with a 4xx or 5xx status code, the response will
@@ -60,7 +252,7 @@ pub struct ResponseNotOk {
 }
 
 /// DHL service division.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "kebab-case")]
 pub enum Division {
     Express,
@@ -77,67 +269,53 @@ pub enum Division {
 }
 
 /// DHL service service types.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ServiceType {
-    #[serde(alias = "parcel:pick-up", rename = "parcel:pick-up")]
+    #[serde(rename = "parcel:pick-up")]
     ParcelPickUp,
-    #[serde(alias = "parcel:drop-off", rename = "parcel:drop-off")]
+    #[serde(rename = "parcel:drop-off")]
     ParcelDropOff,
-    #[serde(alias = "express:pick-up", rename = "xpress:pick-up")]
+    #[serde(rename = "express:pick-up")]
     ExpressPickUp,
-    #[serde(alias = "express:drop-off", rename = "express:drop-off")]
+    #[serde(rename = "express:drop-off")]
     ExpressDropOff,
-    #[serde(
-        alias = "express:drop-off-account",
-        rename = "express:drop-off-account"
-    )]
+    #[serde(rename = "express:drop-off-account")]
     ExpressDropOffAccount,
-    #[serde(alias = "express:drop-off-easy", rename = "express:drop-off-easy")]
+    #[serde(rename = "express:drop-off-easy")]
     ExpressDropOffEasy,
-    #[serde(
-        alias = "express:drop-off-prelabeled",
-        rename = "express:drop-off-prelabeled"
-    )]
+    #[serde(rename = "express:drop-off-prelabeled")]
     ExpressDropOffPrelabeled,
-    #[serde(
-        alias = "parcel:pick-up-registered",
-        rename = "parcel:pick-up-registere"
-    )]
+    #[serde(rename = "parcel:pick-up-registered")]
     ParcelPickUpRegistered,
-    #[serde(
-        alias = "parcel:pick-up-unregistered",
-        rename = "parcel:pick-up-unregistered"
-    )]
+    #[serde(rename = "parcel:pick-up-unregistered")]
     ParcelPickUpUnregistered,
-    #[serde(
-        alias = "parcel:drop-off-unregistered",
-        rename = "parcel:drop-off-unregistered"
-    )]
+    #[serde(rename = "parcel:drop-off-unregistered")]
     ParcelDropOffUnregistered,
-    #[serde(alias = "letter-service", rename = "letter-service")]
+    #[serde(rename = "letter-service")]
     LetterService,
-    #[serde(alias = "postbank", rename = "postbank")]
+    #[serde(rename = "postbank")]
     Postbank,
-    #[serde(alias = "cash-on-delivery", rename = "cash-on-delivery")]
+    #[serde(rename = "cash-on-delivery")]
     CashOnDelivery,
-    #[serde(alias = "franking", rename = "franking")]
+    #[serde(rename = "franking")]
     Franking,
-    #[serde(alias = "cash-service", rename = "cash-service")]
+    #[serde(rename = "cash-service")]
     CashService,
-    #[serde(alias = "packaging-material", rename = "packaging-material")]
+    #[serde(rename = "packaging-material")]
     PackagingMaterial,
-    #[serde(alias = "postident", rename = "postident")]
+    #[serde(rename = "postident")]
     Postident,
-    #[serde(alias = "age-verification", rename = "age-verification")]
+    #[serde(rename = "age-verification")]
     AgeVerification,
-    #[serde(alias = "handicapped-access", rename = "handicapped-access")]
+    #[serde(rename = "handicapped-access")]
     HandicappedAccess,
-    #[serde(alias = "parking", rename = "parking")]
+    #[serde(rename = "parking")]
     Parking,
 }
 
 /// Address information used in other structs like [location_finder::Place](location_finder::Place).
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Only the country shows up in `Debug` output, unless it is [Unredacted](crate::redact::Unredacted).
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Address {
     pub country_code: Option<String>,
@@ -146,8 +324,19 @@ pub struct Address {
     pub street_address: Option<String>,
 }
 
+impl fmt::Debug for Address {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Address")
+            .field("country_code", &self.country_code)
+            .field("postal_code", &Pii(&self.postal_code))
+            .field("address_locality", &Pii(&self.address_locality))
+            .field("street_address", &Pii(&self.street_address))
+            .finish()
+    }
+}
+
 /// Two-letter country codes (<https://en.wikipedia.org/wiki/ISO_3166-1_alpha-2>).
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "UPPERCASE")]
 pub enum CountryCode {
     Ad, // Andorra
@@ -402,7 +591,7 @@ pub enum CountryCode {
 }
 
 /// ISO 639-1 2-character language code (<https://en.wikipedia.org/wiki/ISO_3166-1_alpha-2>).
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum LanguageCode {
     Aa, // Afar
@@ -0,0 +1,667 @@
+use super::{
+    EstimatedDeliveryTimeFrame, Shipment, ShipmentDetail, ShipmentEvent, ShipmentFloatWithUnit,
+    ShipmentPathPoint, ShipmentProduct, ShipmentStatus, ShipmentStatusCode,
+};
+use crate::api::{serialized_name, Address, CountryCode, Division};
+use chrono::{Duration, NaiveDate, NaiveDateTime, NaiveTime};
+
+/// Localities shipments are sent from and to, by country.
+const LOCALITIES: [(CountryCode, &str, &str); 24] = [
+    (CountryCode::De, "Hamburg", "20095"),
+    (CountryCode::De, "Berlin", "10115"),
+    (CountryCode::De, "München", "80331"),
+    (CountryCode::De, "Köln", "50667"),
+    (CountryCode::De, "Bonn", "53111"),
+    (CountryCode::At, "Wien", "1010"),
+    (CountryCode::At, "Graz", "8010"),
+    (CountryCode::Ch, "Zürich", "8001"),
+    (CountryCode::Ch, "Bern", "3011"),
+    (CountryCode::Nl, "Amsterdam", "1012"),
+    (CountryCode::Nl, "Rotterdam", "3011"),
+    (CountryCode::Be, "Brussels", "1000"),
+    (CountryCode::Fr, "Paris", "75001"),
+    (CountryCode::Fr, "Lyon", "69001"),
+    (CountryCode::Gb, "London", "EC1A 1BB"),
+    (CountryCode::Gb, "Manchester", "M1 1AE"),
+    (CountryCode::It, "Milano", "20121"),
+    (CountryCode::Es, "Madrid", "28001"),
+    (CountryCode::Pl, "Warszawa", "00-001"),
+    (CountryCode::Us, "New York", "10001"),
+    (CountryCode::Us, "Chicago", "60601"),
+    (CountryCode::Cn, "Shanghai", "200000"),
+    (CountryCode::Jp, "Tokyo", "100-0001"),
+    (CountryCode::Jp, "Osaka", "530-0001"),
+];
+
+/// What happens to a generated shipment on its way.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scenario {
+    /// Delivered on time.
+    Normal,
+    /// Delivered a few days later than first estimated.
+    Delayed,
+    /// Held by customs of the destination country, never delivered.
+    CustomsHold,
+    /// Could not be delivered and was sent back to the sender.
+    Returned,
+    /// The delivery attempt failed, the shipment waits at a retail outlet.
+    FailedDelivery,
+}
+
+/// Generates plausible but fake tracking data, e.g. for load tests or demos.
+///
+/// Every shipment gets a tracking number and piece ids in the format of its [Division](crate::api::Division)
+/// and a timeline of events following the [Scenario](Scenario), from the sender handing it over
+/// to its final status. The same seed always generates the same shipments.
+///
+/// # Example
+///
+/// ```
+/// # use dhl_wrapper::api::shipment_tracking::{generator::*, *};
+/// # use dhl_wrapper::api::*;
+/// let mut generator = ShipmentGenerator::new(42)
+///     .division(Division::Express)
+///     .origin(CountryCode::De)
+///     .destination(CountryCode::Us)
+///     .scenario(Scenario::Delayed);
+///
+/// let shipment = generator.generate();
+/// assert_eq!(shipment.status.status_code, ShipmentStatusCode::Delivered);
+///
+/// // tracking states after every event, e.g. for a FakeShipmentTracking journey
+/// let journey = generator.journey();
+/// assert_eq!(journey.len(), journey.last().unwrap().events.len());
+///
+/// // 1000 different shipments
+/// let shipments = ShipmentGenerator::new(42).take(1000).collect::<Vec<Shipment>>();
+/// ```
+#[derive(Debug, Clone)]
+pub struct ShipmentGenerator {
+    rng: Rng,
+    division: Division,
+    origin: CountryCode,
+    destination: CountryCode,
+    scenario: Scenario,
+    shipped_from: NaiveDateTime,
+}
+
+impl ShipmentGenerator {
+    /// Creates a generator for domestic [ParcelDe](crate::api::Division::ParcelDe) shipments
+    /// following the [Normal](Scenario::Normal) scenario.
+    pub fn new(seed: u64) -> Self {
+        ShipmentGenerator {
+            rng: Rng(seed),
+            division: Division::ParcelDe,
+            origin: CountryCode::De,
+            destination: CountryCode::De,
+            scenario: Scenario::Normal,
+            shipped_from: NaiveDate::from_ymd_opt(2024, 1, 8)
+                .and_then(|v| v.and_hms_opt(0, 0, 0))
+                .expect("valid date"),
+        }
+    }
+
+    pub fn division(mut self, division: Division) -> Self {
+        self.division = division;
+
+        self
+    }
+
+    pub fn origin(mut self, origin: CountryCode) -> Self {
+        self.origin = origin;
+
+        self
+    }
+
+    pub fn destination(mut self, destination: CountryCode) -> Self {
+        self.destination = destination;
+
+        self
+    }
+
+    pub fn scenario(mut self, scenario: Scenario) -> Self {
+        self.scenario = scenario;
+
+        self
+    }
+
+    /// Shipments are handed over to DHL within two weeks after this point in time.
+    /// Defaults to 2024-01-08.
+    pub fn shipped_from(mut self, shipped_from: NaiveDateTime) -> Self {
+        self.shipped_from = shipped_from;
+
+        self
+    }
+
+    /// Generates a shipment with its complete timeline.
+    pub fn generate(&mut self) -> Shipment {
+        self.journey()
+            .pop()
+            .expect("a journey has at least one event")
+    }
+
+    /// Generates a shipment and returns its tracking state after each event of its timeline,
+    /// oldest first.
+    pub fn journey(&mut self) -> Vec<Shipment> {
+        let id = self.tracking_number();
+        let piece_ids = self.piece_ids(&id);
+        let origin = self.locality(self.origin, None);
+        let destination = self.locality(self.destination, Some(&origin));
+        let weight = self.rng.range(1, 300) as f64 / 10.0;
+        let timeline = self.timeline(&origin, &destination);
+
+        let piece_ids_of_events = match self.division {
+            Division::Express => Some(piece_ids.clone()),
+            _ => None,
+        };
+        let events = timeline
+            .steps
+            .iter()
+            .map(|v| ShipmentEvent {
+                timestamp: v.timestamp,
+                location: Some(v.location.clone()),
+                status_code: Some(v.status_code),
+                status: Some(v.status.to_string()),
+                description: v.description.to_string(),
+                piece_ids: piece_ids_of_events.clone(),
+                remark: v.remark.map(|v| v.to_string()),
+                next_steps: v.next_steps.map(|v| v.to_string()),
+            })
+            .collect::<Vec<ShipmentEvent>>();
+
+        (1..=events.len())
+            .map(|n| {
+                let step = &timeline.steps[n - 1];
+                let latest = &events[n - 1];
+                // DHL lists the latest event first
+                let mut events = events[..n].to_vec();
+                events.reverse();
+
+                Shipment {
+                    id: id.clone(),
+                    service: self.division,
+                    origin: Some(origin.clone()),
+                    destination: Some(destination.clone()),
+                    status: ShipmentStatus {
+                        timestamp: latest.timestamp,
+                        location: step.location.clone(),
+                        status_code: step.status_code,
+                        status: step.status.to_string(),
+                        description: step.description.to_string(),
+                        piece_ids: latest.piece_ids.clone(),
+                        remark: latest.remark.clone(),
+                        next_steps: latest.next_steps.clone(),
+                    },
+                    estimated_time_of_delivery: step.estimated_delivery.map(|v| v.0),
+                    estimated_delivery_time_frame: step.estimated_delivery.map(
+                        |(from, through)| EstimatedDeliveryTimeFrame {
+                            estimated_from: from,
+                            estimated_through: through,
+                        },
+                    ),
+                    estimated_time_of_delivery_remark: match (
+                        step.estimated_delivery,
+                        n > timeline.delayed_from,
+                    ) {
+                        (Some(_), true) => Some("The delivery has been postponed".to_string()),
+                        _ => None,
+                    },
+                    service_url: self.service_url(&id),
+                    reroute_url: None,
+                    details: ShipmentDetail {
+                        carrier: None,
+                        receiver: None,
+                        sender: None,
+                        product: Some(ShipmentProduct {
+                            product_name: self.product_name().to_string(),
+                        }),
+                        proof_of_delivery_signed_available: self.division == Division::Express
+                            && step.status_code == ShipmentStatusCode::Delivered,
+                        proof_of_delivery: None,
+                        total_number_of_pieces: piece_ids.len() as u32,
+                        piece_ids: piece_ids.clone(),
+                        weight: Some(ShipmentFloatWithUnit {
+                            value: weight,
+                            unit_text: Some("kg".to_string()),
+                        }),
+                        volume: None,
+                        loading_meters: None,
+                        dimensions: None,
+                        references: None,
+                        dgf_routes: None,
+                    },
+                    events,
+                }
+            })
+            .collect()
+    }
+
+    fn tracking_number(&mut self) -> String {
+        match self.division {
+            Division::ParcelDe => format!("00340434{}", self.rng.digits(12)),
+            Division::Express => self.rng.digits(10),
+            Division::Ecommerce => format!("GM{}", self.rng.digits(16)),
+            Division::PostDe => format!("RR{}DE", self.rng.digits(9)),
+            Division::ParcelNl => format!("3SDHL{}", self.rng.digits(9)),
+            Division::ParcelUk => self.rng.digits(14),
+            Division::ParcelPl => self.rng.digits(11),
+            _ => self.rng.digits(10),
+        }
+    }
+
+    fn piece_ids(&mut self, tracking_number: &str) -> Vec<String> {
+        match self.division {
+            // parcels are tracked by their piece id
+            Division::ParcelDe
+            | Division::Ecommerce
+            | Division::PostDe
+            | Division::ParcelUk
+            | Division::ParcelNl
+            | Division::ParcelPl => vec![tracking_number.to_string()],
+            _ => {
+                let pieces = self.rng.range(1, 3);
+                (0..pieces)
+                    .map(|_| format!("JD01{}", self.rng.digits(16)))
+                    .collect()
+            }
+        }
+    }
+
+    /// Picks a locality in `country`, different from `other` if possible.
+    fn locality(
+        &mut self,
+        country: CountryCode,
+        other: Option<&ShipmentPathPoint>,
+    ) -> ShipmentPathPoint {
+        let other = other.and_then(|v| v.address.address_locality.as_deref());
+        let localities = LOCALITIES
+            .iter()
+            .filter(|(country_code, locality, _)| {
+                *country_code == country && Some(*locality) != other
+            })
+            .collect::<Vec<&(CountryCode, &str, &str)>>();
+
+        let (locality, postal_code) = match localities.len() {
+            0 => (None, None),
+            n => {
+                let (_, locality, postal_code) =
+                    localities[self.rng.range(0, n as u64 - 1) as usize];
+                (Some(locality.to_string()), Some(postal_code.to_string()))
+            }
+        };
+
+        ShipmentPathPoint {
+            address: Address {
+                country_code: Some(serialized_name(&country)),
+                postal_code,
+                address_locality: locality,
+                street_address: None,
+            },
+            service_point: None,
+        }
+    }
+
+    /// Builds the events of a shipment following the scenario, oldest first.
+    fn timeline(
+        &mut self,
+        origin: &ShipmentPathPoint,
+        destination: &ShipmentPathPoint,
+    ) -> Timeline {
+        let international = self.origin != self.destination;
+        let origin_hub = hub(origin);
+        let destination_hub = hub(destination);
+
+        let handover = self.shipped_from
+            + Duration::days(self.rng.range(0, 13) as i64)
+            + self.rng.hours(8, 17);
+        let mut timeline = Timeline::new(handover - self.rng.hours(2, 20));
+
+        timeline.push(
+            Duration::zero(),
+            ShipmentStatusCode::PreTransit,
+            "SHIPMENT INFORMATION RECEIVED",
+            "The instruction data for this shipment have been provided by the sender to DHL electronically",
+            origin,
+        );
+        timeline.at(handover);
+        timeline.push(
+            Duration::zero(),
+            ShipmentStatusCode::Transit,
+            "PICKED UP",
+            "The shipment has been picked up",
+            origin,
+        );
+        timeline.push(
+            self.rng.hours(3, 6),
+            ShipmentStatusCode::Transit,
+            "PROCESSED",
+            "The shipment has been processed in the parcel center of origin",
+            &origin_hub,
+        );
+
+        if international {
+            timeline.push(
+                self.rng.hours(6, 12),
+                ShipmentStatusCode::Transit,
+                "DEPARTED",
+                "The shipment has left the export parcel center",
+                &origin_hub,
+            );
+            timeline.push(
+                self.rng.hours(18, 40),
+                ShipmentStatusCode::Transit,
+                "ARRIVED",
+                "The shipment has arrived in the destination country",
+                &destination_hub,
+            );
+        }
+
+        if self.scenario == Scenario::CustomsHold {
+            timeline.push(
+                self.rng.hours(2, 8),
+                ShipmentStatusCode::Transit,
+                "HELD AT CUSTOMS",
+                "The shipment is being held by customs",
+                &destination_hub,
+            );
+            timeline.remark(
+                "Customs requires additional documents",
+                "Please contact the sender to provide the commercial invoice",
+            );
+
+            return self.estimate(timeline, international);
+        }
+
+        if international {
+            timeline.push(
+                self.rng.hours(2, 8),
+                ShipmentStatusCode::Transit,
+                "CUSTOMS CLEARED",
+                "The shipment has been cleared by customs",
+                &destination_hub,
+            );
+        }
+        timeline.push(
+            self.rng.hours(4, 12),
+            ShipmentStatusCode::Transit,
+            "ARRIVED",
+            "The shipment has been processed in the destination parcel center",
+            &destination_hub,
+        );
+
+        let delay = match self.scenario {
+            Scenario::Delayed => {
+                timeline.push(
+                    self.rng.hours(1, 3),
+                    ShipmentStatusCode::Transit,
+                    "DELAYED",
+                    "The shipment is delayed due to high volumes",
+                    &destination_hub,
+                );
+                timeline.delayed_from = timeline.steps.len() - 1;
+                self.rng.range(1, 3) as i64
+            }
+            _ => 0,
+        };
+
+        timeline.next_morning(self.rng.range(6, 9), delay);
+        timeline.push(
+            self.rng.hours(0, 1),
+            ShipmentStatusCode::Transit,
+            "OUT FOR DELIVERY",
+            "The shipment has been loaded onto the delivery vehicle",
+            destination,
+        );
+        let delivery_day = timeline.time.date();
+
+        match self.scenario {
+            Scenario::Normal | Scenario::Delayed => {
+                timeline.push(
+                    self.rng.hours(1, 6),
+                    ShipmentStatusCode::Delivered,
+                    "DELIVERED",
+                    "The shipment has been successfully delivered",
+                    destination,
+                );
+            }
+            Scenario::FailedDelivery => {
+                timeline.push(
+                    self.rng.hours(1, 6),
+                    ShipmentStatusCode::Failure,
+                    "DELIVERY ATTEMPT FAILED",
+                    "The recipient was not present, the shipment will be brought to a retail outlet",
+                    destination,
+                );
+                timeline.remark(
+                    "The recipient has been notified by a delivery notice",
+                    "The shipment can be picked up at the retail outlet within 7 days",
+                );
+            }
+            Scenario::Returned => {
+                timeline.push(
+                    self.rng.hours(1, 6),
+                    ShipmentStatusCode::Failure,
+                    "DELIVERY ATTEMPT FAILED",
+                    "The shipment could not be delivered",
+                    destination,
+                );
+                timeline.push(
+                    self.rng.hours(24, 48),
+                    ShipmentStatusCode::Transit,
+                    "RETURNED",
+                    "The shipment is being returned to the sender",
+                    &destination_hub,
+                );
+                timeline.push(
+                    self.rng.hours(24, 72),
+                    ShipmentStatusCode::Transit,
+                    "ARRIVED",
+                    "The shipment has been processed in the parcel center of origin",
+                    &origin_hub,
+                );
+                timeline.next_morning(self.rng.range(7, 11), 0);
+                timeline.push(
+                    self.rng.hours(0, 4),
+                    ShipmentStatusCode::Delivered,
+                    "RETURNED TO SENDER",
+                    "The shipment has been delivered back to the sender",
+                    origin,
+                );
+            }
+            Scenario::CustomsHold => unreachable!("customs holds end before delivery"),
+        }
+
+        // the planned delivery day is known once the shipment is handed over, a delay pushes it back
+        let out_for_delivery = timeline
+            .steps
+            .iter()
+            .position(|v| v.status == "OUT FOR DELIVERY")
+            .unwrap_or(0);
+        timeline.estimate(
+            1,
+            out_for_delivery + 1,
+            delivery_day - Duration::days(delay),
+        );
+        if delay > 0 {
+            timeline.estimate(timeline.delayed_from, out_for_delivery + 1, delivery_day);
+        }
+
+        timeline
+    }
+
+    /// Estimates delivery for shipments stuck before their delivery day is known,
+    /// i.e. those held by customs.
+    fn estimate(&mut self, mut timeline: Timeline, international: bool) -> Timeline {
+        let transit_days = match international {
+            true => self.rng.range(3, 6),
+            false => self.rng.range(1, 2),
+        };
+        let held_at = timeline.steps.len() - 1;
+        let day = timeline.steps[1].timestamp.date() + Duration::days(transit_days as i64);
+        timeline.estimate(1, held_at, day);
+
+        timeline
+    }
+
+    fn product_name(&self) -> &'static str {
+        match (self.division, self.origin == self.destination) {
+            (Division::ParcelDe, true) => "DHL PAKET",
+            (Division::ParcelDe, false) => "DHL PAKET International",
+            (Division::Express, true) => "EXPRESS DOMESTIC",
+            (Division::Express, false) => "EXPRESS WORLDWIDE",
+            (Division::Ecommerce, _) => "Parcel International Standard",
+            (Division::PostDe, _) => "Einschreiben",
+            (Division::ParcelUk, _) => "DHL Parcel UK",
+            (Division::ParcelNl, _) => "DHL Parcel Connect",
+            (Division::ParcelPl, _) => "DHL Parcel",
+            (Division::Dgf, _) => "DHL Global Forwarding",
+            (Division::Freight, _) => "DHL Freight",
+            (Division::Sameday, _) => "DHL Same Day",
+            (Division::Dsc, _) => "DHL Supply Chain",
+        }
+    }
+
+    fn service_url(&self, tracking_number: &str) -> Option<String> {
+        match self.division {
+            Division::ParcelDe => Some(format!(
+                "https://www.dhl.de/de/privatkunden/pakete-empfangen/verfolgen.html?lang=de&idc={}",
+                tracking_number
+            )),
+            Division::Express => Some(format!(
+                "https://www.dhl.com/global-en/home/tracking/tracking-express.html?submit=1&tracking-id={}",
+                tracking_number
+            )),
+            _ => None,
+        }
+    }
+}
+
+impl Iterator for ShipmentGenerator {
+    type Item = Shipment;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.generate())
+    }
+}
+
+/// The parcel center handling shipments of a locality.
+fn hub(locality: &ShipmentPathPoint) -> ShipmentPathPoint {
+    ShipmentPathPoint {
+        address: Address {
+            country_code: locality.address.country_code.clone(),
+            postal_code: None,
+            address_locality: locality.address.address_locality.clone(),
+            street_address: None,
+        },
+        service_point: None,
+    }
+}
+
+struct Step {
+    timestamp: NaiveDateTime,
+    status_code: ShipmentStatusCode,
+    status: &'static str,
+    description: &'static str,
+    location: ShipmentPathPoint,
+    remark: Option<&'static str>,
+    next_steps: Option<&'static str>,
+    /// Estimated delivery time frame as known after this step.
+    estimated_delivery: Option<(NaiveDateTime, NaiveDateTime)>,
+}
+
+struct Timeline {
+    steps: Vec<Step>,
+    time: NaiveDateTime,
+    /// Index of the step from which on the delivery is known to be delayed.
+    delayed_from: usize,
+}
+
+impl Timeline {
+    fn new(start: NaiveDateTime) -> Self {
+        Timeline {
+            steps: Vec::new(),
+            time: start,
+            delayed_from: usize::MAX,
+        }
+    }
+
+    fn at(&mut self, time: NaiveDateTime) {
+        self.time = time;
+    }
+
+    /// Moves on to the morning of the next day, `days` further if delayed.
+    fn next_morning(&mut self, hour: u64, days: i64) {
+        let morning = NaiveTime::from_hms_opt(hour as u32, 0, 0).expect("valid hour");
+        self.time = (self.time.date() + Duration::days(1 + days)).and_time(morning);
+    }
+
+    fn push(
+        &mut self,
+        after: Duration,
+        status_code: ShipmentStatusCode,
+        status: &'static str,
+        description: &'static str,
+        location: &ShipmentPathPoint,
+    ) {
+        self.time += after;
+        self.steps.push(Step {
+            timestamp: self.time,
+            status_code,
+            status,
+            description,
+            location: location.clone(),
+            remark: None,
+            next_steps: None,
+            estimated_delivery: None,
+        });
+    }
+
+    fn remark(&mut self, remark: &'static str, next_steps: &'static str) {
+        if let Some(step) = self.steps.last_mut() {
+            step.remark = Some(remark);
+            step.next_steps = Some(next_steps);
+        }
+    }
+
+    /// Sets the estimated delivery of the steps with an index in `from..until`.
+    fn estimate(&mut self, from: usize, until: usize, day: NaiveDate) {
+        let frame = (
+            day.and_hms_opt(9, 0, 0).expect("valid time"),
+            day.and_hms_opt(18, 0, 0).expect("valid time"),
+        );
+        for step in self.steps.iter_mut().take(until).skip(from) {
+            step.estimated_delivery = Some(frame);
+        }
+    }
+}
+
+/// SplitMix64, a small and fast PRNG whose output is stable across platforms and versions.
+#[derive(Debug, Clone)]
+struct Rng(u64);
+
+impl Rng {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+
+        z ^ (z >> 31)
+    }
+
+    /// Random duration between `min` and `max` hours, in minutes.
+    fn hours(&mut self, min: u64, max: u64) -> Duration {
+        Duration::minutes(self.range(min * 60, max * 60) as i64)
+    }
+
+    /// Random number in `min..=max`.
+    fn range(&mut self, min: u64, max: u64) -> u64 {
+        min + self.next_u64() % (max - min + 1)
+    }
+
+    fn digits(&mut self, count: usize) -> String {
+        (0..count)
+            .map(|_| char::from(b'0' + self.range(0, 9) as u8))
+            .collect()
+    }
+}
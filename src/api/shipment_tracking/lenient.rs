@@ -0,0 +1,87 @@
+use super::{DecodeWarning, GetShipmentTrackingResponse, Shipment};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+/// Decodes every shipment, and every event of it, on its own.
+/// Returns `None` if the response apart from its shipments is malformed.
+pub(super) fn decode_response(value: Value) -> Option<GetShipmentTrackingResponse> {
+    let mut object = match value {
+        Value::Object(v) => v,
+        _ => return None,
+    };
+    let shipments = match object.insert("shipments".to_string(), Value::Array(Vec::new())) {
+        Some(Value::Array(v)) => v,
+        _ => return None,
+    };
+
+    let mut response =
+        serde_json::from_value::<GetShipmentTrackingResponse>(Value::Object(object)).ok()?;
+    for (i, shipment) in shipments.into_iter().enumerate() {
+        let path = format!("shipments[{}]", i);
+        if let Some(v) = decode_shipment(shipment, &path, &mut response.warnings) {
+            response.shipments.push(v);
+        }
+    }
+
+    Some(response)
+}
+
+/// Decodes a shipment without its events first, then each event.
+fn decode_shipment(
+    value: Value,
+    path: &str,
+    warnings: &mut Vec<DecodeWarning>,
+) -> Option<Shipment> {
+    let mut stripped = value.clone();
+    let events = match stripped.get_mut("events") {
+        Some(Value::Array(v)) => std::mem::take(v),
+        _ => Vec::new(),
+    };
+
+    let mut shipment = match serde_path_to_error::deserialize::<_, Shipment>(&stripped) {
+        Ok(v) => v,
+        Err(e) => {
+            warnings.push(warning(path, e, value));
+            return None;
+        }
+    };
+    for (i, event) in events.into_iter().enumerate() {
+        let path = format!("{}.events[{}]", path, i);
+        if let Some(v) = decode(event, &path, warnings) {
+            shipment.events.push(v);
+        }
+    }
+
+    Some(shipment)
+}
+
+fn decode<T: DeserializeOwned>(
+    value: Value,
+    path: &str,
+    warnings: &mut Vec<DecodeWarning>,
+) -> Option<T> {
+    match serde_path_to_error::deserialize::<_, T>(&value) {
+        Ok(v) => Some(v),
+        Err(e) => {
+            warnings.push(warning(path, e, value));
+            None
+        }
+    }
+}
+
+fn warning(
+    path: &str,
+    error: serde_path_to_error::Error<serde_json::Error>,
+    raw: Value,
+) -> DecodeWarning {
+    let path = match error.path().to_string().as_str() {
+        "." => path.to_string(),
+        v => format!("{}.{}", path, v),
+    };
+
+    DecodeWarning {
+        path,
+        message: error.into_inner().to_string(),
+        raw,
+    }
+}
@@ -0,0 +1,289 @@
+use super::location_finder::ApiMode;
+use super::validation::Validator;
+use super::{query_params, ApiProduct, DhlClient, DhlRequest, Environment};
+use crate::auth::{Credentials, CredentialsProvider, EnvCredentials, KeyPool};
+use crate::cache::{RequestKind, ResponseCache};
+use crate::drift::DriftObserver;
+use crate::error::DhlError;
+use crate::redact::Secret;
+use crate::transport::Transport;
+use async_trait::async_trait;
+use serde_json::Value;
+
+pub mod generator;
+mod lenient;
+mod models;
+mod timestamp;
+
+pub use models::*;
+
+/// Base URL of DHL's "Shipment Tracking - Unified" API.
+pub const SHIPMENT_TRACKING_BASE_URL: &str = "https://api-eu.dhl.com/track";
+
+/// Base URL of the test environment of DHL's "Shipment Tracking - Unified" API.
+pub const SHIPMENT_TRACKING_SANDBOX_URL: &str = "https://api-test.dhl.com/track";
+
+/// API struct for calling DHL's "Shipment Tracking - Unified" API.
+///
+/// It calls production unless it is created for another [Environment](crate::api::Environment):
+/// an [ApiMode](ApiMode) chosen at runtime, or [Sandbox](crate::api::Sandbox) or
+/// [Production](crate::api::Production) if it is part of the type.
+pub struct ShipmentTrackingApi<E: Environment = ApiMode> {
+    client: DhlClient,
+    environment: E,
+}
+
+impl ShipmentTrackingApi {
+    /// Creates a new API calling production.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use dhl_wrapper::api::shipment_tracking::ShipmentTrackingApi;
+    /// let api = ShipmentTrackingApi::new("your_api_token");
+    /// ```
+    pub fn new<T: Into<Secret>>(api_key: T) -> Self {
+        ShipmentTrackingApi::new_in(ApiMode::Production, api_key)
+    }
+
+    /// Creates a new API calling production, taking turns with the keys of `pool`,
+    /// see [KeyPool](crate::auth::KeyPool).
+    pub fn with_key_pool(pool: KeyPool) -> Self {
+        ShipmentTrackingApi::with_key_pool_in(ApiMode::Production, pool)
+    }
+
+    /// Creates a new API calling production with the key in the `SHIPMENT_TRACKING_API_KEY`
+    /// environment variable, see [EnvCredentials](crate::auth::EnvCredentials).
+    pub fn from_env() -> Result<Self, DhlError> {
+        ShipmentTrackingApi::from_env_in(ApiMode::Production)
+    }
+
+    /// Creates a new API calling production with the credentials `provider` has for
+    /// [ApiProduct::ShipmentTracking](crate::api::ApiProduct::ShipmentTracking).
+    pub fn from_provider<P: CredentialsProvider + ?Sized>(provider: &P) -> Result<Self, DhlError> {
+        ShipmentTrackingApi::from_provider_in(ApiMode::Production, provider)
+    }
+}
+
+impl<E: Environment> ShipmentTrackingApi<E> {
+    /// Creates a new API calling `environment`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use dhl_wrapper::api::location_finder::ApiMode;
+    /// # use dhl_wrapper::api::shipment_tracking::ShipmentTrackingApi;
+    /// # use dhl_wrapper::api::Sandbox;
+    /// let api = ShipmentTrackingApi::new_in(ApiMode::Sandbox, "your_sandbox_token");
+    ///
+    /// // a ShipmentTrackingApi<Sandbox>
+    /// let api = ShipmentTrackingApi::new_in(Sandbox, "your_sandbox_token");
+    /// ```
+    pub fn new_in<T: Into<Secret>>(environment: E, api_key: T) -> Self {
+        ShipmentTrackingApi::with_client(environment, DhlClient::new(api_key))
+    }
+
+    /// Creates a new API calling `environment`, taking turns with the keys of `pool`.
+    pub fn with_key_pool_in(environment: E, pool: KeyPool) -> Self {
+        let client = DhlClient::with_credentials(Credentials::ApiKeyPool(pool));
+
+        ShipmentTrackingApi::with_client(environment, client)
+    }
+
+    /// Creates a new API calling `environment` with the key in the `SHIPMENT_TRACKING_API_KEY`
+    /// environment variable, or `SANDBOX_SHIPMENT_TRACKING_API_KEY` for the sandbox.
+    pub fn from_env_in(environment: E) -> Result<Self, DhlError> {
+        ShipmentTrackingApi::from_provider_in(environment, &EnvCredentials::new())
+    }
+
+    /// Creates a new API calling `environment` with the credentials `provider` has for
+    /// [ApiProduct::ShipmentTracking](crate::api::ApiProduct::ShipmentTracking) in it.
+    pub fn from_provider_in<P: CredentialsProvider + ?Sized>(
+        environment: E,
+        provider: &P,
+    ) -> Result<Self, DhlError> {
+        let credentials =
+            provider.credentials(ApiProduct::ShipmentTracking, environment.api_mode())?;
+
+        Ok(ShipmentTrackingApi::with_client(
+            environment,
+            DhlClient::with_credentials(credentials),
+        ))
+    }
+
+    fn with_client(environment: E, client: DhlClient) -> Self {
+        let base_url = ApiProduct::ShipmentTracking.base_url_in(environment.api_mode());
+
+        ShipmentTrackingApi {
+            client: client.base_url(ApiProduct::ShipmentTracking, base_url),
+            environment,
+        }
+    }
+
+    /// The environment the API calls, unless its [base URL](ShipmentTrackingApi::base_url) was overridden.
+    pub fn api_mode(&self) -> ApiMode {
+        self.environment.api_mode()
+    }
+
+    /// Caches responses in the given [ResponseCache](crate::cache::ResponseCache).
+    /// Tracking data of delivered shipments is cached forever, unless the
+    /// [CachePolicy](crate::cache::CachePolicy) says otherwise.
+    pub fn cache(mut self, cache: ResponseCache) -> Self {
+        self.client = self.client.cache(cache);
+
+        self
+    }
+
+    /// Sends requests through the given [Transport](crate::transport::Transport)
+    /// instead of a [ReqwestTransport](crate::transport::ReqwestTransport).
+    pub fn transport<T: Transport + 'static>(mut self, transport: T) -> Self {
+        self.client = self.client.transport(transport);
+
+        self
+    }
+
+    /// Decodes each shipment and each event on its own, so one malformed shipment does not
+    /// fail the whole response. Skipped parts are listed in the response's `warnings`.
+    pub fn lenient(mut self, lenient: bool) -> Self {
+        self.client = self.client.lenient(lenient);
+
+        self
+    }
+
+    /// Tells `observer` about every difference between decoded responses and the models,
+    /// like unknown fields, see [SchemaDrift](crate::drift::SchemaDrift).
+    pub fn drift_observer<T: DriftObserver + 'static>(mut self, observer: T) -> Self {
+        self.client = self.client.drift_observer(observer);
+
+        self
+    }
+
+    /// Uses the API to send a request.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let api_key = "your_api_token";
+    /// let tracking_number = "123456789";
+    ///
+    /// # use dhl_wrapper::api::shipment_tracking::*;
+    /// # use dhl_wrapper::transport::cassette::Cassette;
+    /// # let tracking_number = "00340434161094042557";
+    /// let api = ShipmentTrackingApi::new(api_key);
+    /// # let api = api.transport(Cassette::replay("tests/cassettes/shipment_tracking.json").unwrap());
+    ///
+    /// // Get shipment tracking data
+    /// let request = GetShipmentTracking::new(tracking_number);
+    /// let response = api.send(request).await.unwrap();
+    ///
+    /// assert_eq!(response.shipments.is_empty(), false);
+    /// # }
+    /// ```
+    pub async fn send<T: ShipmentTrackingRequest>(
+        &self,
+        request: T,
+    ) -> Result<T::Response, DhlError> {
+        self.client.send(request).await
+    }
+}
+
+impl ShipmentTrackingApi<ApiMode> {
+    /// Overrides the base URL derived from the [ApiMode](ApiMode), e.g. to call a mock server in tests.
+    /// APIs of a [Sandbox](crate::api::Sandbox) or [Production](crate::api::Production) type
+    /// always call that environment.
+    pub fn base_url<T: Into<String>>(mut self, base_url: T) -> Self {
+        self.client = self.client.base_url(ApiProduct::ShipmentTracking, base_url);
+
+        self
+    }
+}
+
+/// Tracking shipments, as done by the [ShipmentTrackingApi](ShipmentTrackingApi).
+///
+/// Depend on this trait instead of the API struct to swap in a
+/// [FakeShipmentTracking](crate::fake::FakeShipmentTracking) in unit tests.
+#[async_trait]
+pub trait TrackShipments: Send + Sync {
+    async fn track(
+        &self,
+        request: GetShipmentTracking,
+    ) -> Result<GetShipmentTrackingResponse, DhlError>;
+}
+
+#[async_trait]
+impl<E: Environment> TrackShipments for ShipmentTrackingApi<E> {
+    async fn track(
+        &self,
+        request: GetShipmentTracking,
+    ) -> Result<GetShipmentTrackingResponse, DhlError> {
+        self.send(request).await
+    }
+}
+
+/// A marker trait for the [DhlRequests](crate::api::DhlRequest)
+/// which can be sent via the [ShipmentTrackingApi](ShipmentTrackingApi).
+pub trait ShipmentTrackingRequest: DhlRequest {}
+
+impl ShipmentTrackingRequest for GetShipmentTracking {}
+
+impl DhlRequest for GetShipmentTracking {
+    type Response = GetShipmentTrackingResponse;
+
+    fn product(&self) -> ApiProduct {
+        ApiProduct::ShipmentTracking
+    }
+
+    fn path(&self) -> String {
+        "/shipments".to_string()
+    }
+
+    fn query(&self) -> Result<Vec<(String, String)>, DhlError> {
+        query_params(self)
+    }
+
+    fn kind(&self) -> RequestKind {
+        RequestKind::ShipmentTracking
+    }
+
+    fn validate(&self) -> Result<(), DhlError> {
+        let mut validator = Validator::default();
+        validator.not_blank("tracking_number", &self.tracking_number);
+        validator.check(
+            !self.tracking_number.trim().contains(char::is_whitespace),
+            "tracking_number",
+            "must not contain whitespace",
+        );
+        if let Some(v) = &self.recipient_postal_code {
+            validator.not_blank("recipient_postal_code", v);
+        }
+        validator.range("limit", self.limit, 1, u32::MAX);
+
+        validator.finish()
+    }
+
+    /// Tracking data of delivered shipments never changes.
+    fn is_final(response: &Self::Response) -> bool {
+        !response.shipments.is_empty()
+            && response
+                .shipments
+                .iter()
+                .all(|v| matches!(v.status.status_code, ShipmentStatusCode::Delivered))
+            && response.warnings.is_empty()
+    }
+
+    fn decode_lenient(value: Value) -> Option<Self::Response> {
+        lenient::decode_response(value)
+    }
+}
+
+/// Part of a response that failed to decode and was skipped.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodeWarning {
+    /// JSON path of the value that failed, like `shipments[2].details.receiver.givenName`.
+    pub path: String,
+    pub message: String,
+    /// The skipped shipment or event.
+    pub raw: Value,
+}
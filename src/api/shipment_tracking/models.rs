@@ -0,0 +1,433 @@
+// @generated by `cargo run -p dhl_codegen` from `openapi/shipment-tracking.json`. Do not edit by hand.
+
+//! Requests and responses of DHL's "Shipment Tracking - Unified" API.
+
+use super::timestamp;
+use super::DecodeWarning;
+use crate::api::Address;
+use crate::api::CountryCode;
+use crate::api::Division;
+use crate::api::LanguageCode;
+use crate::redact::Pii;
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Parameters of the `GET /shipments` request.
+///
+/// Returns tracking data of the shipments with a tracking number.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetShipmentTracking {
+    pub(crate) tracking_number: String,
+    pub(crate) service: Option<Division>,
+    pub(crate) requester_country_code: Option<CountryCode>,
+    pub(crate) origin_country_code: Option<CountryCode>,
+    pub(crate) recipient_postal_code: Option<String>,
+    pub(crate) language: Option<LanguageCode>,
+    pub(crate) offset: Option<u32>,
+    pub(crate) limit: Option<u32>,
+}
+
+impl GetShipmentTracking {
+    pub fn new<T: Into<String>>(tracking_number: T) -> Self {
+        GetShipmentTracking {
+            tracking_number: tracking_number.into(),
+            service: None,
+            requester_country_code: None,
+            origin_country_code: None,
+            recipient_postal_code: None,
+            language: None,
+            offset: None,
+            limit: None,
+        }
+    }
+
+    /// Only returns shipments of this division.
+    pub fn service(mut self, service: Option<Division>) -> Self {
+        self.service = service;
+
+        self
+    }
+
+    /// Country of the consumer of the API, used to optimize the search.
+    pub fn requester_country_code(mut self, requester_country_code: Option<CountryCode>) -> Self {
+        self.requester_country_code = requester_country_code;
+
+        self
+    }
+
+    /// Country the shipment was sent from, used to optimize the search.
+    pub fn origin_country_code(mut self, origin_country_code: Option<CountryCode>) -> Self {
+        self.origin_country_code = origin_country_code;
+
+        self
+    }
+
+    /// Postal code of the destination address, needed for some divisions to return details like names.
+    pub fn recipient_postal_code<T: Into<String>>(
+        mut self,
+        recipient_postal_code: Option<T>,
+    ) -> Self {
+        self.recipient_postal_code = recipient_postal_code.map(|v| v.into());
+
+        self
+    }
+
+    /// Language of the event descriptions, if the division supports it.
+    pub fn language(mut self, language: Option<LanguageCode>) -> Self {
+        self.language = language;
+
+        self
+    }
+
+    /// Pagination offset.
+    pub fn offset(mut self, offset: Option<u32>) -> Self {
+        self.offset = offset;
+
+        self
+    }
+
+    /// Maximum number of shipments returned.
+    pub fn limit(mut self, limit: Option<u32>) -> Self {
+        self.limit = limit;
+
+        self
+    }
+}
+
+/// Successful response of a shipment tracking request.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetShipmentTrackingResponse {
+    pub url: Option<String>,
+    pub prev_url: Option<String>,
+    pub next_url: Option<String>,
+    pub first_url: Option<String>,
+    pub last_url: Option<String>,
+    pub shipments: Vec<Shipment>,
+    #[serde(default)]
+    pub possible_additional_shipments_url: Vec<String>,
+    /// Shipments and events skipped by a [lenient](super::ShipmentTrackingApi::lenient) API.
+    #[serde(skip)]
+    pub warnings: Vec<DecodeWarning>,
+}
+
+/// A shipment with its tracking information like status or ETA.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Shipment {
+    pub id: String,
+    pub service: Division,
+    pub origin: Option<ShipmentPathPoint>,
+    pub destination: Option<ShipmentPathPoint>,
+    pub status: ShipmentStatus,
+    #[serde(default, with = "timestamp::option")]
+    pub estimated_time_of_delivery: Option<NaiveDateTime>,
+    pub estimated_delivery_time_frame: Option<EstimatedDeliveryTimeFrame>,
+    pub estimated_time_of_delivery_remark: Option<String>,
+    pub service_url: Option<String>,
+    pub reroute_url: Option<String>,
+    #[serde(default)]
+    pub details: ShipmentDetail,
+    #[serde(default)]
+    pub events: Vec<ShipmentEvent>,
+}
+
+/// A stop on a shipment's route. Could be the origin, destination or a stop in between.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShipmentPathPoint {
+    pub address: Address,
+    pub service_point: Option<SimpleServicePoint>,
+}
+
+/// DHL service point that is a stop on a shipment's route.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimpleServicePoint {
+    pub url: String,
+    pub label: String,
+}
+
+/// Current status of a shipment.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShipmentStatus {
+    #[serde(with = "timestamp")]
+    pub timestamp: NaiveDateTime,
+    pub location: ShipmentPathPoint,
+    pub status_code: ShipmentStatusCode,
+    pub status: String,
+    #[serde(default)]
+    pub description: String,
+    pub piece_ids: Option<Vec<String>>,
+    pub remark: Option<String>,
+    pub next_steps: Option<String>,
+}
+
+impl fmt::Debug for ShipmentStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ShipmentStatus")
+            .field("timestamp", &self.timestamp)
+            .field("location", &self.location)
+            .field("status_code", &self.status_code)
+            .field("status", &self.status)
+            .field("description", &Pii(&self.description))
+            .field("piece_ids", &self.piece_ids)
+            .field("remark", &Pii(&self.remark))
+            .field("next_steps", &Pii(&self.next_steps))
+            .finish()
+    }
+}
+
+/// DHL's status codes for shipment tracking.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ShipmentStatusCode {
+    PreTransit,
+    Transit,
+    Delivered,
+    Failure,
+    /// Also used for values this crate does not know yet.
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EstimatedDeliveryTimeFrame {
+    #[serde(with = "timestamp")]
+    pub estimated_from: NaiveDateTime,
+    #[serde(with = "timestamp")]
+    pub estimated_through: NaiveDateTime,
+}
+
+/// Details on a tracked shipment.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShipmentDetail {
+    pub carrier: Option<ShipmentCarrier>,
+    pub receiver: Option<ShipmentParty>,
+    pub sender: Option<ShipmentParty>,
+    pub product: Option<ShipmentProduct>,
+    #[serde(default)]
+    pub proof_of_delivery_signed_available: bool,
+    pub proof_of_delivery: Option<ShipmentProofOfDelivery>,
+    #[serde(default)]
+    pub total_number_of_pieces: u32,
+    #[serde(default)]
+    pub piece_ids: Vec<String>,
+    pub weight: Option<ShipmentFloatWithUnit>,
+    pub volume: Option<ShipmentFloatWithUnit>,
+    pub loading_meters: Option<f64>,
+    pub dimensions: Option<ShipmentDimension>,
+    pub references: Option<Vec<ShipmentDetailReference>>,
+    #[serde(rename = "dgf:routes")]
+    pub dgf_routes: Option<Vec<ShipmentDgfRoute>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShipmentCarrier {
+    #[serde(rename = "@type")]
+    pub r#type: String,
+    pub organization_name: Option<String>,
+}
+
+/// Identification data for shipment parties like sender or receiver.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShipmentParty {
+    #[serde(rename = "@type")]
+    pub r#type: String,
+    pub organization_name: Option<String>,
+    pub family_name: Option<String>,
+    pub given_name: Option<String>,
+    pub name: Option<String>,
+}
+
+impl fmt::Debug for ShipmentParty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ShipmentParty")
+            .field("type", &self.r#type)
+            .field("organization_name", &Pii(&self.organization_name))
+            .field("family_name", &Pii(&self.family_name))
+            .field("given_name", &Pii(&self.given_name))
+            .field("name", &Pii(&self.name))
+            .finish()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShipmentProduct {
+    pub product_name: String,
+}
+
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShipmentProofOfDelivery {
+    #[serde(with = "timestamp")]
+    pub timestamp: NaiveDateTime,
+    pub signature_url: Option<String>,
+    pub document_url: Option<String>,
+    pub signed: Option<ShipmentSigned>,
+}
+
+impl fmt::Debug for ShipmentProofOfDelivery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ShipmentProofOfDelivery")
+            .field("timestamp", &self.timestamp)
+            .field("signature_url", &Pii(&self.signature_url))
+            .field("document_url", &Pii(&self.document_url))
+            .field("signed", &self.signed)
+            .finish()
+    }
+}
+
+/// Identification data on the subject signing for proof of delivery.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShipmentSigned {
+    #[serde(rename = "@type")]
+    pub r#type: String,
+    pub family_name: Option<String>,
+    pub given_name: Option<String>,
+    pub name: Option<String>,
+}
+
+impl fmt::Debug for ShipmentSigned {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ShipmentSigned")
+            .field("type", &self.r#type)
+            .field("family_name", &Pii(&self.family_name))
+            .field("given_name", &Pii(&self.given_name))
+            .field("name", &Pii(&self.name))
+            .finish()
+    }
+}
+
+/// Float value with a string specifying the unit.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShipmentFloatWithUnit {
+    pub value: f64,
+    pub unit_text: Option<String>,
+}
+
+/// Dimensions (x,y,z) of a shipment.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShipmentDimension {
+    pub width: Option<ShipmentFloatWithUnit>,
+    pub height: Option<ShipmentFloatWithUnit>,
+    pub length: Option<ShipmentFloatWithUnit>,
+}
+
+/// Labeled identification numbers for entities related to the shipment.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShipmentDetailReference {
+    pub number: String,
+    pub r#type: ShipmentDetailReferenceType,
+}
+
+/// Type of a reference to an entity related to the shipment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ShipmentDetailReferenceType {
+    CustomerReference,
+    CustomerConfirmationNumber,
+    LocalTrackingNumber,
+    EcommerceNumber,
+    Housebill,
+    Masterbill,
+    ContainerNumber,
+    ShipmentId,
+    DomesticConsignmentId,
+    Reference,
+}
+
+/// DHL Global Forwarding route.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShipmentDgfRoute {
+    #[serde(rename = "dgf:vesselName")]
+    pub dgf_vessel_name: Option<String>,
+    #[serde(rename = "dgf:voyageFlightNumber")]
+    pub dgf_voyage_flight_number: Option<String>,
+    #[serde(rename = "dgf:airportOfDeparture")]
+    pub dgf_airport_of_departure: Option<ShipmentDgfLocation>,
+    #[serde(rename = "dgf:airportOfDestination")]
+    pub dgf_airport_of_destination: Option<ShipmentDgfLocation>,
+    #[serde(
+        rename = "dgf:estimatedDepartureDate",
+        default,
+        with = "timestamp::option"
+    )]
+    pub dgf_estimated_departure_date: Option<NaiveDateTime>,
+    #[serde(
+        rename = "dgf:estimatedArrivalDate",
+        default,
+        with = "timestamp::option"
+    )]
+    pub dgf_estimated_arrival_date: Option<NaiveDateTime>,
+    #[serde(rename = "dgf:placeOfAcceptance")]
+    pub dgf_place_of_acceptance: Option<ShipmentDgfSimpleLocation>,
+    #[serde(rename = "dgf:portOfLoading")]
+    pub dgf_port_of_loading: Option<ShipmentDgfSimpleLocation>,
+    #[serde(rename = "dgf:portOfUnloading")]
+    pub dgf_port_of_unloading: Option<ShipmentDgfSimpleLocation>,
+    #[serde(rename = "dgf:placeOfDelivery")]
+    pub dgf_place_of_delivery: Option<ShipmentDgfSimpleLocation>,
+}
+
+/// Stop on a DHL Global Forwarding route.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShipmentDgfLocation {
+    #[serde(rename = "dgf:locationName")]
+    pub dgf_location_name: String,
+    #[serde(rename = "dgf:locationCode")]
+    pub dgf_location_code: Option<String>,
+    pub country_code: Option<CountryCode>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShipmentDgfSimpleLocation {
+    #[serde(rename = "dgf:locationName")]
+    pub dgf_location_name: String,
+}
+
+/// Significant point in time during shipment processing.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShipmentEvent {
+    #[serde(with = "timestamp")]
+    pub timestamp: NaiveDateTime,
+    pub location: Option<ShipmentPathPoint>,
+    pub status_code: Option<ShipmentStatusCode>,
+    pub status: Option<String>,
+    #[serde(default)]
+    pub description: String,
+    pub piece_ids: Option<Vec<String>>,
+    pub remark: Option<String>,
+    pub next_steps: Option<String>,
+}
+
+impl fmt::Debug for ShipmentEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ShipmentEvent")
+            .field("timestamp", &self.timestamp)
+            .field("location", &self.location)
+            .field("status_code", &self.status_code)
+            .field("status", &self.status)
+            .field("description", &Pii(&self.description))
+            .field("piece_ids", &self.piece_ids)
+            .field("remark", &Pii(&self.remark))
+            .field("next_steps", &Pii(&self.next_steps))
+            .finish()
+    }
+}
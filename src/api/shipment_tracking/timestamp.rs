@@ -0,0 +1,68 @@
+//! Lenient (de)serialization of timestamps.
+//!
+//! Divisions format timestamps differently: most send local times like `2022-03-02T11:24:00`,
+//! some add an offset or `Z`, some send fractional seconds or only a date. All of them are read
+//! as the local time they describe, dropping the offset, and written back without one.
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime};
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+pub fn serialize<S: Serializer>(value: &NaiveDateTime, serializer: S) -> Result<S::Ok, S::Error> {
+    value.serialize(serializer)
+}
+
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<NaiveDateTime, D::Error> {
+    let value = String::deserialize(deserializer)?;
+
+    parse(&value).ok_or_else(|| D::Error::custom(format!("invalid timestamp {:?}", value)))
+}
+
+fn parse(value: &str) -> Option<NaiveDateTime> {
+    let value = value.trim();
+
+    NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.f")
+        .or_else(|_| NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M"))
+        .or_else(|_| NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S%.f"))
+        .ok()
+        .or_else(|| {
+            DateTime::parse_from_rfc3339(value)
+                .ok()
+                .map(|v| v.naive_local())
+        })
+        .or_else(|| {
+            DateTime::parse_from_str(value, "%Y-%m-%dT%H:%M%:z")
+                .ok()
+                .map(|v| v.naive_local())
+        })
+        .or_else(|| {
+            NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                .ok()
+                .and_then(|v| v.and_hms_opt(0, 0, 0))
+        })
+}
+
+pub mod option {
+    use super::parse;
+    use chrono::NaiveDateTime;
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        value: &Option<NaiveDateTime>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        value.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<NaiveDateTime>, D::Error> {
+        match Option::<String>::deserialize(deserializer)? {
+            Some(value) => parse(&value)
+                .map(Some)
+                .ok_or_else(|| D::Error::custom(format!("invalid timestamp {:?}", value))),
+            None => Ok(None),
+        }
+    }
+}
@@ -0,0 +1,136 @@
+use super::CountryCode;
+use crate::error::{DhlError, Violation};
+
+/// Collects the constraints a request violates.
+#[derive(Default)]
+pub(crate) struct Validator {
+    violations: Vec<Violation>,
+}
+
+impl Validator {
+    /// Notes a violation of `field` unless `ok`.
+    pub(crate) fn check<T: Into<String>>(&mut self, ok: bool, field: &'static str, message: T) {
+        if !ok {
+            self.violations.push(Violation {
+                field,
+                message: message.into(),
+            });
+        }
+    }
+
+    pub(crate) fn not_blank(&mut self, field: &'static str, value: &str) {
+        self.check(!value.trim().is_empty(), field, "must not be blank");
+    }
+
+    pub(crate) fn range(&mut self, field: &'static str, value: Option<u32>, min: u32, max: u32) {
+        if let Some(v) = value {
+            self.check(
+                (min..=max).contains(&v),
+                field,
+                format!("must be between {} and {}, got {}", min, max, v),
+            );
+        }
+    }
+
+    pub(crate) fn coordinates(&mut self, latitude: f64, longitude: f64) {
+        self.check(
+            (-90.0..=90.0).contains(&latitude),
+            "latitude",
+            format!("must be between -90 and 90, got {}", latitude),
+        );
+        self.check(
+            (-180.0..=180.0).contains(&longitude),
+            "longitude",
+            format!("must be between -180 and 180, got {}", longitude),
+        );
+    }
+
+    pub(crate) fn postal_code(&mut self, field: &'static str, country: CountryCode, value: &str) {
+        self.check(
+            country.is_valid_postal_code(value),
+            field,
+            format!("{:?} is not a postal code of {:?}", value, country),
+        );
+    }
+
+    pub(crate) fn finish(self) -> Result<(), DhlError> {
+        if self.violations.is_empty() {
+            return Ok(());
+        }
+
+        Err(DhlError::InvalidRequest(self.violations))
+    }
+}
+
+impl CountryCode {
+    /// Whether `postal_code` looks like a postal code of the country.
+    /// Countries without a known format accept any short code of letters, digits, spaces and dashes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use dhl_wrapper::api::CountryCode;
+    /// assert!(CountryCode::De.is_valid_postal_code("20357"));
+    /// assert!(CountryCode::Nl.is_valid_postal_code("1012 AB"));
+    /// assert!(!CountryCode::De.is_valid_postal_code("2035"));
+    /// ```
+    pub fn is_valid_postal_code(&self, postal_code: &str) -> bool {
+        let postal_code = postal_code.trim();
+        match postal_code_formats(*self) {
+            Some(formats) => formats
+                .iter()
+                .any(|v| matches_format(v.as_bytes(), postal_code.as_bytes())),
+            None => {
+                (2..=10).contains(&postal_code.len())
+                    && postal_code
+                        .chars()
+                        .all(|v| v.is_ascii_alphanumeric() || v == ' ' || v == '-')
+            }
+        }
+    }
+}
+
+/// Postal code formats, `9` being a digit, `A` a letter and spaces optional.
+fn postal_code_formats(country: CountryCode) -> Option<&'static [&'static str]> {
+    use CountryCode::*;
+
+    let formats: &'static [&'static str] = match country {
+        De | Fr | It | Es | Fi | Ee | Hr | Mc | Sm => &["99999"],
+        At | Ch | Li | Be | Lu | Dk | No | Hu | Si | Bg | Cy | Au | Nz | Za => &["9999"],
+        Nl => &["9999 AA"],
+        Pl => &["99-999"],
+        Pt => &["9999-999"],
+        Se | Cz | Sk | Gr => &["999 99"],
+        Us => &["99999", "99999-9999"],
+        Ca => &["A9A 9A9"],
+        Gb => &[
+            "A9 9AA", "A99 9AA", "AA9 9AA", "AA99 9AA", "A9A 9AA", "AA9A 9AA",
+        ],
+        Jp => &["999-9999"],
+        In => &["999999"],
+        Cn => &["999999"],
+        Br => &["99999-999"],
+        _ => return None,
+    };
+
+    Some(formats)
+}
+
+fn matches_format(format: &[u8], value: &[u8]) -> bool {
+    match (format.split_first(), value.split_first()) {
+        (None, None) => true,
+        (Some((b' ', format)), _) => {
+            matches_format(format, value)
+                || value.first() == Some(&b' ') && matches_format(format, &value[1..])
+        }
+        (Some((f, format)), Some((v, value))) => {
+            let fits = match f {
+                b'9' => v.is_ascii_digit(),
+                b'A' => v.is_ascii_alphabetic(),
+                f => f == v,
+            };
+            fits && matches_format(format, value)
+        }
+        _ => false,
+    }
+}
@@ -0,0 +1,320 @@
+use crate::api::{AuthScheme, Body};
+use crate::error::DhlError;
+use crate::redact::Secret;
+use crate::transport::{HttpRequest, Transport};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use pool::PooledTransport;
+use serde::Deserialize;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use zeroize::Zeroize;
+
+mod pool;
+mod provider;
+
+pub use pool::{KeyPool, KeySelection, KeyUsage};
+pub use provider::*;
+
+/// Name of the header carrying API keys.
+pub const API_KEY_HEADER: &str = "DHL-API-Key";
+
+/// Lifetime assumed for tokens without an `expires_in`.
+const DEFAULT_TOKEN_LIFETIME: Duration = Duration::from_secs(60 * 60);
+
+/// Credentials for one of the [AuthSchemes](crate::api::AuthScheme) DHL's APIs use.
+#[derive(Debug, Clone)]
+pub enum Credentials {
+    /// Sent in the `DHL-API-Key` header.
+    ApiKey(Secret),
+    /// Keys taking turns in the `DHL-API-Key` header.
+    ApiKeyPool(KeyPool),
+    /// Sent as HTTP basic auth.
+    Basic { username: String, password: Secret },
+    /// Exchanged for bearer tokens at an OAuth2 token endpoint.
+    OAuth2(OAuth2),
+}
+
+impl Credentials {
+    pub fn api_key<T: Into<Secret>>(api_key: T) -> Self {
+        Credentials::ApiKey(api_key.into())
+    }
+
+    pub fn basic<T: Into<String>, U: Into<Secret>>(username: T, password: U) -> Self {
+        Credentials::Basic {
+            username: username.into(),
+            password: password.into(),
+        }
+    }
+
+    /// The scheme these credentials are used for.
+    pub fn scheme(&self) -> AuthScheme {
+        match self {
+            Credentials::ApiKey(_) | Credentials::ApiKeyPool(_) => AuthScheme::ApiKey,
+            Credentials::Basic { .. } => AuthScheme::Basic,
+            Credentials::OAuth2(_) => AuthScheme::OAuth2,
+        }
+    }
+}
+
+/// OAuth2 grant used to get tokens.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Grant {
+    /// Authenticates the client itself.
+    ClientCredentials,
+    /// Authenticates a user of the client, like a business customer portal user.
+    Password { username: String, password: Secret },
+}
+
+/// Credentials of an OAuth2 client.
+///
+/// # Example
+///
+/// ```
+/// # use dhl_wrapper::auth::*;
+/// # use std::time::Duration;
+/// let credentials = OAuth2::client_credentials(
+///     "https://api-eu.dhl.com/parcel/de/account/auth/ropc/v1/token",
+///     "your_client_id",
+///     "your_client_secret",
+/// )
+/// .refresh_before(Duration::from_secs(120));
+/// ```
+#[derive(Debug, Clone)]
+pub struct OAuth2 {
+    token_url: String,
+    client_id: String,
+    client_secret: Secret,
+    grant: Grant,
+    refresh_before: Duration,
+}
+
+impl OAuth2 {
+    pub fn client_credentials<T, U, V>(token_url: T, client_id: U, client_secret: V) -> Self
+    where
+        T: Into<String>,
+        U: Into<String>,
+        V: Into<Secret>,
+    {
+        OAuth2 {
+            token_url: token_url.into(),
+            client_id: client_id.into(),
+            client_secret: client_secret.into(),
+            grant: Grant::ClientCredentials,
+            refresh_before: Duration::from_secs(60),
+        }
+    }
+
+    /// Credentials using the password grant for `username`.
+    pub fn password<T: Into<String>, U: Into<Secret>>(mut self, username: T, password: U) -> Self {
+        self.grant = Grant::Password {
+            username: username.into(),
+            password: password.into(),
+        };
+
+        self
+    }
+
+    /// Renews tokens this long before they expire, so requests never race the expiry.
+    /// Defaults to a minute.
+    pub fn refresh_before(mut self, refresh_before: Duration) -> Self {
+        self.refresh_before = refresh_before;
+
+        self
+    }
+
+    /// Form parameters of a token request.
+    fn params(&self) -> Vec<(&str, &str)> {
+        let mut params = vec![
+            ("client_id", self.client_id.as_str()),
+            ("client_secret", self.client_secret.expose()),
+        ];
+        match &self.grant {
+            Grant::ClientCredentials => params.push(("grant_type", "client_credentials")),
+            Grant::Password { username, password } => {
+                params.push(("grant_type", "password"));
+                params.push(("username", username));
+                params.push(("password", password.expose()));
+            }
+        }
+
+        params
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    access_token: Secret,
+    expires_in: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenError {
+    error: String,
+    #[serde(default)]
+    error_description: String,
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    access_token: Secret,
+    refresh_at: Instant,
+}
+
+/// Cached token of an [OAuth2](OAuth2) client, renewed by one request at a time.
+#[derive(Debug)]
+struct Session {
+    credentials: OAuth2,
+    token: Mutex<Option<Token>>,
+    renewal: tokio::sync::Mutex<()>,
+}
+
+impl Session {
+    /// The cached token, unless it is due for renewal.
+    fn cached(&self) -> Option<Secret> {
+        self.token
+            .lock()
+            .expect("token lock poisoned")
+            .as_ref()
+            .filter(|v| Instant::now() < v.refresh_at)
+            .map(|v| v.access_token.clone())
+    }
+
+    async fn access_token(&self, transport: &Arc<dyn Transport>) -> Result<Secret, DhlError> {
+        if let Some(v) = self.cached() {
+            return Ok(v);
+        }
+
+        // concurrent callers wait here for the first one to renew the token
+        let _renewal = self.renewal.lock().await;
+        if let Some(v) = self.cached() {
+            return Ok(v);
+        }
+
+        let requested_at = Instant::now();
+        let token = self.request_token(transport).await?;
+        let lifetime = token
+            .expires_in
+            .map_or(DEFAULT_TOKEN_LIFETIME, Duration::from_secs);
+        let refresh_at = requested_at + lifetime.saturating_sub(self.credentials.refresh_before);
+        *self.token.lock().expect("token lock poisoned") = Some(Token {
+            access_token: token.access_token.clone(),
+            refresh_at,
+        });
+
+        Ok(token.access_token)
+    }
+
+    async fn request_token(
+        &self,
+        transport: &Arc<dyn Transport>,
+    ) -> Result<TokenResponse, DhlError> {
+        let body = Body::form(&self.credentials.params());
+        let response = transport
+            .execute(HttpRequest {
+                method: "POST".to_string(),
+                url: self.credentials.token_url.clone(),
+                headers: vec![("Content-Type".to_string(), body.content_type)],
+                body: Some(body.bytes),
+            })
+            .await?;
+
+        if !(200..300).contains(&response.status) {
+            let error = serde_json::from_slice::<TokenError>(&response.body).ok();
+            return Err(DhlError::ResponseNotOk {
+                status: response.status as u32,
+                title: error
+                    .as_ref()
+                    .map_or("token request failed".to_string(), |v| v.error.clone()),
+                detail: error.map(|v| v.error_description).unwrap_or_default(),
+            });
+        }
+
+        Ok(serde_json::from_slice(&response.body)?)
+    }
+}
+
+/// Credentials of a client, turning them into the headers each request needs.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Authenticator {
+    api_key: Option<Secret>,
+    key_pool: Option<KeyPool>,
+    basic: Option<Secret>,
+    oauth2: Option<Arc<Session>>,
+}
+
+impl Authenticator {
+    pub(crate) fn add(&mut self, credentials: Credentials) {
+        match credentials {
+            Credentials::ApiKey(v) => self.api_key = Some(v),
+            Credentials::ApiKeyPool(v) => self.key_pool = Some(v),
+            Credentials::Basic { username, password } => {
+                let mut pair = format!("{}:{}", username, password.expose());
+                self.basic = Some(Secret::new(STANDARD.encode(&pair)));
+                pair.zeroize();
+            }
+            Credentials::OAuth2(v) => {
+                self.oauth2 = Some(Arc::new(Session {
+                    credentials: v,
+                    token: Mutex::new(None),
+                    renewal: tokio::sync::Mutex::new(()),
+                }))
+            }
+        }
+    }
+
+    /// Headers authenticating a request with `scheme`, getting a token first if needed.
+    pub(crate) async fn headers(
+        &self,
+        scheme: AuthScheme,
+        transport: &Arc<dyn Transport>,
+    ) -> Result<Vec<(String, String)>, DhlError> {
+        let header = match scheme {
+            AuthScheme::None => return Ok(Vec::new()),
+            // set by the transport of the pool
+            AuthScheme::ApiKey if self.key_pool.is_some() => return Ok(Vec::new()),
+            AuthScheme::ApiKey => match &self.api_key {
+                Some(v) if !v.is_blank() => {
+                    (API_KEY_HEADER.to_string(), v.expose().trim().to_string())
+                }
+                Some(_) => return Err(missing("the API key is empty")),
+                None => return Err(missing("the request needs an API key")),
+            },
+            AuthScheme::Basic => match &self.basic {
+                Some(v) => ("Authorization".to_string(), format!("Basic {}", v.expose())),
+                None => return Err(missing("the request needs basic auth credentials")),
+            },
+            AuthScheme::OAuth2 => match &self.oauth2 {
+                Some(v) => {
+                    let token = v.access_token(transport).await?;
+                    (
+                        "Authorization".to_string(),
+                        format!("Bearer {}", token.expose()),
+                    )
+                }
+                None => return Err(missing("the request needs OAuth2 credentials")),
+            },
+        };
+
+        Ok(vec![header])
+    }
+
+    /// Transport for requests with `scheme`, taking keys from the key pool if there is one.
+    pub(crate) fn transport(
+        &self,
+        scheme: AuthScheme,
+        transport: &Arc<dyn Transport>,
+    ) -> Arc<dyn Transport> {
+        match (&self.key_pool, scheme) {
+            (Some(pool), AuthScheme::ApiKey) => Arc::new(PooledTransport {
+                pool: pool.clone(),
+                inner: transport.clone(),
+            }),
+            _ => transport.clone(),
+        }
+    }
+}
+
+fn missing(message: &str) -> DhlError {
+    DhlError::MissingCredentials(message.to_string())
+}
@@ -0,0 +1,279 @@
+use super::API_KEY_HEADER;
+use crate::error::DhlError;
+use crate::redact::Secret;
+use crate::transport::{HttpRequest, HttpResponse, Transport};
+use async_trait::async_trait;
+use serde::Deserialize;
+use std::fmt;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+/// How a [KeyPool](KeyPool) picks the key for the next request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeySelection {
+    /// One key after the other.
+    RoundRobin,
+    /// The key which sent the fewest requests so far.
+    LeastUsed,
+}
+
+/// Usage of a key in a [KeyPool](KeyPool), see [KeyPool::usage](KeyPool::usage).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyUsage {
+    /// Last characters of the key, like `…swow`.
+    pub key: String,
+    pub requests: u64,
+    /// Responses telling that the rate limit or quota of the key was exceeded.
+    pub rate_limited: u64,
+    /// Time left until the key is used again, if it is benched.
+    pub benched_for: Option<Duration>,
+}
+
+#[derive(Debug)]
+struct PooledKey {
+    key: Secret,
+    requests: u64,
+    rate_limited: u64,
+    benched_until: Option<Instant>,
+}
+
+impl PooledKey {
+    fn is_benched(&self, now: Instant) -> bool {
+        self.benched_until.is_some_and(|v| now < v)
+    }
+}
+
+#[derive(Debug)]
+struct PoolState {
+    keys: Vec<PooledKey>,
+    next: usize,
+}
+
+/// Several API keys of one API, e.g. of multiple DHL developer apps to spread a quota.
+///
+/// A key whose rate limit or quota is exceeded is benched until its window resets,
+/// as told by the `Retry-After` header, or for the [bench duration](KeyPool::bench_duration).
+/// The request is then sent again with the next key.
+///
+/// # Example
+///
+/// ```
+/// # use dhl_wrapper::api::shipment_tracking::*;
+/// # use dhl_wrapper::auth::*;
+/// let pool = KeyPool::new(["first_api_token", "second_api_token"])
+///     .selection(KeySelection::LeastUsed);
+/// let api = ShipmentTrackingApi::with_key_pool(pool.clone());
+///
+/// assert_eq!(pool.usage()[0].requests, 0);
+/// ```
+#[derive(Clone)]
+pub struct KeyPool {
+    state: Arc<Mutex<PoolState>>,
+    selection: KeySelection,
+    bench_duration: Duration,
+}
+
+impl fmt::Debug for KeyPool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KeyPool")
+            .field("usage", &self.usage())
+            .field("selection", &self.selection)
+            .field("bench_duration", &self.bench_duration)
+            .finish()
+    }
+}
+
+impl KeyPool {
+    /// Creates a pool picking `keys` round-robin. Blank keys are left out.
+    pub fn new<I, T>(keys: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<Secret>,
+    {
+        let keys = keys
+            .into_iter()
+            .map(Into::into)
+            .filter(|v: &Secret| !v.is_blank())
+            .map(|key| PooledKey {
+                key,
+                requests: 0,
+                rate_limited: 0,
+                benched_until: None,
+            })
+            .collect();
+
+        KeyPool {
+            state: Arc::new(Mutex::new(PoolState { keys, next: 0 })),
+            selection: KeySelection::RoundRobin,
+            bench_duration: Duration::from_secs(60),
+        }
+    }
+
+    pub fn selection(mut self, selection: KeySelection) -> Self {
+        self.selection = selection;
+
+        self
+    }
+
+    /// How long a key is benched if DHL does not tell when its window resets.
+    /// Defaults to a minute.
+    pub fn bench_duration(mut self, bench_duration: Duration) -> Self {
+        self.bench_duration = bench_duration;
+
+        self
+    }
+
+    /// Usage of every key, in the order they were given.
+    pub fn usage(&self) -> Vec<KeyUsage> {
+        let now = Instant::now();
+
+        self.state()
+            .keys
+            .iter()
+            .map(|v| KeyUsage {
+                key: mask(v.key.expose()),
+                requests: v.requests,
+                rate_limited: v.rate_limited,
+                benched_for: v.benched_until.filter(|&v| v > now).map(|v| v - now),
+            })
+            .collect()
+    }
+
+    fn state(&self) -> MutexGuard<'_, PoolState> {
+        self.state.lock().expect("key pool lock poisoned")
+    }
+
+    /// Picks the key of the next request and counts the request.
+    fn acquire(&self) -> Result<Secret, DhlError> {
+        let now = Instant::now();
+        let mut state = self.state();
+        if state.keys.is_empty() {
+            return Err(DhlError::MissingCredentials(
+                "the key pool is empty".to_string(),
+            ));
+        }
+
+        let len = state.keys.len();
+        let mut available = (0..len)
+            .map(|n| (state.next + n) % len)
+            .filter(|&i| !state.keys[i].is_benched(now));
+        let index = match self.selection {
+            KeySelection::RoundRobin => available.next(),
+            KeySelection::LeastUsed => available.min_by_key(|&i| (state.keys[i].requests, i)),
+        };
+        let index = match index {
+            Some(v) => v,
+            None => {
+                let wait = state
+                    .keys
+                    .iter()
+                    .filter_map(|v| v.benched_until)
+                    .min()
+                    .map_or(Duration::ZERO, |v| v.saturating_duration_since(now));
+                return Err(DhlError::RateLimited(wait));
+            }
+        };
+
+        state.next = (index + 1) % len;
+        let key = &mut state.keys[index];
+        key.requests += 1;
+
+        Ok(key.key.clone())
+    }
+
+    /// Benches `key` if `response` tells that its limit is exceeded. Returns whether it did.
+    fn record(&self, key: &Secret, response: &HttpResponse) -> bool {
+        if !is_limit_exceeded(response) {
+            return false;
+        }
+
+        let bench_duration = retry_after(response).unwrap_or(self.bench_duration);
+        let mut state = self.state();
+        if let Some(v) = state.keys.iter_mut().find(|v| &v.key == key) {
+            v.rate_limited += 1;
+            v.benched_until = Some(Instant::now() + bench_duration);
+        }
+
+        true
+    }
+}
+
+/// Last four characters of a key, enough to tell keys apart in reports.
+fn mask(key: &str) -> String {
+    let chars = key.chars().collect::<Vec<char>>();
+    let start = chars.len().saturating_sub(4);
+
+    format!("…{}", chars[start..].iter().collect::<String>())
+}
+
+#[derive(Deserialize)]
+struct Problem {
+    #[serde(default)]
+    title: String,
+    #[serde(default)]
+    detail: String,
+}
+
+/// Whether DHL says the rate limit or quota of the key is exceeded.
+fn is_limit_exceeded(response: &HttpResponse) -> bool {
+    if response.status == 429 {
+        return true;
+    }
+    if (200..300).contains(&response.status) {
+        return false;
+    }
+
+    serde_json::from_slice::<Problem>(&response.body).is_ok_and(|v| {
+        format!("{} {}", v.title, v.detail)
+            .to_lowercase()
+            .contains("quota")
+    })
+}
+
+/// Seconds in the `Retry-After` header.
+fn retry_after(response: &HttpResponse) -> Option<Duration> {
+    response
+        .headers
+        .iter()
+        .find(|(k, _)| k.eq_ignore_ascii_case("retry-after"))
+        .and_then(|(_, v)| v.trim().parse().ok())
+        .map(Duration::from_secs)
+}
+
+/// Transport sending each request with a key from the pool,
+/// and again with the next key if the limit of the first one is exceeded.
+pub(crate) struct PooledTransport {
+    pub(crate) pool: KeyPool,
+    pub(crate) inner: Arc<dyn Transport>,
+}
+
+#[async_trait]
+impl Transport for PooledTransport {
+    async fn execute(&self, request: HttpRequest) -> Result<HttpResponse, DhlError> {
+        let mut last = None;
+        loop {
+            let key = match self.pool.acquire() {
+                Ok(v) => v,
+                // every key is benched, so hand out the last response telling so
+                Err(DhlError::RateLimited(_)) if last.is_some() => break,
+                Err(e) => return Err(e),
+            };
+
+            let mut request = request.clone();
+            request
+                .headers
+                .retain(|(k, _)| !k.eq_ignore_ascii_case(API_KEY_HEADER));
+            request
+                .headers
+                .push((API_KEY_HEADER.to_string(), key.expose().to_string()));
+
+            let response = self.inner.execute(request).await?;
+            if !self.pool.record(&key, &response) {
+                return Ok(response);
+            }
+            last = Some(response);
+        }
+
+        Ok(last.expect("a response was recorded"))
+    }
+}
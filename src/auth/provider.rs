@@ -0,0 +1,276 @@
+use super::{Credentials, OAuth2};
+use crate::api::location_finder::ApiMode;
+use crate::api::ApiProduct;
+use crate::error::DhlError;
+use crate::redact::Secret;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// fields credentials are read from
+const API_KEY: &str = "api_key";
+const USERNAME: &str = "username";
+const PASSWORD: &str = "password";
+const CLIENT_ID: &str = "client_id";
+const CLIENT_SECRET: &str = "client_secret";
+const TOKEN_URL: &str = "token_url";
+
+/// A source of [Credentials](Credentials) per API product and environment,
+/// like environment variables, a config file or a secret manager.
+pub trait CredentialsProvider: Send + Sync {
+    /// Credentials for `product` in the environment `mode`, or
+    /// [DhlError::MissingCredentials](DhlError::MissingCredentials) naming what is missing.
+    /// Sandbox and production keys are kept apart, so a sandbox key never reaches production.
+    fn credentials(&self, product: ApiProduct, mode: ApiMode) -> Result<Credentials, DhlError>;
+}
+
+/// Builds credentials from the fields found by `field`, which names fields for error messages.
+///
+/// An `api_key` wins over OAuth2 fields, which win over a basic auth `username` and `password`.
+/// OAuth2 credentials with a `username` and `password` use the password grant.
+fn resolve<F, N>(field: F, name: N) -> Result<Credentials, DhlError>
+where
+    F: Fn(&str) -> Option<Secret>,
+    N: Fn(&str) -> String,
+{
+    if let Some(v) = field(API_KEY) {
+        return Ok(Credentials::ApiKey(v));
+    }
+
+    let require = |fields: &[&str]| {
+        let missing = fields
+            .iter()
+            .filter(|v| field(v).is_none())
+            .map(|v| name(v))
+            .collect::<Vec<String>>();
+        match missing.is_empty() {
+            true => Ok(()),
+            false => Err(DhlError::MissingCredentials(format!(
+                "missing {}",
+                missing.join(", ")
+            ))),
+        }
+    };
+    let value = |v: &str| field(v).unwrap_or_default();
+    let text = |v: &str| value(v).expose().to_string();
+
+    let has_any = |fields: &[&str]| fields.iter().any(|v| field(v).is_some());
+    if has_any(&[CLIENT_ID, CLIENT_SECRET, TOKEN_URL]) {
+        require(&[CLIENT_ID, CLIENT_SECRET, TOKEN_URL])?;
+        let oauth2 =
+            OAuth2::client_credentials(text(TOKEN_URL), text(CLIENT_ID), value(CLIENT_SECRET));
+        if !has_any(&[USERNAME, PASSWORD]) {
+            return Ok(Credentials::OAuth2(oauth2));
+        }
+        require(&[USERNAME, PASSWORD])?;
+
+        return Ok(Credentials::OAuth2(
+            oauth2.password(text(USERNAME), value(PASSWORD)),
+        ));
+    }
+    if has_any(&[USERNAME, PASSWORD]) {
+        require(&[USERNAME, PASSWORD])?;
+
+        return Ok(Credentials::basic(text(USERNAME), value(PASSWORD)));
+    }
+
+    Err(DhlError::MissingCredentials(format!(
+        "missing {}",
+        name(API_KEY)
+    )))
+}
+
+/// Reads credentials from environment variables named after the product,
+/// like `SHIPMENT_TRACKING_API_KEY` or `LOCATION_FINDER_CLIENT_ID`.
+/// Those of the sandbox start with `SANDBOX_`, like `SANDBOX_SHIPMENT_TRACKING_API_KEY`.
+///
+/// # Example
+///
+/// ```
+/// # use dhl_wrapper::api::location_finder::ApiMode;
+/// # use dhl_wrapper::api::ApiProduct;
+/// # use dhl_wrapper::auth::*;
+/// # use dhl_wrapper::error::DhlError;
+/// let provider = EnvCredentials::new().prefix("MY_APP_");
+///
+/// match provider.credentials(ApiProduct::ShipmentTracking, ApiMode::Sandbox) {
+///     Err(DhlError::MissingCredentials(v)) => {
+///         assert_eq!(v, "missing MY_APP_SANDBOX_SHIPMENT_TRACKING_API_KEY")
+///     }
+///     v => panic!("unexpected {:?}", v),
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct EnvCredentials {
+    prefix: String,
+}
+
+impl EnvCredentials {
+    pub fn new() -> Self {
+        EnvCredentials::default()
+    }
+
+    /// Prepends `prefix` to every variable name.
+    pub fn prefix<T: Into<String>>(mut self, prefix: T) -> Self {
+        self.prefix = prefix.into();
+
+        self
+    }
+
+    fn var(&self, product: ApiProduct, mode: ApiMode, field: &str) -> String {
+        let environment = match mode {
+            ApiMode::Sandbox => "SANDBOX_",
+            ApiMode::Production => "",
+        };
+
+        format!("{}{}{}_{}", self.prefix, environment, product.name(), field).to_uppercase()
+    }
+}
+
+impl CredentialsProvider for EnvCredentials {
+    fn credentials(&self, product: ApiProduct, mode: ApiMode) -> Result<Credentials, DhlError> {
+        resolve(
+            |field| {
+                std::env::var(self.var(product, mode, field))
+                    .ok()
+                    .map(Secret::from)
+                    .filter(|v| !v.is_blank())
+            },
+            |field| self.var(product, mode, field),
+        )
+    }
+}
+
+/// Reads credentials from a TOML or JSON file with a table per product,
+/// and one per product in the `sandbox` table for the sandbox.
+///
+/// ```toml
+/// [shipment_tracking]
+/// api_key = "your_api_key"
+///
+/// [location_finder]
+/// api_key = "your_other_api_key"
+///
+/// [sandbox.shipment_tracking]
+/// api_key = "your_sandbox_api_key"
+/// ```
+#[derive(Debug, Clone)]
+pub struct FileCredentials {
+    path: PathBuf,
+    file: CredentialsFile,
+}
+
+/// Fields by product, in production and the sandbox.
+#[derive(Debug, Clone, Deserialize)]
+struct CredentialsFile {
+    #[serde(default)]
+    sandbox: BTreeMap<String, BTreeMap<String, Secret>>,
+    #[serde(flatten)]
+    production: BTreeMap<String, BTreeMap<String, Secret>>,
+}
+
+impl FileCredentials {
+    /// Loads `path`, which must end with `.toml` or `.json`.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, DhlError> {
+        let path = path.as_ref().to_path_buf();
+        let content = fs::read_to_string(&path)?;
+        let invalid = |e: &dyn std::fmt::Display| {
+            DhlError::Config(format!(
+                "invalid credentials file {}: {}",
+                path.display(),
+                e
+            ))
+        };
+        let file = match path.extension().and_then(|v| v.to_str()) {
+            Some("toml") => toml::from_str(&content).map_err(|e| invalid(&e))?,
+            Some("json") => serde_json::from_str(&content).map_err(|e| invalid(&e))?,
+            _ => {
+                return Err(DhlError::Config(format!(
+                    "credentials file {} is neither .toml nor .json",
+                    path.display()
+                )))
+            }
+        };
+
+        Ok(FileCredentials { path, file })
+    }
+}
+
+impl CredentialsProvider for FileCredentials {
+    fn credentials(&self, product: ApiProduct, mode: ApiMode) -> Result<Credentials, DhlError> {
+        let (products, table) = match mode {
+            ApiMode::Sandbox => (&self.file.sandbox, "sandbox."),
+            ApiMode::Production => (&self.file.production, ""),
+        };
+        let fields = products.get(product.name());
+
+        resolve(
+            |field| {
+                fields
+                    .and_then(|v| v.get(field))
+                    .filter(|v| !v.is_blank())
+                    .cloned()
+            },
+            |field| {
+                format!(
+                    "{}{}.{} in {}",
+                    table,
+                    product.name(),
+                    field,
+                    self.path.display()
+                )
+            },
+        )
+    }
+}
+
+/// Asks providers in order, returning the first credentials found.
+///
+/// # Example
+///
+/// ```
+/// # use dhl_wrapper::auth::*;
+/// let provider = ChainedCredentials::new()
+///     .with(EnvCredentials::new())
+///     .with(EnvCredentials::new().prefix("DHL_"));
+/// ```
+#[derive(Default)]
+pub struct ChainedCredentials {
+    providers: Vec<Box<dyn CredentialsProvider>>,
+}
+
+impl ChainedCredentials {
+    pub fn new() -> Self {
+        ChainedCredentials::default()
+    }
+
+    /// Asks `provider` after the ones added before.
+    pub fn with<T: CredentialsProvider + 'static>(mut self, provider: T) -> Self {
+        self.providers.push(Box::new(provider));
+
+        self
+    }
+}
+
+impl CredentialsProvider for ChainedCredentials {
+    /// Fails with what every provider is missing if none has credentials,
+    /// or with the first error which is not about missing credentials.
+    fn credentials(&self, product: ApiProduct, mode: ApiMode) -> Result<Credentials, DhlError> {
+        let mut missing = Vec::new();
+        for provider in &self.providers {
+            match provider.credentials(product, mode) {
+                Ok(v) => return Ok(v),
+                Err(DhlError::MissingCredentials(v)) => missing.push(v),
+                Err(e) => return Err(e),
+            }
+        }
+
+        match missing.is_empty() {
+            true => Err(DhlError::MissingCredentials(
+                "no credentials providers".to_string(),
+            )),
+            false => Err(DhlError::MissingCredentials(missing.join("; "))),
+        }
+    }
+}
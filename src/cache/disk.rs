@@ -0,0 +1,216 @@
+use super::{CacheStore, CachedResponse};
+use crate::error::DhlError;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Cache store persisting responses in a directory, so they survive restarts
+/// and can be shared between test runs.
+///
+/// Every response is stored as a `<hash>.json` file holding its key, status, headers and timestamps
+/// next to a `<hash>.body` file holding the raw body. Failing file system operations are
+/// treated like cache misses.
+///
+/// # Example
+///
+/// ```no_run
+/// # use dhl_wrapper::api::shipment_tracking::*;
+/// # use dhl_wrapper::cache::*;
+/// let store = DiskCache::new("target/dhl-cache")
+///     .unwrap()
+///     .max_size(50 * 1024 * 1024);
+/// let cache = ResponseCache::new(store).offline_only(std::env::var("CI").is_ok());
+///
+/// let api = ShipmentTrackingApi::new("your_api_token").cache(cache);
+/// ```
+pub struct DiskCache {
+    dir: PathBuf,
+    max_size: Option<u64>,
+    lock: Mutex<()>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Metadata {
+    key: String,
+    status: u16,
+    headers: Vec<(String, String)>,
+    stored_at: u64,
+    expires_at: Option<u64>,
+}
+
+impl DiskCache {
+    /// Creates a store in `dir`, creating the directory if needed.
+    pub fn new<P: Into<PathBuf>>(dir: P) -> Result<Self, DhlError> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+
+        Ok(DiskCache {
+            dir,
+            max_size: None,
+            lock: Mutex::new(()),
+        })
+    }
+
+    /// Limits the total size of stored files in bytes.
+    /// Once exceeded, the oldest responses are evicted.
+    pub fn max_size(mut self, bytes: u64) -> Self {
+        self.max_size = Some(bytes);
+
+        self
+    }
+
+    /// Removes all responses that expired before now.
+    pub fn purge_expired(&self) -> Result<(), DhlError> {
+        let _guard = self.lock.lock().expect("disk cache lock poisoned");
+        let now = SystemTime::now();
+
+        for (hash, metadata, _) in self.entries()? {
+            if metadata.expires_at.map(from_millis).unwrap_or(now) < now {
+                self.remove_files(&hash);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn paths(&self, hash: &str) -> (PathBuf, PathBuf) {
+        (
+            self.dir.join(format!("{}.json", hash)),
+            self.dir.join(format!("{}.body", hash)),
+        )
+    }
+
+    fn remove_files(&self, hash: &str) {
+        let (metadata_path, body_path) = self.paths(hash);
+        let _ = fs::remove_file(metadata_path);
+        let _ = fs::remove_file(body_path);
+    }
+
+    /// Lists all entries as hash, metadata and size of both files.
+    fn entries(&self) -> Result<Vec<(String, Metadata, u64)>, DhlError> {
+        let mut entries = Vec::new();
+
+        for file in fs::read_dir(&self.dir)? {
+            let path = file?.path();
+            if path.extension().and_then(|v| v.to_str()) != Some("json") {
+                continue;
+            }
+            let hash = match path.file_stem().and_then(|v| v.to_str()) {
+                Some(v) => v.to_string(),
+                None => continue,
+            };
+            let metadata = match read_metadata(&path) {
+                Some(v) => v,
+                None => continue,
+            };
+            let (_, body_path) = self.paths(&hash);
+            let size = file_size(&path) + file_size(&body_path);
+
+            entries.push((hash, metadata, size));
+        }
+
+        Ok(entries)
+    }
+
+    fn evict(&self, max_size: u64) -> Result<(), DhlError> {
+        let mut entries = self.entries()?;
+        let mut total = entries.iter().map(|(_, _, size)| size).sum::<u64>();
+        if total <= max_size {
+            return Ok(());
+        }
+
+        entries.sort_by_key(|(_, metadata, _)| metadata.stored_at);
+        for (hash, _, size) in entries {
+            if total <= max_size {
+                break;
+            }
+            self.remove_files(&hash);
+            total = total.saturating_sub(size);
+        }
+
+        Ok(())
+    }
+}
+
+impl CacheStore for DiskCache {
+    fn get(&self, key: &str) -> Option<CachedResponse> {
+        let _guard = self.lock.lock().expect("disk cache lock poisoned");
+        let (metadata_path, body_path) = self.paths(&hash(key));
+
+        // the key is compared to rule out hash collisions
+        let metadata = read_metadata(&metadata_path).filter(|v| v.key == key)?;
+        let body = fs::read(body_path).ok()?;
+
+        Some(CachedResponse {
+            status: metadata.status,
+            headers: metadata.headers,
+            body,
+            stored_at: from_millis(metadata.stored_at),
+            expires_at: metadata.expires_at.map(from_millis),
+        })
+    }
+
+    fn put(&self, key: &str, response: CachedResponse) {
+        let _guard = self.lock.lock().expect("disk cache lock poisoned");
+        let (metadata_path, body_path) = self.paths(&hash(key));
+
+        let metadata = Metadata {
+            key: key.to_string(),
+            status: response.status,
+            headers: response.headers,
+            stored_at: to_millis(response.stored_at),
+            expires_at: response.expires_at.map(to_millis),
+        };
+        let metadata = match serde_json::to_vec_pretty(&metadata) {
+            Ok(v) => v,
+            Err(_) => return,
+        };
+
+        // the body is written first, so there is never metadata without a body
+        if fs::write(&body_path, &response.body).is_err()
+            || fs::write(&metadata_path, metadata).is_err()
+        {
+            let _ = fs::remove_file(body_path);
+            return;
+        }
+
+        if let Some(max_size) = self.max_size {
+            let _ = self.evict(max_size);
+        }
+    }
+
+    fn remove(&self, key: &str) {
+        let _guard = self.lock.lock().expect("disk cache lock poisoned");
+        self.remove_files(&hash(key));
+    }
+}
+
+fn read_metadata(path: &Path) -> Option<Metadata> {
+    let bytes = fs::read(path).ok()?;
+    serde_json::from_slice(&bytes).ok()
+}
+
+fn file_size(path: &Path) -> u64 {
+    fs::metadata(path).map(|v| v.len()).unwrap_or(0)
+}
+
+/// 64 bit FNV-1a hash, used for file names since it is stable across Rust versions.
+fn hash(key: &str) -> String {
+    let hash = key.bytes().fold(0xcbf29ce484222325_u64, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+    });
+
+    format!("{:016x}", hash)
+}
+
+fn to_millis(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|v| v.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+fn from_millis(millis: u64) -> SystemTime {
+    UNIX_EPOCH + Duration::from_millis(millis)
+}
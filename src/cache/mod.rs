@@ -0,0 +1,340 @@
+use reqwest::Url;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+
+#[cfg(feature = "disk-cache")]
+mod disk;
+#[cfg(feature = "disk-cache")]
+pub use disk::DiskCache;
+
+/// A response as stored in a [CacheStore](CacheStore).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CachedResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+    pub stored_at: SystemTime,
+    /// `None` for responses that never expire, like tracking data of delivered shipments.
+    pub expires_at: Option<SystemTime>,
+}
+
+/// A trait all cache backends must implement in order to be used by a [ResponseCache](ResponseCache).
+/// Keys are normalized request URLs, see [cache_key](cache_key).
+pub trait CacheStore: Send + Sync {
+    fn get(&self, key: &str) -> Option<CachedResponse>;
+
+    fn put(&self, key: &str, response: CachedResponse);
+
+    fn remove(&self, key: &str);
+}
+
+/// In-memory cache store evicting the least recently used entry once its capacity is reached.
+pub struct MemoryCache {
+    capacity: usize,
+    inner: Mutex<MemoryCacheInner>,
+}
+
+struct MemoryCacheInner {
+    tick: u64,
+    entries: HashMap<String, (u64, CachedResponse)>,
+}
+
+impl MemoryCache {
+    /// Creates a store holding at most `capacity` responses.
+    pub fn new(capacity: usize) -> Self {
+        MemoryCache {
+            capacity: capacity.max(1),
+            inner: Mutex::new(MemoryCacheInner {
+                tick: 0,
+                entries: HashMap::new(),
+            }),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.inner
+            .lock()
+            .expect("cache lock poisoned")
+            .entries
+            .len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl CacheStore for MemoryCache {
+    fn get(&self, key: &str) -> Option<CachedResponse> {
+        let mut inner = self.inner.lock().expect("cache lock poisoned");
+        inner.tick += 1;
+        let tick = inner.tick;
+
+        inner.entries.get_mut(key).map(|(last_used, response)| {
+            *last_used = tick;
+            response.clone()
+        })
+    }
+
+    fn put(&self, key: &str, response: CachedResponse) {
+        let mut inner = self.inner.lock().expect("cache lock poisoned");
+        inner.tick += 1;
+        let tick = inner.tick;
+
+        if !inner.entries.contains_key(key) && inner.entries.len() >= self.capacity {
+            let least_recently_used = inner
+                .entries
+                .iter()
+                .min_by_key(|(_, (last_used, _))| *last_used)
+                .map(|(k, _)| k.clone());
+            if let Some(k) = least_recently_used {
+                inner.entries.remove(&k);
+            }
+        }
+
+        inner.entries.insert(key.to_string(), (tick, response));
+    }
+
+    fn remove(&self, key: &str) {
+        self.inner
+            .lock()
+            .expect("cache lock poisoned")
+            .entries
+            .remove(key);
+    }
+}
+
+/// Kinds of requests, used to configure a TTL per kind in a [CachePolicy](CachePolicy).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RequestKind {
+    ShipmentTracking,
+    LocationsByAddress,
+    LocationsByGeo,
+    LocationByKeywordId,
+    LocationById,
+    /// Requests of APIs without a kind of their own.
+    Other,
+}
+
+/// Decides how long responses are cached.
+#[derive(Debug, Clone)]
+pub struct CachePolicy {
+    default_ttl: Duration,
+    ttls: HashMap<RequestKind, Duration>,
+    stale_while_revalidate: Duration,
+    cache_delivered_forever: bool,
+}
+
+impl Default for CachePolicy {
+    /// Caches tracking data for 5 minutes and location data for a day.
+    /// Tracking data of delivered shipments is cached forever.
+    fn default() -> Self {
+        CachePolicy::new(Duration::from_secs(24 * 60 * 60))
+            .ttl(RequestKind::ShipmentTracking, Duration::from_secs(5 * 60))
+    }
+}
+
+impl CachePolicy {
+    /// Creates a policy caching all responses for `default_ttl`.
+    pub fn new(default_ttl: Duration) -> Self {
+        CachePolicy {
+            default_ttl,
+            ttls: HashMap::new(),
+            stale_while_revalidate: Duration::ZERO,
+            cache_delivered_forever: true,
+        }
+    }
+
+    /// Overrides the TTL for one kind of request. `Duration::ZERO` disables caching for it.
+    pub fn ttl(mut self, kind: RequestKind, ttl: Duration) -> Self {
+        self.ttls.insert(kind, ttl);
+
+        self
+    }
+
+    /// For this long after expiry, a cached response is still returned
+    /// while a fresh one is fetched in the background.
+    pub fn stale_while_revalidate(mut self, value: Duration) -> Self {
+        self.stale_while_revalidate = value;
+
+        self
+    }
+
+    /// Whether tracking responses whose shipments are all delivered are cached without expiry.
+    /// Enabled by default.
+    pub fn cache_delivered_forever(mut self, value: bool) -> Self {
+        self.cache_delivered_forever = value;
+
+        self
+    }
+
+    pub fn ttl_for(&self, kind: RequestKind) -> Duration {
+        self.ttls.get(&kind).cloned().unwrap_or(self.default_ttl)
+    }
+}
+
+pub(crate) enum Lookup {
+    Fresh(CachedResponse),
+    Stale(CachedResponse),
+    Miss,
+}
+
+/// Caches responses of both APIs, keyed by normalized request URL.
+/// Cloning is cheap and clones share their entries, so one cache can be used by several APIs.
+///
+/// # Example
+///
+/// ```
+/// # use dhl_wrapper::api::location_finder::*;
+/// # use dhl_wrapper::cache::*;
+/// # use std::time::Duration;
+/// let cache = ResponseCache::memory(1000).policy(
+///     CachePolicy::default()
+///         .ttl(RequestKind::LocationById, Duration::from_secs(60 * 60))
+///         .stale_while_revalidate(Duration::from_secs(60)),
+/// );
+///
+/// let api = LocationFinderApi::new(ApiMode::Production, "your_api_token").cache(cache);
+/// ```
+#[derive(Clone)]
+pub struct ResponseCache {
+    store: Arc<dyn CacheStore>,
+    policy: Arc<CachePolicy>,
+    refreshing: Arc<Mutex<HashSet<String>>>,
+    offline_only: bool,
+}
+
+impl ResponseCache {
+    /// Creates a cache using a custom store and the [default policy](CachePolicy::default).
+    pub fn new<S: CacheStore + 'static>(store: S) -> Self {
+        ResponseCache {
+            store: Arc::new(store),
+            policy: Arc::new(CachePolicy::default()),
+            refreshing: Arc::new(Mutex::new(HashSet::new())),
+            offline_only: false,
+        }
+    }
+
+    /// Creates an in-memory LRU cache holding at most `capacity` responses.
+    pub fn memory(capacity: usize) -> Self {
+        ResponseCache::new(MemoryCache::new(capacity))
+    }
+
+    pub fn policy(mut self, policy: CachePolicy) -> Self {
+        self.policy = Arc::new(policy);
+
+        self
+    }
+
+    /// In offline only mode, cached responses are served even if they expired and
+    /// requests without a cached response fail with [DhlError::CacheMiss](crate::error::DhlError::CacheMiss)
+    /// instead of calling DHL. Useful in development and CI to save the daily quota.
+    pub fn offline_only(mut self, value: bool) -> Self {
+        self.offline_only = value;
+
+        self
+    }
+
+    pub fn is_offline_only(&self) -> bool {
+        self.offline_only
+    }
+
+    /// Drops the cached response for `url`, if any.
+    pub fn invalidate(&self, url: &str) {
+        self.store.remove(&cache_key(url));
+    }
+
+    pub(crate) fn lookup(&self, key: &str) -> Lookup {
+        let response = match self.store.get(key) {
+            Some(v) => v,
+            None => return Lookup::Miss,
+        };
+        if self.offline_only {
+            return Lookup::Fresh(response);
+        }
+
+        let now = SystemTime::now();
+        match response.expires_at {
+            None => Lookup::Fresh(response),
+            Some(expires_at) if now < expires_at => Lookup::Fresh(response),
+            Some(expires_at) if now < expires_at + self.policy.stale_while_revalidate => {
+                Lookup::Stale(response)
+            }
+            Some(_) => {
+                self.store.remove(key);
+                Lookup::Miss
+            }
+        }
+    }
+
+    /// Stores a successful response. `delivered` marks tracking responses
+    /// whose shipments are all delivered.
+    pub(crate) fn store(
+        &self,
+        key: &str,
+        kind: RequestKind,
+        mut response: CachedResponse,
+        delivered: bool,
+    ) {
+        let ttl = self.policy.ttl_for(kind);
+        if delivered && self.policy.cache_delivered_forever {
+            response.expires_at = None;
+        } else if ttl.is_zero() {
+            return;
+        } else {
+            response.expires_at = Some(response.stored_at + ttl);
+        }
+
+        self.store.put(key, response);
+    }
+
+    /// Marks `key` as being refreshed. Returns `false` if a refresh is already running.
+    pub(crate) fn start_refresh(&self, key: &str) -> bool {
+        self.refreshing
+            .lock()
+            .expect("cache lock poisoned")
+            .insert(key.to_string())
+    }
+
+    pub(crate) fn finish_refresh(&self, key: &str) {
+        self.refreshing
+            .lock()
+            .expect("cache lock poisoned")
+            .remove(key);
+    }
+}
+
+/// Normalizes a request URL into a cache key: scheme and host are lowercased
+/// and query parameters are sorted, so equivalent requests share an entry.
+///
+/// # Example
+///
+/// ```
+/// # use dhl_wrapper::cache::cache_key;
+/// assert_eq!(
+///     cache_key("https://API.dhl.com/track/shipments?service=express&trackingNumber=123"),
+///     cache_key("https://api.dhl.com/track/shipments?trackingNumber=123&service=express"),
+/// );
+/// ```
+pub fn cache_key(url: &str) -> String {
+    let mut url = match Url::parse(url) {
+        Ok(v) => v,
+        Err(_) => return url.to_string(),
+    };
+
+    let mut pairs = url
+        .query_pairs()
+        .map(|(k, v)| (k.into_owned(), v.into_owned()))
+        .collect::<Vec<(String, String)>>();
+    pairs.sort();
+
+    if pairs.is_empty() {
+        url.set_query(None);
+    } else {
+        url.query_pairs_mut().clear().extend_pairs(pairs);
+    }
+    url.set_fragment(None);
+
+    url.to_string()
+}
@@ -0,0 +1,312 @@
+use serde::de::value::BorrowedStrDeserializer;
+use serde::de::{
+    self, DeserializeOwned, DeserializeSeed, Deserializer, MapAccess, SeqAccess, Visitor,
+};
+use serde::{forward_to_deserialize_any, Serialize};
+use serde_json::Value;
+
+/// What differs between a response and the models.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DriftKind {
+    /// A field the model does not know and drops.
+    UnknownField,
+    /// An enum value the model does not know, decoded to its catch-all variant.
+    UnknownEnumValue(String),
+    /// A field missing in the response, decoded to its default value.
+    MissingField,
+}
+
+/// A difference between a response and the models, found while decoding the response.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaDrift {
+    pub kind: DriftKind,
+    /// Name of the struct or enum, like `ShipmentDetail`.
+    pub type_name: &'static str,
+    /// JSON path of the field or value, like `shipments[0].details.carrier`.
+    pub path: String,
+}
+
+/// A trait all drift observers must implement in order to be told about [SchemaDrift](SchemaDrift)
+/// by an API. Implemented for closures, too.
+///
+/// # Example
+///
+/// ```
+/// # use dhl_wrapper::api::shipment_tracking::ShipmentTrackingApi;
+/// let api = ShipmentTrackingApi::new("your_api_token").drift_observer(|drift: &_| {
+///     eprintln!("DHL changed their payloads: {:?}", drift);
+/// });
+/// ```
+pub trait DriftObserver: Send + Sync {
+    fn observe(&self, drift: &SchemaDrift);
+}
+
+impl<F: Fn(&SchemaDrift) + Send + Sync> DriftObserver for F {
+    fn observe(&self, drift: &SchemaDrift) {
+        self(drift)
+    }
+}
+
+/// Finds the drift between a response `value` and the model `res` it decoded to.
+pub(crate) fn detect<R: DeserializeOwned + Serialize>(value: &Value, res: &R) -> Vec<SchemaDrift> {
+    let mut report = Report::default();
+    let tracked = Tracked {
+        value,
+        path: Vec::new(),
+        report: &mut report,
+    };
+    if R::deserialize(tracked).is_err() {
+        return Vec::new();
+    }
+
+    // defaulted fields only show up in the decoded model
+    let decoded = serde_json::to_value(res).unwrap_or(Value::Null);
+    for visited in &report.structs {
+        let object = match lookup(&decoded, &visited.path) {
+            Some(Value::Object(v)) => v,
+            _ => continue,
+        };
+        for (key, value) in object {
+            if !value.is_null() && !visited.has_field(key) {
+                report.drift.push(SchemaDrift {
+                    kind: DriftKind::MissingField,
+                    type_name: visited.type_name,
+                    path: format_path(&child(&visited.path, Segment::Key(key.clone()))),
+                });
+            }
+        }
+    }
+
+    report.drift
+}
+
+fn lookup<'a>(value: &'a Value, path: &[Segment]) -> Option<&'a Value> {
+    path.iter().try_fold(value, |value, segment| match segment {
+        Segment::Key(v) => value.get(v),
+        Segment::Index(v) => value.get(v),
+    })
+}
+
+#[derive(Debug, Clone)]
+enum Segment {
+    Key(String),
+    Index(usize),
+}
+
+/// Formats a path like `shipments[0].details`.
+fn format_path(path: &[Segment]) -> String {
+    let mut res = String::new();
+    for segment in path {
+        match segment {
+            Segment::Key(v) if res.is_empty() => res.push_str(v),
+            Segment::Key(v) => res.push_str(&format!(".{}", v)),
+            Segment::Index(v) => res.push_str(&format!("[{}]", v)),
+        }
+    }
+
+    res
+}
+
+fn child(path: &[Segment], segment: Segment) -> Vec<Segment> {
+    let mut path = path.to_vec();
+    path.push(segment);
+
+    path
+}
+
+#[derive(Default)]
+struct Report {
+    drift: Vec<SchemaDrift>,
+    structs: Vec<VisitedStruct>,
+}
+
+/// A JSON object decoded to a struct.
+struct VisitedStruct {
+    path: Vec<Segment>,
+    type_name: &'static str,
+    keys: Vec<String>,
+}
+
+impl VisitedStruct {
+    /// Whether the object has the field serialized as `name`, or an alias of it.
+    /// Aliases like `dgf:vesselName` and `@type` are matched by their letters.
+    fn has_field(&self, name: &str) -> bool {
+        let letters = |v: &str| {
+            v.chars()
+                .filter(|v| v.is_alphanumeric())
+                .flat_map(|v| v.to_lowercase())
+                .collect::<String>()
+        };
+
+        self.keys
+            .iter()
+            .any(|v| v == name || letters(v) == letters(name))
+    }
+}
+
+/// Deserializer over a JSON value, noting unknown fields and enum values on the way.
+struct Tracked<'de, 'r> {
+    value: &'de Value,
+    path: Vec<Segment>,
+    report: &'r mut Report,
+}
+
+impl<'de, 'r> Deserializer<'de> for Tracked<'de, 'r> {
+    type Error = serde_json::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.value {
+            Value::Null => visitor.visit_unit(),
+            Value::Bool(v) => visitor.visit_bool(*v),
+            Value::Number(v) => v.deserialize_any(visitor),
+            Value::String(v) => visitor.visit_borrowed_str(v),
+            Value::Array(v) => visitor.visit_seq(Seq {
+                iter: v.iter().enumerate(),
+                path: self.path,
+                report: self.report,
+            }),
+            Value::Object(v) => visitor.visit_map(Object {
+                iter: v.iter(),
+                next: None,
+                path: self.path,
+                report: self.report,
+            }),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.value {
+            Value::Null => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        if let Value::Object(object) = self.value {
+            for key in object.keys().filter(|v| !fields.contains(&v.as_str())) {
+                self.report.drift.push(SchemaDrift {
+                    kind: DriftKind::UnknownField,
+                    type_name: name,
+                    path: format_path(&child(&self.path, Segment::Key(key.clone()))),
+                });
+            }
+            self.report.structs.push(VisitedStruct {
+                path: self.path.clone(),
+                type_name: name,
+                keys: object.keys().cloned().collect(),
+            });
+        }
+
+        self.deserialize_any(visitor)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        match self.value {
+            Value::String(v) => {
+                if !variants.contains(&v.as_str()) {
+                    self.report.drift.push(SchemaDrift {
+                        kind: DriftKind::UnknownEnumValue(v.clone()),
+                        type_name: name,
+                        path: format_path(&self.path),
+                    });
+                }
+                visitor.visit_enum(BorrowedStrDeserializer::new(v))
+            }
+            v => v.deserialize_enum(name, variants, visitor),
+        }
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_unit()
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map identifier
+    }
+}
+
+struct Seq<'de, 'r> {
+    iter: std::iter::Enumerate<std::slice::Iter<'de, Value>>,
+    path: Vec<Segment>,
+    report: &'r mut Report,
+}
+
+impl<'de> SeqAccess<'de> for Seq<'de, '_> {
+    type Error = serde_json::Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Self::Error> {
+        let (i, value) = match self.iter.next() {
+            Some(v) => v,
+            None => return Ok(None),
+        };
+
+        seed.deserialize(Tracked {
+            value,
+            path: child(&self.path, Segment::Index(i)),
+            report: &mut *self.report,
+        })
+        .map(Some)
+    }
+}
+
+struct Object<'de, 'r> {
+    iter: serde_json::map::Iter<'de>,
+    next: Option<(&'de String, &'de Value)>,
+    path: Vec<Segment>,
+    report: &'r mut Report,
+}
+
+impl<'de> MapAccess<'de> for Object<'de, '_> {
+    type Error = serde_json::Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Self::Error> {
+        self.next = self.iter.next();
+        match self.next {
+            Some((key, _)) => seed
+                .deserialize(BorrowedStrDeserializer::new(key))
+                .map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, Self::Error> {
+        let (key, value) = self
+            .next
+            .take()
+            .ok_or_else(|| de::Error::custom("value requested before key"))?;
+
+        seed.deserialize(Tracked {
+            value,
+            path: child(&self.path, Segment::Key(key.clone())),
+            report: &mut *self.report,
+        })
+    }
+}
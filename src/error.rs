@@ -1,3 +1,4 @@
+use std::fmt;
 use thiserror::Error;
 
 /// Custom Error representing what could go wrong when building requests and calling APIs.
@@ -11,8 +12,53 @@ pub enum DhlError {
         title: String,
         detail: String,
     },
+    #[error("InvalidRequest Error: {}", list(.0))]
+    InvalidRequest(Vec<Violation>),
     #[error("Reqwest Error: {0}")]
     Reqwest(#[from] reqwest::Error),
     #[error("Serde Error: {0}")]
     Serde(#[from] serde_json::Error),
+    #[error("Decode Error at `{path}`: {message} (status {status}, url {url})")]
+    Decode {
+        /// JSON path of the value that failed, like `shipments[2].details.receiver.givenName`.
+        path: String,
+        message: String,
+        /// Request URL with secrets redacted.
+        url: String,
+        status: u32,
+        /// Start of the response body. Left out of the error message, as it may hold personal data.
+        snippet: String,
+    },
+    #[error("Io Error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Transport Error: {0}")]
+    Transport(String),
+    #[error("RateLimited Error: every API key is benched for another {0:?}")]
+    RateLimited(std::time::Duration),
+    #[error("Config Error: {0}")]
+    Config(String),
+    #[error("CacheMiss Error: no cached response for {0} and the cache is offline only")]
+    CacheMiss(String),
+}
+
+/// A constraint violated by a request, see [DhlError::InvalidRequest](DhlError::InvalidRequest).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    /// Name of the request parameter, like `radius`.
+    pub field: &'static str,
+    pub message: String,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.field, self.message)
+    }
+}
+
+fn list(violations: &[Violation]) -> String {
+    violations
+        .iter()
+        .map(|v| v.to_string())
+        .collect::<Vec<String>>()
+        .join(", ")
 }
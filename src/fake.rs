@@ -0,0 +1,425 @@
+use crate::api::location_finder::*;
+use crate::api::shipment_tracking::*;
+use crate::api::{serialized_name, DhlRequest, ServiceType};
+use crate::error::DhlError;
+use async_trait::async_trait;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// Default radius of DHL's location search in meters.
+const DEFAULT_RADIUS: f64 = 5000.0;
+
+/// Fake [TrackShipments](crate::api::shipment_tracking::TrackShipments) answering from
+/// registered shipments instead of calling DHL.
+///
+/// A shipment journey is a list of tracking states of the same shipment. Every request for its
+/// tracking number returns the next state, the last one is repeated once the journey is over.
+/// Unknown tracking numbers are answered with a 404 like DHL does.
+/// Invalid requests fail validation just like they do in the real API, and are not recorded.
+///
+/// Clones share their shipments and requests, so a clone can be handed to the code under test
+/// while the original is used for assertions.
+///
+/// # Example
+///
+/// ```
+/// # #[tokio::main]
+/// # async fn main() {
+/// # use dhl_wrapper::api::shipment_tracking::*;
+/// # use dhl_wrapper::fake::*;
+/// async fn is_delivered(api: &dyn TrackShipments, tracking_number: &str) -> bool {
+///     match api.track(GetShipmentTracking::new(tracking_number)).await {
+///         Ok(v) => v
+///             .shipments
+///             .iter()
+///             .all(|v| matches!(v.status.status_code, ShipmentStatusCode::Delivered)),
+///         Err(_) => false,
+///     }
+/// }
+///
+/// let fake = FakeShipmentTracking::new().fail("00340434161094042557", 500);
+///
+/// assert!(!is_delivered(&fake, "00340434161094042557").await);
+/// assert_eq!(fake.requests(), vec![GetShipmentTracking::new("00340434161094042557")]);
+/// # }
+/// ```
+#[derive(Clone, Default)]
+pub struct FakeShipmentTracking {
+    inner: Arc<Mutex<TrackingState>>,
+}
+
+#[derive(Default)]
+struct TrackingState {
+    journeys: HashMap<String, Journey>,
+    errors: HashMap<String, u32>,
+    requests: Vec<GetShipmentTracking>,
+}
+
+struct Journey {
+    stages: Vec<Shipment>,
+    position: usize,
+}
+
+impl FakeShipmentTracking {
+    pub fn new() -> Self {
+        FakeShipmentTracking::default()
+    }
+
+    /// Registers a shipment under its id, always returning the same tracking state.
+    pub fn shipment(self, shipment: Shipment) -> Self {
+        let tracking_number = shipment.id.clone();
+
+        self.journey(tracking_number, vec![shipment])
+    }
+
+    /// Registers a journey for `tracking_number`, returning the next of `stages` on every request.
+    pub fn journey<T: Into<String>>(self, tracking_number: T, stages: Vec<Shipment>) -> Self {
+        self.lock().journeys.insert(
+            tracking_number.into(),
+            Journey {
+                stages,
+                position: 0,
+            },
+        );
+
+        self
+    }
+
+    /// Answers all requests for `tracking_number` with an error of the given HTTP status.
+    pub fn fail<T: Into<String>>(self, tracking_number: T, status: u32) -> Self {
+        self.lock().errors.insert(tracking_number.into(), status);
+
+        self
+    }
+
+    /// All requests made so far, in order.
+    pub fn requests(&self) -> Vec<GetShipmentTracking> {
+        self.lock().requests.clone()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, TrackingState> {
+        self.inner.lock().expect("fake lock poisoned")
+    }
+}
+
+#[async_trait]
+impl TrackShipments for FakeShipmentTracking {
+    async fn track(
+        &self,
+        request: GetShipmentTracking,
+    ) -> Result<GetShipmentTrackingResponse, DhlError> {
+        request.validate()?;
+        let mut state = self.lock();
+        state.requests.push(request.clone());
+
+        if let Some(status) = state.errors.get(&request.tracking_number) {
+            return Err(error(*status));
+        }
+
+        let journey = match state.journeys.get_mut(&request.tracking_number) {
+            Some(v) if !v.stages.is_empty() => v,
+            _ => return Err(error(404)),
+        };
+        let shipment = journey.stages[journey.position.min(journey.stages.len() - 1)].clone();
+        journey.position += 1;
+
+        if request.service.is_some_and(|v| v != shipment.service) {
+            return Err(error(404));
+        }
+
+        Ok(GetShipmentTrackingResponse {
+            url: None,
+            prev_url: None,
+            next_url: None,
+            first_url: None,
+            last_url: None,
+            shipments: vec![shipment],
+            possible_additional_shipments_url: Vec::new(),
+            warnings: Vec::new(),
+        })
+    }
+}
+
+/// A request received by a [FakeLocationFinder](FakeLocationFinder).
+#[derive(Debug, Clone, PartialEq)]
+pub enum LocationFinderCall {
+    FindByAddress(GetLocationsByAddress),
+    FindByGeo(GetLocationsByGeo),
+    FindByKeywordId(GetLocationByKeywordId),
+    LocationById(GetLocationById),
+}
+
+/// Fake [FindLocations](crate::api::location_finder::FindLocations) searching registered
+/// locations instead of calling DHL.
+///
+/// Searches by address match the country, postal code and locality of a location,
+/// searches by coordinates use the radius of the request. Both apply the location type,
+/// service type and provider type filters as well as the limit.
+/// Invalid requests fail validation just like they do in the real API, and are not recorded.
+///
+/// Clones share their locations and requests, so a clone can be handed to the code under test
+/// while the original is used for assertions.
+///
+/// # Example
+///
+/// ```
+/// # #[tokio::main]
+/// # async fn main() {
+/// # use dhl_wrapper::api::location_finder::*;
+/// # use dhl_wrapper::fake::*;
+/// let fake = FakeLocationFinder::new();
+/// fake.fail_next(429);
+///
+/// let result = fake.location_by_id(GetLocationById::new("8003-4101479")).await;
+///
+/// assert!(result.is_err());
+/// assert_eq!(
+///     fake.requests(),
+///     vec![LocationFinderCall::LocationById(GetLocationById::new("8003-4101479"))]
+/// );
+/// # }
+/// ```
+#[derive(Clone, Default)]
+pub struct FakeLocationFinder {
+    inner: Arc<Mutex<LocationState>>,
+}
+
+#[derive(Default)]
+struct LocationState {
+    locations: Vec<ServicePoint>,
+    errors: VecDeque<u32>,
+    requests: Vec<LocationFinderCall>,
+}
+
+impl FakeLocationFinder {
+    pub fn new() -> Self {
+        FakeLocationFinder::default()
+    }
+
+    /// Registers a location to be found.
+    pub fn location(self, location: ServicePoint) -> Self {
+        self.lock().locations.push(location);
+
+        self
+    }
+
+    /// Answers the next request with an error of the given HTTP status.
+    /// Calling this several times queues up errors for the following requests.
+    pub fn fail_next(&self, status: u32) {
+        self.lock().errors.push_back(status);
+    }
+
+    /// All requests made so far, in order.
+    pub fn requests(&self) -> Vec<LocationFinderCall> {
+        self.lock().requests.clone()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, LocationState> {
+        self.inner.lock().expect("fake lock poisoned")
+    }
+
+    /// Records the request and returns the locations, or the next queued error.
+    fn receive(&self, call: LocationFinderCall) -> Result<Vec<ServicePoint>, DhlError> {
+        let mut state = self.lock();
+        state.requests.push(call);
+
+        match state.errors.pop_front() {
+            Some(status) => Err(error(status)),
+            None => Ok(state.locations.clone()),
+        }
+    }
+}
+
+#[async_trait]
+impl FindLocations for FakeLocationFinder {
+    async fn find_by_address(
+        &self,
+        request: GetLocationsByAddress,
+    ) -> Result<GetLocationsResponse, DhlError> {
+        request.validate()?;
+        let locations = self.receive(LocationFinderCall::FindByAddress(request.clone()))?;
+        let country_code = serialized_name(&request.country_code);
+        let filters = LocationFilters::new(
+            request.location_type.as_deref(),
+            request.service_type.as_deref(),
+            request.provider_type,
+        );
+
+        let mut locations = locations
+            .into_iter()
+            .filter(|v| {
+                let address = &v.place.address;
+                matches(Some(&country_code), address.country_code.as_deref())
+                    && matches(
+                        request.postal_code.as_deref(),
+                        address.postal_code.as_deref(),
+                    )
+                    && matches(
+                        request.address_locality.as_deref(),
+                        address.address_locality.as_deref(),
+                    )
+                    && filters.matches(v)
+            })
+            .collect::<Vec<ServicePoint>>();
+        if let Some(limit) = request.limit {
+            locations.truncate(limit as usize);
+        }
+
+        Ok(GetLocationsResponse { locations })
+    }
+
+    async fn find_by_geo(
+        &self,
+        request: GetLocationsByGeo,
+    ) -> Result<GetLocationsResponse, DhlError> {
+        request.validate()?;
+        let locations = self.receive(LocationFinderCall::FindByGeo(request.clone()))?;
+        let origin = Geo {
+            latitude: request.latitude,
+            longitude: request.longitude,
+        };
+        let radius = request.radius.map_or(DEFAULT_RADIUS, f64::from);
+        let filters = LocationFilters::new(
+            request.location_type.as_deref(),
+            request.service_type.as_deref(),
+            request.provider_type,
+        );
+
+        let mut locations = locations
+            .into_iter()
+            .filter(|v| filters.matches(v))
+            .filter_map(|mut v| {
+                let distance = origin.distance_to(&v.place.geo);
+                if distance > radius {
+                    return None;
+                }
+                v.distance = Some(distance.round() as u32);
+
+                Some(v)
+            })
+            .collect::<Vec<ServicePoint>>();
+        locations.sort_by_key(|v| v.distance);
+        if let Some(limit) = request.limit {
+            locations.truncate(limit as usize);
+        }
+
+        Ok(GetLocationsResponse { locations })
+    }
+
+    async fn find_by_keyword_id(
+        &self,
+        request: GetLocationByKeywordId,
+    ) -> Result<GetLocationResponse, DhlError> {
+        request.validate()?;
+        let locations = self.receive(LocationFinderCall::FindByKeywordId(request.clone()))?;
+        let country_code = serialized_name(&request.country_code);
+
+        locations
+            .into_iter()
+            .find(|v| {
+                let address = &v.place.address;
+                v.location.keyword_id == request.keyword_id
+                    && matches(Some(&country_code), address.country_code.as_deref())
+                    && matches(Some(&request.postal_code), address.postal_code.as_deref())
+            })
+            .ok_or_else(|| error(404))
+    }
+
+    async fn location_by_id(
+        &self,
+        request: GetLocationById,
+    ) -> Result<GetLocationResponse, DhlError> {
+        request.validate()?;
+        let locations = self.receive(LocationFinderCall::LocationById(request.clone()))?;
+
+        locations
+            .into_iter()
+            .find(|v| v.location.ids.iter().any(|v| v.location_id == request.id))
+            .ok_or_else(|| error(404))
+    }
+}
+
+/// Title and detail of the problem DHL responds with for the given HTTP status.
+/// Shared by the fakes and the mock server.
+pub(crate) fn problem_details(status: u32) -> (&'static str, &'static str) {
+    match status {
+        401 => ("Unauthorized", "Invalid API key."),
+        404 => (
+            "No result found",
+            "No result found for the given parameters.",
+        ),
+        429 => ("Too many requests", "Quota exceeded."),
+        500 => ("Internal Server Error", "Something went wrong."),
+        _ => ("Error", "Error injected by a fake API."),
+    }
+}
+
+/// Builds the error the APIs return for a response with the given HTTP status.
+fn error(status: u32) -> DhlError {
+    let (title, detail) = problem_details(status);
+
+    DhlError::ResponseNotOk {
+        status,
+        title: title.to_string(),
+        detail: detail.to_string(),
+    }
+}
+
+/// A missing expectation matches everything, a given one has to equal the value (ignoring case).
+pub(crate) fn matches(expected: Option<&str>, value: Option<&str>) -> bool {
+    match expected {
+        Some(expected) => value.is_some_and(|v| v.eq_ignore_ascii_case(expected)),
+        None => true,
+    }
+}
+
+/// Location type, service type and provider filters of the location searches, by wire name.
+/// Shared by the fakes and the mock server.
+#[derive(Debug, Default)]
+pub(crate) struct LocationFilters {
+    pub location_types: Option<Vec<String>>,
+    pub service_types: Option<Vec<String>>,
+    pub provider_type: Option<String>,
+}
+
+impl LocationFilters {
+    fn new(
+        location_types: Option<&[LocationType]>,
+        service_types: Option<&[ServiceType]>,
+        provider_type: Option<ProviderType>,
+    ) -> Self {
+        LocationFilters {
+            location_types: location_types.map(|v| v.iter().map(serialized_name).collect()),
+            service_types: service_types.map(|v| v.iter().map(serialized_name).collect()),
+            provider_type: provider_type.map(|v| serialized_name(&v)),
+        }
+    }
+
+    /// Whether a location is of any of the location types, offers all of the service types
+    /// and is run by the provider.
+    pub(crate) fn matches(&self, location: &ServicePoint) -> bool {
+        let location_type_matches = match &self.location_types {
+            Some(types) => types.contains(&serialized_name(&location.location.r#type)),
+            None => true,
+        };
+        let service_types_match = match &self.service_types {
+            Some(types) => types.iter().all(|expected| {
+                location
+                    .service_types
+                    .iter()
+                    .any(|v| &serialized_name(v) == expected)
+            }),
+            None => true,
+        };
+        let provider_type_matches = match &self.provider_type {
+            Some(provider) => location
+                .location
+                .ids
+                .iter()
+                .any(|id| id.provider.eq_ignore_ascii_case(provider)),
+            None => true,
+        };
+
+        location_type_matches && service_types_match && provider_type_matches
+    }
+}
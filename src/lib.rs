@@ -13,5 +13,27 @@
 /// Implementation of various DHL APIs.
 pub mod api;
 
+/// Credentials for the authentication schemes of DHL's APIs, including OAuth2 token management.
+pub mod auth;
+
+/// Secrets, and redaction of personal data in `Debug` output and in shipments to be stored.
+pub mod redact;
+
+/// Response caching for both APIs.
+pub mod cache;
+
+/// Transports carrying requests to DHL, including record/replay cassettes for tests.
+pub mod transport;
+
+/// Reporting of differences between DHL's responses and the models.
+pub mod drift;
+
+/// In-process fakes of both APIs, for unit tests of code depending on them.
+pub mod fake;
+
+/// Local mock server behaving like DHL's APIs, for integration tests.
+#[cfg(feature = "mock-server")]
+pub mod mock;
+
 /// Custom error enum.
 pub mod error;
@@ -0,0 +1,486 @@
+use crate::api::location_finder::{Geo, ServicePoint};
+use crate::api::shipment_tracking::Shipment;
+use crate::error::DhlError;
+use crate::fake::{matches, problem_details, LocationFilters};
+use axum::extract::{Path, Query, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use serde_json::json;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio::sync::oneshot;
+
+type Params = Query<HashMap<String, String>>;
+
+/// Errors the [MockServer](MockServer) can be told to respond with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InjectedError {
+    /// 401, as DHL responds to missing or invalid API keys.
+    Unauthorized,
+    /// 404, as DHL responds to unknown tracking numbers or locations.
+    NotFound,
+    /// 429, as DHL responds once the quota or rate limit is exceeded.
+    TooManyRequests,
+    /// 500
+    InternalServerError,
+}
+
+impl InjectedError {
+    fn status(&self) -> StatusCode {
+        match self {
+            InjectedError::Unauthorized => StatusCode::UNAUTHORIZED,
+            InjectedError::NotFound => StatusCode::NOT_FOUND,
+            InjectedError::TooManyRequests => StatusCode::TOO_MANY_REQUESTS,
+            InjectedError::InternalServerError => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    fn response(&self) -> Response {
+        let status = self.status();
+        let (title, detail) = problem_details(status.as_u16().into());
+
+        problem(status, title, detail)
+    }
+}
+
+/// Tracking data and locations the [MockServer](MockServer) responds with.
+#[derive(Debug, Clone, Default)]
+pub struct Fixtures {
+    pub shipments: Vec<Shipment>,
+    pub locations: Vec<ServicePoint>,
+}
+
+impl Fixtures {
+    pub fn new() -> Self {
+        Fixtures::default()
+    }
+
+    pub fn shipment(mut self, shipment: Shipment) -> Self {
+        self.shipments.push(shipment);
+
+        self
+    }
+
+    pub fn location(mut self, location: ServicePoint) -> Self {
+        self.locations.push(location);
+
+        self
+    }
+}
+
+#[derive(Default)]
+struct MockState {
+    api_keys: HashSet<String>,
+    fixtures: Fixtures,
+    latency: Duration,
+    queued_errors: VecDeque<InjectedError>,
+    permanent_error: Option<InjectedError>,
+    requests: Vec<String>,
+}
+
+type SharedState = Arc<Mutex<MockState>>;
+
+/// Local HTTP server behaving like DHL's "Shipment Tracking - Unified" and
+/// "Location Finder - Unified" APIs, for integration tests without network or API keys.
+///
+/// Point the APIs to it via their `base_url` method.
+/// The server shuts down when it is dropped.
+///
+/// # Example
+///
+/// ```
+/// # #[tokio::main]
+/// # async fn main() {
+/// # use dhl_wrapper::api::shipment_tracking::*;
+/// # use dhl_wrapper::error::DhlError;
+/// # use dhl_wrapper::mock::*;
+/// let server = MockServer::start(Fixtures::new()).await.unwrap();
+///
+/// let api = ShipmentTrackingApi::new(server.api_key())
+///     .base_url(server.shipment_tracking_url());
+///
+/// server.fail_next(InjectedError::TooManyRequests);
+/// let result = api.send(GetShipmentTracking::new("123456789")).await;
+///
+/// assert!(matches!(result, Err(DhlError::ResponseNotOk { status: 429, .. })));
+/// # }
+/// ```
+pub struct MockServer {
+    addr: SocketAddr,
+    state: SharedState,
+    shutdown: Option<oneshot::Sender<()>>,
+}
+
+impl MockServer {
+    /// API key the server accepts unless others are added via [api_keys](MockServer::api_keys).
+    pub const DEFAULT_API_KEY: &'static str = "mock-api-key";
+
+    /// Starts a server on a random local port.
+    pub async fn start(fixtures: Fixtures) -> Result<Self, DhlError> {
+        let state = Arc::new(Mutex::new(MockState {
+            api_keys: HashSet::from([MockServer::DEFAULT_API_KEY.to_string()]),
+            fixtures,
+            ..MockState::default()
+        }));
+
+        let app = Router::new()
+            .route("/track/shipments", get(track_shipments))
+            .route("/location-finder/v1/find-by-address", get(find_by_address))
+            .route("/location-finder/v1/find-by-geo", get(find_by_geo))
+            .route(
+                "/location-finder/v1/find-by-keyword-id",
+                get(find_by_keyword_id),
+            )
+            .route("/location-finder/v1/locations/:id", get(location_by_id))
+            .with_state(state.clone());
+
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let addr = listener.local_addr()?;
+        let (shutdown, shutdown_signal) = oneshot::channel::<()>();
+        tokio::spawn(async move {
+            let _ = axum::serve(listener, app)
+                .with_graceful_shutdown(async {
+                    let _ = shutdown_signal.await;
+                })
+                .await;
+        });
+
+        Ok(MockServer {
+            addr,
+            state,
+            shutdown: Some(shutdown),
+        })
+    }
+
+    pub fn url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// Base URL to pass to [ShipmentTrackingApi::base_url](crate::api::shipment_tracking::ShipmentTrackingApi::base_url).
+    pub fn shipment_tracking_url(&self) -> String {
+        format!("{}/track", self.url())
+    }
+
+    /// Base URL to pass to [LocationFinderApi::base_url](crate::api::location_finder::LocationFinderApi::base_url).
+    pub fn location_finder_url(&self) -> String {
+        format!("{}/location-finder/v1", self.url())
+    }
+
+    /// An API key the server accepts.
+    pub fn api_key(&self) -> String {
+        MockServer::DEFAULT_API_KEY.to_string()
+    }
+
+    /// Replaces the accepted API keys. Requests with other keys get a 401.
+    pub fn api_keys<I: IntoIterator<Item = T>, T: Into<String>>(&self, api_keys: I) {
+        self.lock().api_keys = api_keys.into_iter().map(|v| v.into()).collect();
+    }
+
+    pub fn add_shipment(&self, shipment: Shipment) {
+        self.lock().fixtures.shipments.push(shipment);
+    }
+
+    pub fn add_location(&self, location: ServicePoint) {
+        self.lock().fixtures.locations.push(location);
+    }
+
+    /// Delays every response by `latency`.
+    pub fn latency(&self, latency: Duration) {
+        self.lock().latency = latency;
+    }
+
+    /// Responds to the next request with `error`. Queued errors are used up in order.
+    pub fn fail_next(&self, error: InjectedError) {
+        self.lock().queued_errors.push_back(error);
+    }
+
+    /// Responds to all requests with `error` until called with `None`.
+    pub fn fail_always(&self, error: Option<InjectedError>) {
+        self.lock().permanent_error = error;
+    }
+
+    /// Paths and queries of all requests received so far.
+    pub fn requests(&self) -> Vec<String> {
+        self.lock().requests.clone()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, MockState> {
+        self.state.lock().expect("mock server lock poisoned")
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            let _ = shutdown.send(());
+        }
+    }
+}
+
+fn problem(status: StatusCode, title: &str, detail: &str) -> Response {
+    (
+        status,
+        [("content-type", "application/problem+json")],
+        Json(json!({
+            "status": status.as_u16(),
+            "title": title,
+            "detail": detail,
+        })),
+    )
+        .into_response()
+}
+
+/// Simulates latency, checks the API key and injected errors, and logs the request.
+/// Returns the response to send instead of the regular one, if any.
+async fn check(
+    state: &SharedState,
+    headers: &HeaderMap,
+    path: String,
+    params: &HashMap<String, String>,
+) -> Option<Response> {
+    let latency = state.lock().expect("mock server lock poisoned").latency;
+    if !latency.is_zero() {
+        tokio::time::sleep(latency).await;
+    }
+
+    let mut state = state.lock().expect("mock server lock poisoned");
+    let mut query = params
+        .iter()
+        .map(|(k, v)| format!("{}={}", k, v))
+        .collect::<Vec<String>>();
+    query.sort();
+    state.requests.push(if query.is_empty() {
+        path
+    } else {
+        format!("{}?{}", path, query.join("&"))
+    });
+
+    if let Some(error) = state.queued_errors.pop_front().or(state.permanent_error) {
+        return Some(error.response());
+    }
+
+    let api_key = headers.get("DHL-API-Key").and_then(|v| v.to_str().ok());
+    match api_key {
+        Some(v) if state.api_keys.contains(v) => None,
+        _ => Some(InjectedError::Unauthorized.response()),
+    }
+}
+
+fn not_found() -> Response {
+    InjectedError::NotFound.response()
+}
+
+async fn track_shipments(
+    State(state): State<SharedState>,
+    headers: HeaderMap,
+    Query(params): Params,
+) -> Response {
+    if let Some(v) = check(&state, &headers, "/track/shipments".into(), &params).await {
+        return v;
+    }
+
+    let tracking_number = match params.get("trackingNumber") {
+        Some(v) => v,
+        None => {
+            return problem(
+                StatusCode::BAD_REQUEST,
+                "Bad request",
+                "trackingNumber is required.",
+            )
+        }
+    };
+
+    let state = state.lock().expect("mock server lock poisoned");
+    let shipments = state
+        .fixtures
+        .shipments
+        .iter()
+        .filter(|v| &v.id == tracking_number)
+        .collect::<Vec<&Shipment>>();
+    if shipments.is_empty() {
+        return not_found();
+    }
+
+    Json(json!({
+        "shipments": shipments,
+        "possibleAdditionalShipmentsUrl": [],
+    }))
+    .into_response()
+}
+
+async fn find_by_address(
+    State(state): State<SharedState>,
+    headers: HeaderMap,
+    Query(params): Params,
+) -> Response {
+    let path = "/location-finder/v1/find-by-address".to_string();
+    if let Some(v) = check(&state, &headers, path, &params).await {
+        return v;
+    }
+
+    let state = state.lock().expect("mock server lock poisoned");
+    let locations = state
+        .fixtures
+        .locations
+        .iter()
+        .filter(|v| {
+            let address = &v.place.address;
+            matches_param(&params, "countryCode", address.country_code.as_deref())
+                && matches_param(&params, "postalCode", address.postal_code.as_deref())
+                && matches_param(
+                    &params,
+                    "addressLocality",
+                    address.address_locality.as_deref(),
+                )
+        })
+        .filter(|v| matches_filters(&params, v))
+        .cloned()
+        .collect::<Vec<ServicePoint>>();
+
+    locations_response(&params, locations)
+}
+
+async fn find_by_geo(
+    State(state): State<SharedState>,
+    headers: HeaderMap,
+    Query(params): Params,
+) -> Response {
+    let path = "/location-finder/v1/find-by-geo".to_string();
+    if let Some(v) = check(&state, &headers, path, &params).await {
+        return v;
+    }
+
+    let coordinate = |name: &str| params.get(name).and_then(|v| v.parse::<f64>().ok());
+    let (latitude, longitude) = match (coordinate("latitude"), coordinate("longitude")) {
+        (Some(latitude), Some(longitude)) => (latitude, longitude),
+        _ => {
+            return problem(
+                StatusCode::BAD_REQUEST,
+                "Bad request",
+                "latitude and longitude are required.",
+            )
+        }
+    };
+    let radius = params
+        .get("radius")
+        .and_then(|v| v.parse::<f64>().ok())
+        .unwrap_or(5000.0);
+
+    let origin = Geo {
+        latitude,
+        longitude,
+    };
+
+    let state = state.lock().expect("mock server lock poisoned");
+    let mut locations = state
+        .fixtures
+        .locations
+        .iter()
+        .filter(|v| matches_filters(&params, v))
+        .filter_map(|v| {
+            let distance = origin.distance_to(&v.place.geo);
+            if distance > radius {
+                return None;
+            }
+            let mut location = v.clone();
+            location.distance = Some(distance.round() as u32);
+
+            Some(location)
+        })
+        .collect::<Vec<ServicePoint>>();
+    locations.sort_by_key(|v| v.distance);
+
+    locations_response(&params, locations)
+}
+
+async fn find_by_keyword_id(
+    State(state): State<SharedState>,
+    headers: HeaderMap,
+    Query(params): Params,
+) -> Response {
+    let path = "/location-finder/v1/find-by-keyword-id".to_string();
+    if let Some(v) = check(&state, &headers, path, &params).await {
+        return v;
+    }
+
+    let state = state.lock().expect("mock server lock poisoned");
+    let location = state.fixtures.locations.iter().find(|v| {
+        matches_param(&params, "keywordId", Some(&v.location.keyword_id))
+            && matches_param(
+                &params,
+                "countryCode",
+                v.place.address.country_code.as_deref(),
+            )
+            && matches_param(
+                &params,
+                "postalCode",
+                v.place.address.postal_code.as_deref(),
+            )
+    });
+
+    match location {
+        Some(v) => Json(v).into_response(),
+        None => not_found(),
+    }
+}
+
+async fn location_by_id(
+    State(state): State<SharedState>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+) -> Response {
+    let path = format!("/location-finder/v1/locations/{}", id);
+    if let Some(v) = check(&state, &headers, path, &HashMap::new()).await {
+        return v;
+    }
+
+    let state = state.lock().expect("mock server lock poisoned");
+    let location = state
+        .fixtures
+        .locations
+        .iter()
+        .find(|v| v.location.ids.iter().any(|v| v.location_id == id));
+
+    match location {
+        Some(v) => Json(v).into_response(),
+        None => not_found(),
+    }
+}
+
+fn locations_response(
+    params: &HashMap<String, String>,
+    mut locations: Vec<ServicePoint>,
+) -> Response {
+    if let Some(limit) = params.get("limit").and_then(|v| v.parse::<usize>().ok()) {
+        locations.truncate(limit);
+    }
+
+    Json(json!({ "locations": locations })).into_response()
+}
+
+/// A missing parameter matches everything, a given one has to equal the value (ignoring case).
+fn matches_param(params: &HashMap<String, String>, name: &str, value: Option<&str>) -> bool {
+    matches(params.get(name).map(String::as_str), value)
+}
+
+/// Applies the `locationType`, `serviceType` and `providerType` filters, the first two of which
+/// may hold comma-separated lists.
+fn matches_filters(params: &HashMap<String, String>, location: &ServicePoint) -> bool {
+    let list = |name: &str| {
+        params
+            .get(name)
+            .map(|v| v.split(',').map(str::to_string).collect())
+    };
+
+    LocationFilters {
+        location_types: list("locationType"),
+        service_types: list("serviceType"),
+        provider_type: params.get("providerType").cloned(),
+    }
+    .matches(location)
+}
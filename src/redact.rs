@@ -0,0 +1,406 @@
+use crate::api::shipment_tracking::{Shipment, ShipmentParty};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Deserializer};
+use sha2::Sha256;
+use std::cell::Cell;
+use std::fmt;
+use zeroize::Zeroize;
+
+/// What secrets and personal data are printed as.
+pub(crate) const REDACTED: &str = "<redacted>";
+
+thread_local! {
+    /// Whether an [Unredacted](Unredacted) is being formatted on this thread.
+    static UNREDACTED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// `Debug` of a value showing the personal data models hide in their own `Debug` output,
+/// like receiver names, signatures, addresses and the free text of tracking events.
+/// Only the wrapped value is affected, the rest of the program keeps redacting.
+/// Secrets stay hidden.
+///
+/// # Example
+///
+/// ```
+/// # use dhl_wrapper::api::Address;
+/// # use dhl_wrapper::redact::Unredacted;
+/// let address = Address {
+///     country_code: Some("DE".to_string()),
+///     postal_code: Some("53113".to_string()),
+///     address_locality: Some("Bonn".to_string()),
+///     street_address: Some("Charles-de-Gaulle-Straße 20".to_string()),
+/// };
+///
+/// assert!(!format!("{:?}", address).contains("Bonn"));
+/// assert!(format!("{:?}", Unredacted(&address)).contains("Bonn"));
+/// ```
+pub struct Unredacted<'a, T: ?Sized>(pub &'a T);
+
+impl<T: fmt::Debug + ?Sized> fmt::Debug for Unredacted<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        /// Restores the previous state, also if formatting panics.
+        struct Restore(bool);
+
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                UNREDACTED.with(|v| v.set(self.0));
+            }
+        }
+
+        let _restore = Restore(UNREDACTED.with(|v| v.replace(true)));
+        self.0.fmt(f)
+    }
+}
+
+/// Values holding personal data, printed by [Pii](Pii).
+pub(crate) trait Redact: fmt::Debug {
+    fn fmt_redacted(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result;
+}
+
+impl Redact for String {
+    fn fmt_redacted(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(REDACTED)
+    }
+}
+
+impl<T: fmt::Debug> Redact for Option<T> {
+    fn fmt_redacted(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Some(_) => write!(f, "Some({})", REDACTED),
+            None => f.write_str("None"),
+        }
+    }
+}
+
+/// `Debug` of a field holding personal data, hiding its value unless it is part of an [Unredacted](Unredacted).
+pub(crate) struct Pii<'a, T>(pub(crate) &'a T);
+
+impl<T: Redact> fmt::Debug for Pii<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match UNREDACTED.with(Cell::get) {
+            true => self.0.fmt(f),
+            false => self.0.fmt_redacted(f),
+        }
+    }
+}
+
+/// A secret, like an API key or password, which is zeroed in memory when dropped
+/// and never shows up in `Debug` or `Display` output.
+///
+/// # Example
+///
+/// ```
+/// # use dhl_wrapper::redact::Secret;
+/// let secret = Secret::new("muchsecretwow");
+///
+/// assert_eq!(format!("{:?}", secret), "<redacted>");
+/// assert_eq!(secret.expose(), "muchsecretwow");
+/// ```
+#[derive(Clone, PartialEq, Eq, Default)]
+pub struct Secret(String);
+
+impl Secret {
+    pub fn new<T: Into<String>>(secret: T) -> Self {
+        Secret(secret.into())
+    }
+
+    /// The secret itself, to be sent to DHL.
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+
+    /// Whether the secret is empty or whitespace only.
+    pub fn is_blank(&self) -> bool {
+        self.0.trim().is_empty()
+    }
+}
+
+impl Drop for Secret {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl fmt::Debug for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(REDACTED)
+    }
+}
+
+impl fmt::Display for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(REDACTED)
+    }
+}
+
+impl From<String> for Secret {
+    fn from(secret: String) -> Self {
+        Secret(secret)
+    }
+}
+
+impl From<&str> for Secret {
+    fn from(secret: &str) -> Self {
+        Secret(secret.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for Secret {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(Secret)
+    }
+}
+
+/// What a [RedactionPolicy](RedactionPolicy) does with one kind of personal data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Treatment {
+    /// Leaves the value as it is.
+    Keep,
+    /// Replaces the value with `None`.
+    Strip,
+    /// Replaces the value with a keyed hash, so equal values still match after redaction.
+    Hash,
+}
+
+/// Strips or pseudonymizes the personal data in shipments, e.g. before they are stored for analytics.
+///
+/// The redacted shipment has the same shape, only the values of optional fields change.
+/// Names also show up in the free text of the status and events, like `Signed for by: A. MUSTER`,
+/// so they are replaced there with their hash, or with `<redacted>` if they are stripped.
+/// Hashes are hex encoded HMAC-SHA256 digests keyed with the policy's key,
+/// so they cannot be reversed by hashing guessed names without it.
+///
+/// # Example
+///
+/// ```
+/// # use dhl_wrapper::redact::*;
+/// # fn example(shipment: dhl_wrapper::api::shipment_tracking::Shipment) {
+/// let policy = RedactionPolicy::new("your_pseudonymization_key")
+///     .street_addresses(Treatment::Strip);
+///
+/// let safe_to_store = policy.apply(&shipment);
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct RedactionPolicy {
+    key: Secret,
+    party_names: Treatment,
+    signer_names: Treatment,
+    street_addresses: Treatment,
+    proof_of_delivery_urls: Treatment,
+}
+
+impl RedactionPolicy {
+    /// Creates a policy hashing names and street addresses with `key` and stripping proof of delivery URLs.
+    /// Keep the key to get the same hashes in every export.
+    pub fn new<K: Into<Secret>>(key: K) -> Self {
+        RedactionPolicy {
+            key: key.into(),
+            party_names: Treatment::Hash,
+            signer_names: Treatment::Hash,
+            street_addresses: Treatment::Hash,
+            proof_of_delivery_urls: Treatment::Strip,
+        }
+    }
+
+    /// Names of the sender and receiver, including organization names.
+    pub fn party_names(mut self, party_names: Treatment) -> Self {
+        self.party_names = party_names;
+
+        self
+    }
+
+    /// Names of whoever signed for the proof of delivery.
+    pub fn signer_names(mut self, signer_names: Treatment) -> Self {
+        self.signer_names = signer_names;
+
+        self
+    }
+
+    /// Street addresses of the origin, destination, status and events.
+    /// Postal codes, localities and countries are kept.
+    pub fn street_addresses(mut self, street_addresses: Treatment) -> Self {
+        self.street_addresses = street_addresses;
+
+        self
+    }
+
+    /// URLs of the signature and proof of delivery document.
+    pub fn proof_of_delivery_urls(mut self, proof_of_delivery_urls: Treatment) -> Self {
+        self.proof_of_delivery_urls = proof_of_delivery_urls;
+
+        self
+    }
+
+    /// A copy of `shipment` with personal data treated as the policy says.
+    pub fn apply(&self, shipment: &Shipment) -> Shipment {
+        let names = self.names(shipment);
+        let mut shipment = shipment.clone();
+
+        let texts = [&mut shipment.status.description]
+            .into_iter()
+            .chain(shipment.status.remark.iter_mut())
+            .chain(shipment.status.next_steps.iter_mut())
+            .chain(shipment.events.iter_mut().flat_map(|v| {
+                [&mut v.description]
+                    .into_iter()
+                    .chain(v.remark.iter_mut())
+                    .chain(v.next_steps.iter_mut())
+            }));
+        for text in texts {
+            *text = replace_names(text, &names);
+        }
+
+        let points = shipment
+            .origin
+            .iter_mut()
+            .chain(shipment.destination.iter_mut())
+            .chain([&mut shipment.status.location])
+            .chain(
+                shipment
+                    .events
+                    .iter_mut()
+                    .filter_map(|v| v.location.as_mut()),
+            );
+        for point in points {
+            self.treat(self.street_addresses, &mut point.address.street_address);
+        }
+
+        let details = &mut shipment.details;
+        for party in [&mut details.sender, &mut details.receiver]
+            .into_iter()
+            .flatten()
+        {
+            self.treat_party(party);
+        }
+        if let Some(proof) = &mut details.proof_of_delivery {
+            let treatment = self.proof_of_delivery_urls;
+            self.treat(treatment, &mut proof.signature_url);
+            self.treat(treatment, &mut proof.document_url);
+            if let Some(signed) = &mut proof.signed {
+                let treatment = self.signer_names;
+                self.treat(treatment, &mut signed.family_name);
+                self.treat(treatment, &mut signed.given_name);
+                self.treat(treatment, &mut signed.name);
+            }
+        }
+
+        shipment
+    }
+
+    /// Names to replace in free text with what they are replaced with, longest first.
+    /// They are matched ignoring case, as texts often spell them in capitals.
+    /// Given names are left out, as short ones like `A.` would match anything.
+    fn names(&self, shipment: &Shipment) -> Vec<(String, String)> {
+        let details = &shipment.details;
+        let parties = [&details.sender, &details.receiver]
+            .into_iter()
+            .flatten()
+            .flat_map(|v| [&v.organization_name, &v.family_name, &v.name])
+            .map(|v| (self.party_names, v));
+        let signed = details
+            .proof_of_delivery
+            .as_ref()
+            .and_then(|v| v.signed.as_ref());
+        let signers = signed
+            .into_iter()
+            .flat_map(|v| [&v.family_name, &v.name])
+            .map(|v| (self.signer_names, v));
+
+        let mut names = parties
+            .chain(signers)
+            .filter_map(|(treatment, name)| {
+                let name = name.as_ref().filter(|v| !v.trim().is_empty())?;
+                let replacement = match treatment {
+                    Treatment::Keep => return None,
+                    Treatment::Strip => REDACTED.to_string(),
+                    Treatment::Hash => self.hash(name),
+                };
+                Some((name.clone(), replacement))
+            })
+            .collect::<Vec<(String, String)>>();
+        names.sort_by(|a, b| b.0.len().cmp(&a.0.len()).then_with(|| a.0.cmp(&b.0)));
+        names.dedup();
+
+        names
+    }
+
+    fn treat_party(&self, party: &mut ShipmentParty) {
+        let treatment = self.party_names;
+        self.treat(treatment, &mut party.organization_name);
+        self.treat(treatment, &mut party.family_name);
+        self.treat(treatment, &mut party.given_name);
+        self.treat(treatment, &mut party.name);
+    }
+
+    fn treat(&self, treatment: Treatment, value: &mut Option<String>) {
+        match treatment {
+            Treatment::Keep => {}
+            Treatment::Strip => *value = None,
+            Treatment::Hash => *value = value.as_deref().map(|v| self.hash(v)),
+        }
+    }
+
+    fn hash(&self, value: &str) -> String {
+        let mut mac = Hmac::<Sha256>::new_from_slice(self.key.expose().as_bytes())
+            .expect("HMAC takes keys of any length");
+        mac.update(value.as_bytes());
+
+        mac.finalize()
+            .into_bytes()
+            .iter()
+            .map(|v| format!("{:02x}", v))
+            .collect()
+    }
+}
+
+/// Replaces the names in `text` in a single pass, so replacements are never matched again.
+/// Names match whole words ignoring case, and are tried in order.
+fn replace_names(text: &str, names: &[(String, String)]) -> String {
+    let mut res = String::with_capacity(text.len());
+    let mut previous = None;
+    let mut rest = text;
+    while let Some(c) = rest.chars().next() {
+        let found = names
+            .iter()
+            .find_map(|(name, replacement)| Some((match_len(rest, name, previous)?, replacement)));
+        match found {
+            Some((len, replacement)) => {
+                res.push_str(replacement);
+                previous = rest[..len].chars().last();
+                rest = &rest[len..];
+            }
+            None => {
+                res.push(c);
+                previous = Some(c);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+    }
+
+    res
+}
+
+/// Length in bytes of the start of `text` matching `name` ignoring case, if it starts with it.
+/// A name starting or ending with a letter or digit has to start or end a word,
+/// `previous` being the character before `text`.
+fn match_len(text: &str, name: &str, previous: Option<char>) -> Option<usize> {
+    let is_word = |c: Option<char>| c.is_some_and(char::is_alphanumeric);
+    if is_word(name.chars().next()) && is_word(previous) {
+        return None;
+    }
+
+    let mut chars = text.char_indices().peekable();
+    for n in name.chars() {
+        let (_, c) = chars.next()?;
+        if !c.to_lowercase().eq(n.to_lowercase()) {
+            return None;
+        }
+    }
+    let next = chars.peek().copied();
+    if is_word(name.chars().last()) && is_word(next.map(|(_, c)| c)) {
+        return None;
+    }
+
+    Some(next.map_or(text.len(), |(i, _)| i))
+}
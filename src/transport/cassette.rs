@@ -0,0 +1,304 @@
+use super::{
+    redact_body, redact_headers, redact_json, redact_url, HttpRequest, HttpResponse,
+    ReqwestTransport, Transport,
+};
+use crate::cache::cache_key;
+use crate::error::DhlError;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// Environment variable deciding the mode of cassettes created by [Cassette::from_env](Cassette::from_env).
+pub const CASSETTE_MODE_VAR: &str = "DHL_CASSETTE_MODE";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CassetteMode {
+    /// Sends requests to DHL and writes request/response pairs to the cassette file.
+    Record,
+    /// Answers requests from the cassette file without touching the network.
+    Replay,
+}
+
+/// A request/response pair stored in a cassette.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Interaction {
+    pub request: HttpRequest,
+    pub response: RecordedResponse,
+}
+
+/// A response stored in a cassette. JSON bodies are stored as JSON to keep cassettes
+/// readable, any other body is stored as a string.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Value,
+}
+
+impl From<HttpResponse> for RecordedResponse {
+    fn from(response: HttpResponse) -> Self {
+        let mut body = serde_json::from_slice(&response.body).unwrap_or_else(|_| {
+            Value::String(String::from_utf8_lossy(&response.body).into_owned())
+        });
+        redact_json(&mut body);
+
+        RecordedResponse {
+            status: response.status,
+            headers: redact_headers(response.headers),
+            body,
+        }
+    }
+}
+
+impl From<RecordedResponse> for HttpResponse {
+    fn from(response: RecordedResponse) -> Self {
+        let body = match response.body {
+            Value::String(v) => v.into_bytes(),
+            v => v.to_string().into_bytes(),
+        };
+
+        HttpResponse {
+            status: response.status,
+            headers: response.headers,
+            body,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct CassetteFile {
+    interactions: Vec<Interaction>,
+}
+
+/// What requests are matched by: method, cache key of the URL and hash of the body.
+type MatchKey = (String, String, Option<String>);
+
+/// Key a request is matched by when replaying.
+fn match_key(request: &HttpRequest) -> MatchKey {
+    (
+        request.method.clone(),
+        cache_key(&redact_url(&request.url)),
+        body_hash(&request.headers, request.body.clone()),
+    )
+}
+
+/// SHA-256 of the redacted body, with multipart boundaries replaced by a fixed one
+/// as they are random for every request.
+fn body_hash(headers: &[(String, String)], body: Option<Vec<u8>>) -> Option<String> {
+    let mut body = redact_body(headers, body)?;
+    let boundary = headers
+        .iter()
+        .filter(|(k, _)| k.eq_ignore_ascii_case("content-type"))
+        .find_map(|(_, v)| v.split("boundary=").nth(1))
+        .map(|v| v.trim_matches('"').as_bytes());
+    if let Some(boundary) = boundary.filter(|v| !v.is_empty()) {
+        let mut normalized = Vec::with_capacity(body.len());
+        let mut rest = &body[..];
+        while let Some(i) = rest.windows(boundary.len()).position(|v| v == boundary) {
+            normalized.extend_from_slice(&rest[..i]);
+            normalized.extend_from_slice(b"boundary");
+            rest = &rest[i + boundary.len()..];
+        }
+        normalized.extend_from_slice(rest);
+        body = normalized;
+    }
+
+    Some(
+        Sha256::digest(&body)
+            .iter()
+            .map(|v| format!("{:02x}", v))
+            .collect(),
+    )
+}
+
+struct CassetteInner {
+    path: PathBuf,
+    /// Transport requests are sent through while recording.
+    upstream: Option<Arc<dyn Transport>>,
+    interactions: Mutex<Vec<Interaction>>,
+    /// Number of times each request has been replayed, by its match key.
+    replayed: Mutex<HashMap<MatchKey, usize>>,
+}
+
+/// Transport recording real request/response pairs into a file and replaying them,
+/// so tests run deterministically and without network or API keys.
+///
+/// API keys and other secrets are redacted before anything is written to disk.
+/// Requests are matched by method and URL, ignoring the order of query parameters,
+/// and by a hash of the body if they have one.
+/// If the same request was recorded several times, the recorded responses are replayed
+/// in order and the last one is repeated once all were used.
+///
+/// # Example
+///
+/// ```no_run
+/// # #[tokio::main]
+/// # async fn main() {
+/// # use dhl_wrapper::api::location_finder::*;
+/// # use dhl_wrapper::transport::cassette::*;
+/// // `Cassette::from_env` would record instead with `DHL_CASSETTE_MODE=record`
+/// let cassette = Cassette::replay("tests/cassettes/location_finder.json").unwrap();
+///
+/// let api = LocationFinderApi::new(ApiMode::Production, "your_api_token")
+///     .transport(cassette.clone());
+///
+/// let response = api.send(GetLocationById::new("8003-4101479")).await.unwrap();
+///
+/// assert!(!response.opening_hours.is_empty());
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct Cassette {
+    inner: Arc<CassetteInner>,
+}
+
+impl Cassette {
+    /// Creates an empty cassette that records into `path`, sending requests via a [ReqwestTransport](ReqwestTransport).
+    pub fn record<P: Into<PathBuf>>(path: P) -> Self {
+        Cassette::record_with(path, ReqwestTransport::new())
+    }
+
+    /// Creates an empty cassette that records into `path`, sending requests via `upstream`.
+    pub fn record_with<P: Into<PathBuf>, T: Transport + 'static>(path: P, upstream: T) -> Self {
+        Cassette::create(path.into(), Some(Arc::new(upstream)), Vec::new())
+    }
+
+    /// Loads the cassette stored in `path` for replay.
+    pub fn replay<P: Into<PathBuf>>(path: P) -> Result<Self, DhlError> {
+        let path = path.into();
+        let file = serde_json::from_slice::<CassetteFile>(&fs::read(&path)?)?;
+
+        Ok(Cassette::create(path, None, file.interactions))
+    }
+
+    /// Records if the environment variable `DHL_CASSETTE_MODE` is set to `record`, replays otherwise.
+    pub fn from_env<P: Into<PathBuf>>(path: P) -> Result<Self, DhlError> {
+        match std::env::var(CASSETTE_MODE_VAR) {
+            Ok(v) if v.eq_ignore_ascii_case("record") => Ok(Cassette::record(path)),
+            _ => Cassette::replay(path),
+        }
+    }
+
+    fn create(
+        path: PathBuf,
+        upstream: Option<Arc<dyn Transport>>,
+        interactions: Vec<Interaction>,
+    ) -> Self {
+        Cassette {
+            inner: Arc::new(CassetteInner {
+                path,
+                upstream,
+                interactions: Mutex::new(interactions),
+                replayed: Mutex::new(HashMap::new()),
+            }),
+        }
+    }
+
+    pub fn mode(&self) -> CassetteMode {
+        match self.inner.upstream {
+            Some(_) => CassetteMode::Record,
+            None => CassetteMode::Replay,
+        }
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.mode() == CassetteMode::Record
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.inner.path
+    }
+
+    /// All interactions on the cassette, in the order they were recorded.
+    pub fn interactions(&self) -> Vec<Interaction> {
+        self.inner
+            .interactions
+            .lock()
+            .expect("cassette lock poisoned")
+            .clone()
+    }
+
+    /// Writes all interactions to the cassette file.
+    pub fn save(&self) -> Result<(), DhlError> {
+        let file = CassetteFile {
+            interactions: self.interactions(),
+        };
+        if let Some(dir) = self.inner.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&self.inner.path, serde_json::to_vec_pretty(&file)?)?;
+
+        Ok(())
+    }
+
+    async fn record_interaction(
+        &self,
+        upstream: &Arc<dyn Transport>,
+        request: HttpRequest,
+    ) -> Result<HttpResponse, DhlError> {
+        let response = upstream.execute(request.clone()).await?;
+
+        let interaction = Interaction {
+            request: HttpRequest {
+                method: request.method,
+                url: redact_url(&request.url),
+                body: redact_body(&request.headers, request.body),
+                headers: redact_headers(request.headers),
+            },
+            response: response.clone().into(),
+        };
+        self.inner
+            .interactions
+            .lock()
+            .expect("cassette lock poisoned")
+            .push(interaction);
+        // saved after every interaction, so an aborted test run still leaves a usable cassette
+        self.save()?;
+
+        Ok(response)
+    }
+
+    fn replay_interaction(&self, request: &HttpRequest) -> Result<HttpResponse, DhlError> {
+        let key = match_key(request);
+
+        let interactions = self
+            .inner
+            .interactions
+            .lock()
+            .expect("cassette lock poisoned");
+        let matching = interactions
+            .iter()
+            .filter(|v| match_key(&v.request) == key)
+            .collect::<Vec<&Interaction>>();
+        if matching.is_empty() {
+            return Err(DhlError::Transport(format!(
+                "no interaction for {} {} on cassette {}",
+                request.method,
+                redact_url(&request.url),
+                self.inner.path.display()
+            )));
+        }
+
+        let mut replayed = self.inner.replayed.lock().expect("cassette lock poisoned");
+        let count = replayed.entry(key).or_insert(0);
+        let interaction = matching[(*count).min(matching.len() - 1)];
+        *count += 1;
+
+        Ok(interaction.response.clone().into())
+    }
+}
+
+#[async_trait]
+impl Transport for Cassette {
+    async fn execute(&self, request: HttpRequest) -> Result<HttpResponse, DhlError> {
+        match &self.inner.upstream {
+            Some(upstream) => self.record_interaction(upstream, request).await,
+            None => self.replay_interaction(&request),
+        }
+    }
+}
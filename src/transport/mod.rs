@@ -0,0 +1,220 @@
+use crate::error::DhlError;
+use crate::redact::REDACTED;
+use async_trait::async_trait;
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt;
+use std::sync::Arc;
+
+pub mod cassette;
+pub mod retry;
+
+/// Header, parameter and field names whose values are never written to cassettes or errors.
+const SECRET_NAMES: [&str; 10] = [
+    "dhl-api-key",
+    "authorization",
+    "apikey",
+    "api_key",
+    "cookie",
+    "set-cookie",
+    "client_secret",
+    "password",
+    "access_token",
+    "refresh_token",
+];
+
+/// An HTTP request as handed to a [Transport](Transport).
+/// Its `Debug` output leaves out secrets and the body.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HttpRequest {
+    pub method: String,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<Vec<u8>>,
+}
+
+impl fmt::Debug for HttpRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HttpRequest")
+            .field("method", &self.method)
+            .field("url", &redact_url(&self.url))
+            .field("headers", &redact_headers(self.headers.clone()))
+            .field(
+                "body",
+                &self.body.as_ref().map(|v| format!("{} bytes", v.len())),
+            )
+            .finish()
+    }
+}
+
+/// An HTTP response as returned by a [Transport](Transport).
+/// Its `Debug` output leaves out secrets and the body.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HttpResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl fmt::Debug for HttpResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HttpResponse")
+            .field("status", &self.status)
+            .field("headers", &redact_headers(self.headers.clone()))
+            .field("body", &format!("{} bytes", self.body.len()))
+            .finish()
+    }
+}
+
+/// A trait for everything that can carry requests to DHL and bring back their responses.
+/// APIs use a [ReqwestTransport](ReqwestTransport) unless they are given another one,
+/// like a [Cassette](cassette::Cassette) in tests.
+#[async_trait]
+pub trait Transport: Send + Sync {
+    async fn execute(&self, request: HttpRequest) -> Result<HttpResponse, DhlError>;
+}
+
+#[async_trait]
+impl<T: Transport + ?Sized> Transport for Arc<T> {
+    async fn execute(&self, request: HttpRequest) -> Result<HttpResponse, DhlError> {
+        (**self).execute(request).await
+    }
+}
+
+/// Transport sending requests over the network using [reqwest](https://docs.rs/reqwest).
+#[derive(Debug, Clone, Default)]
+pub struct ReqwestTransport {
+    client: reqwest::Client,
+}
+
+impl ReqwestTransport {
+    pub fn new() -> Self {
+        ReqwestTransport::default()
+    }
+
+    /// Uses a preconfigured client, e.g. one with custom timeouts or a proxy.
+    pub fn with_client(client: reqwest::Client) -> Self {
+        ReqwestTransport { client }
+    }
+}
+
+#[async_trait]
+impl Transport for ReqwestTransport {
+    async fn execute(&self, request: HttpRequest) -> Result<HttpResponse, DhlError> {
+        let method = reqwest::Method::from_bytes(request.method.as_bytes())
+            .map_err(|_| DhlError::Transport(format!("invalid method {}", request.method)))?;
+
+        let mut builder = self.client.request(method, &request.url);
+        for (name, value) in &request.headers {
+            builder = builder.header(name, value);
+        }
+        if let Some(body) = request.body {
+            builder = builder.body(body);
+        }
+        let res = builder.send().await?;
+
+        let status = res.status().as_u16();
+        let headers = res
+            .headers()
+            .iter()
+            .filter_map(|(k, v)| v.to_str().ok().map(|v| (k.to_string(), v.to_string())))
+            .collect();
+        let body = res.bytes().await?.to_vec();
+
+        Ok(HttpResponse {
+            status,
+            headers,
+            body,
+        })
+    }
+}
+
+fn is_secret(name: &str) -> bool {
+    SECRET_NAMES.iter().any(|v| v.eq_ignore_ascii_case(name))
+}
+
+/// Replaces the values of secret headers, like the API key.
+pub(crate) fn redact_headers(headers: Vec<(String, String)>) -> Vec<(String, String)> {
+    headers
+        .into_iter()
+        .map(|(name, value)| {
+            if is_secret(&name) {
+                (name, REDACTED.to_string())
+            } else {
+                (name, value)
+            }
+        })
+        .collect()
+}
+
+/// Replaces the values of secret query parameters.
+pub(crate) fn redact_url(url: &str) -> String {
+    let mut parsed = match Url::parse(url) {
+        Ok(v) => v,
+        Err(_) => return url.to_string(),
+    };
+    if !parsed.query_pairs().any(|(k, _)| is_secret(&k)) {
+        return url.to_string();
+    }
+
+    let pairs = parsed
+        .query_pairs()
+        .map(|(k, v)| {
+            let v = if is_secret(&k) {
+                REDACTED.to_string()
+            } else {
+                v.into_owned()
+            };
+            (k.into_owned(), v)
+        })
+        .collect::<Vec<(String, String)>>();
+    parsed.query_pairs_mut().clear().extend_pairs(pairs);
+
+    parsed.to_string()
+}
+
+/// Replaces the values of secret parameters in form bodies, like an OAuth2 client secret.
+pub(crate) fn redact_body(headers: &[(String, String)], body: Option<Vec<u8>>) -> Option<Vec<u8>> {
+    let is_form = headers.iter().any(|(k, v)| {
+        k.eq_ignore_ascii_case("content-type") && v.starts_with("application/x-www-form-urlencoded")
+    });
+    let body = body?;
+    if !is_form {
+        return Some(body);
+    }
+
+    let pairs = form_urlencoded::parse(&body).map(|(k, v)| {
+        let v = if is_secret(&k) {
+            REDACTED.to_string()
+        } else {
+            v.into_owned()
+        };
+        (k.into_owned(), v)
+    });
+
+    Some(
+        form_urlencoded::Serializer::new(String::new())
+            .extend_pairs(pairs)
+            .finish()
+            .into_bytes(),
+    )
+}
+
+/// Replaces the values of secret fields in a JSON body, like an OAuth2 access token.
+pub(crate) fn redact_json(value: &mut Value) {
+    match value {
+        Value::Object(v) => {
+            for (k, v) in v.iter_mut() {
+                if is_secret(k) {
+                    *v = Value::String(REDACTED.to_string());
+                } else {
+                    redact_json(v);
+                }
+            }
+        }
+        Value::Array(v) => v.iter_mut().for_each(redact_json),
+        _ => {}
+    }
+}
@@ -0,0 +1,110 @@
+use super::{HttpRequest, HttpResponse, Transport};
+use crate::api::{Method, IDEMPOTENCY_KEY_HEADER};
+use crate::error::DhlError;
+use async_trait::async_trait;
+use std::time::Duration;
+
+/// Statuses of responses worth another try, as the request likely never reached DHL's backend.
+const RETRYABLE_STATUSES: [u16; 3] = [502, 503, 504];
+
+/// Transport retrying failed requests on another transport, with exponential backoff.
+///
+/// Only requests which are safe to send twice are retried: those with an idempotent method
+/// like GET, PUT or DELETE, and POST or PATCH requests with an `Idempotency-Key` header.
+///
+/// # Example
+///
+/// ```
+/// # use dhl_wrapper::api::DhlClient;
+/// # use dhl_wrapper::transport::{retry::Retry, ReqwestTransport};
+/// # use std::time::Duration;
+/// let transport = Retry::new(ReqwestTransport::new())
+///     .max_retries(3)
+///     .backoff(Duration::from_millis(500));
+/// let client = DhlClient::new("your_api_token").transport(transport);
+/// ```
+pub struct Retry<T> {
+    inner: T,
+    max_retries: u32,
+    backoff: Duration,
+    max_backoff: Duration,
+}
+
+impl<T: Transport> Retry<T> {
+    /// Retries twice, waiting 200ms before the first retry and at most 30s before any.
+    pub fn new(inner: T) -> Self {
+        Retry {
+            inner,
+            max_retries: 2,
+            backoff: Duration::from_millis(200),
+            max_backoff: Duration::from_secs(30),
+        }
+    }
+
+    pub fn max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+
+        self
+    }
+
+    /// Wait before the first retry, doubled for every further one.
+    pub fn backoff(mut self, backoff: Duration) -> Self {
+        self.backoff = backoff;
+
+        self
+    }
+
+    /// Longest wait before a retry, however many came before.
+    pub fn max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.max_backoff = max_backoff;
+
+        self
+    }
+
+    /// Wait before the retry after `retries` earlier ones.
+    fn delay(&self, retries: u32) -> Duration {
+        let factor = 2u32.checked_pow(retries).unwrap_or(u32::MAX);
+
+        self.backoff.saturating_mul(factor).min(self.max_backoff)
+    }
+}
+
+/// Whether sending `request` twice has the same effect as sending it once.
+fn is_repeatable(request: &HttpRequest) -> bool {
+    let idempotent_method = request
+        .method
+        .parse::<Method>()
+        .is_ok_and(|v| v.is_idempotent());
+    let has_key = request
+        .headers
+        .iter()
+        .any(|(k, _)| k.eq_ignore_ascii_case(IDEMPOTENCY_KEY_HEADER));
+
+    idempotent_method || has_key
+}
+
+#[async_trait]
+impl<T: Transport> Transport for Retry<T> {
+    async fn execute(&self, request: HttpRequest) -> Result<HttpResponse, DhlError> {
+        let max_retries = match is_repeatable(&request) {
+            true => self.max_retries,
+            false => 0,
+        };
+
+        let mut retries = 0;
+        loop {
+            let res = self.inner.execute(request.clone()).await;
+            let retryable = match &res {
+                Ok(v) => RETRYABLE_STATUSES.contains(&v.status),
+                Err(DhlError::Reqwest(_) | DhlError::Transport(_)) => true,
+                Err(_) => false,
+            };
+            if !retryable || retries >= max_retries {
+                return res;
+            }
+
+            tokio::time::sleep(self.delay(retries)).await;
+            retries += 1;
+        }
+    }
+}
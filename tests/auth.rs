@@ -0,0 +1,208 @@
+use async_trait::async_trait;
+use dhl_wrapper::api::*;
+use dhl_wrapper::auth::*;
+use dhl_wrapper::error::DhlError;
+use dhl_wrapper::transport::{HttpRequest, HttpResponse, Transport};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+const TOKEN_URL: &str = "https://api.example.com/token";
+
+/// Transport serving an OAuth2 token endpoint and an API echoing the `Authorization` header.
+#[derive(Clone)]
+struct Server {
+    expires_in: u64,
+    token_status: u16,
+    requests: Arc<Mutex<Vec<HttpRequest>>>,
+}
+
+impl Server {
+    fn new(expires_in: u64) -> Self {
+        Server {
+            expires_in,
+            token_status: 200,
+            requests: Arc::default(),
+        }
+    }
+
+    fn token_requests(&self) -> Vec<HttpRequest> {
+        let requests = self.requests.lock().unwrap();
+        requests
+            .iter()
+            .filter(|v| v.url == TOKEN_URL)
+            .cloned()
+            .collect()
+    }
+}
+
+#[async_trait]
+impl Transport for Server {
+    async fn execute(&self, request: HttpRequest) -> Result<HttpResponse, DhlError> {
+        let (status, body) = if request.url == TOKEN_URL {
+            let n = self.token_requests().len();
+            self.requests.lock().unwrap().push(request);
+            // slow enough for concurrent requests to pile up behind the renewal
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            match self.token_status {
+                200 => (
+                    200,
+                    format!(
+                        r#"{{"access_token":"token-{}","token_type":"Bearer","expires_in":{}}}"#,
+                        n, self.expires_in
+                    ),
+                ),
+                v => (
+                    v,
+                    r#"{"error":"invalid_client","error_description":"Client authentication failed"}"#
+                        .to_string(),
+                ),
+            }
+        } else {
+            let authorization = request
+                .headers
+                .iter()
+                .find(|(k, _)| k == "Authorization")
+                .map(|(_, v)| v.clone())
+                .unwrap_or_default();
+            self.requests.lock().unwrap().push(request);
+            (200, format!(r#"{{"authorization":"{}"}}"#, authorization))
+        };
+
+        Ok(HttpResponse {
+            status,
+            headers: Vec::new(),
+            body: body.into_bytes(),
+        })
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Echo {
+    authorization: String,
+}
+
+struct GetEcho(AuthScheme);
+
+impl DhlRequest for GetEcho {
+    type Response = Echo;
+
+    fn product(&self) -> ApiProduct {
+        ApiProduct::LocationFinder
+    }
+
+    fn path(&self) -> String {
+        "/echo".to_string()
+    }
+
+    fn auth(&self) -> AuthScheme {
+        self.0
+    }
+}
+
+fn oauth2() -> OAuth2 {
+    OAuth2::client_credentials(TOKEN_URL, "client", "muchsecretwow")
+}
+
+fn client(server: &Server, oauth2: OAuth2) -> DhlClient {
+    DhlClient::with_credentials(Credentials::OAuth2(oauth2))
+        .base_url(ApiProduct::LocationFinder, "https://api.example.com")
+        .transport(server.clone())
+}
+
+#[tokio::test]
+async fn send_basic_auth() {
+    let server = Server::new(0);
+    let client = DhlClient::with_credentials(Credentials::basic("user", "pass"))
+        .base_url(ApiProduct::LocationFinder, "https://api.example.com")
+        .transport(server);
+
+    let response = client.send(GetEcho(AuthScheme::Basic)).await.unwrap();
+    assert_eq!(response.authorization, "Basic dXNlcjpwYXNz");
+}
+
+#[tokio::test]
+async fn report_missing_credentials() {
+    let client = DhlClient::new("muchsecretwow").transport(Server::new(0));
+    let result = client.send(GetEcho(AuthScheme::OAuth2)).await;
+    assert!(matches!(result, Err(DhlError::MissingCredentials(_))));
+    let result = client.send(GetEcho(AuthScheme::Basic)).await;
+    assert!(matches!(result, Err(DhlError::MissingCredentials(_))));
+
+    let client = DhlClient::new("  ").transport(Server::new(0));
+    let result = client.send(GetEcho(AuthScheme::ApiKey)).await;
+    assert!(matches!(result, Err(DhlError::MissingCredentials(_))));
+}
+
+#[tokio::test]
+async fn cache_oauth2_tokens() {
+    let server = Server::new(1799);
+    let client = client(&server, oauth2());
+
+    for _ in 0..3 {
+        let response = client.send(GetEcho(AuthScheme::OAuth2)).await.unwrap();
+        assert_eq!(response.authorization, "Bearer token-0");
+    }
+
+    let token_requests = server.token_requests();
+    assert_eq!(token_requests.len(), 1);
+    assert_eq!(token_requests[0].method, "POST");
+    assert_eq!(
+        String::from_utf8(token_requests[0].body.clone().unwrap()).unwrap(),
+        "client_id=client&client_secret=muchsecretwow&grant_type=client_credentials"
+    );
+}
+
+#[tokio::test]
+async fn renew_tokens_before_they_expire() {
+    let server = Server::new(60);
+    let client = client(&server, oauth2().refresh_before(Duration::from_secs(60)));
+
+    let first = client.send(GetEcho(AuthScheme::OAuth2)).await.unwrap();
+    let second = client.send(GetEcho(AuthScheme::OAuth2)).await.unwrap();
+
+    assert_eq!(first.authorization, "Bearer token-0");
+    assert_eq!(second.authorization, "Bearer token-1");
+}
+
+#[tokio::test]
+async fn renew_tokens_once_for_concurrent_requests() {
+    let server = Server::new(1799);
+    let client = Arc::new(client(&server, oauth2().password("user", "pa&ss")));
+
+    let handles = (0..10)
+        .map(|_| {
+            let client = client.clone();
+            tokio::spawn(async move { client.send(GetEcho(AuthScheme::OAuth2)).await })
+        })
+        .collect::<Vec<_>>();
+    for handle in handles {
+        let response = handle.await.unwrap().unwrap();
+        assert_eq!(response.authorization, "Bearer token-0");
+    }
+
+    let token_requests = server.token_requests();
+    assert_eq!(token_requests.len(), 1);
+    assert_eq!(
+        String::from_utf8(token_requests[0].body.clone().unwrap()).unwrap(),
+        "client_id=client&client_secret=muchsecretwow&grant_type=password&username=user&password=pa%26ss"
+    );
+}
+
+#[tokio::test]
+async fn report_failed_token_requests() {
+    let server = Server {
+        token_status: 401,
+        ..Server::new(1799)
+    };
+    let client = client(&server, oauth2());
+
+    let result = client.send(GetEcho(AuthScheme::OAuth2)).await;
+    match result {
+        Err(DhlError::ResponseNotOk { status, title, .. }) => {
+            assert_eq!(status, 401);
+            assert_eq!(title, "invalid_client");
+        }
+        v => panic!("expected a failed token request, got {:?}", v),
+    }
+}
@@ -0,0 +1,209 @@
+use async_trait::async_trait;
+use dhl_wrapper::api::location_finder::{ApiMode, GetLocationById, LocationFinderApi};
+use dhl_wrapper::api::shipment_tracking::*;
+use dhl_wrapper::api::DhlClient;
+use dhl_wrapper::cache::*;
+use dhl_wrapper::error::DhlError;
+use dhl_wrapper::transport::{HttpRequest, HttpResponse, Transport};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+fn response(body: &str) -> CachedResponse {
+    CachedResponse {
+        status: 200,
+        headers: vec![("content-type".to_string(), "application/json".to_string())],
+        body: body.as_bytes().to_vec(),
+        stored_at: SystemTime::now(),
+        expires_at: None,
+    }
+}
+
+#[test]
+fn memory_cache_evicts_least_recently_used() {
+    let cache = MemoryCache::new(2);
+    cache.put("a", response("a"));
+    cache.put("b", response("b"));
+
+    // touch "a", so "b" is the least recently used entry
+    assert_eq!(cache.get("a").unwrap().body, b"a");
+    cache.put("c", response("c"));
+
+    assert_eq!(cache.len(), 2);
+    assert!(cache.get("a").is_some());
+    assert!(cache.get("b").is_none());
+    assert!(cache.get("c").is_some());
+
+    cache.remove("a");
+    assert_eq!(cache.len(), 1);
+}
+
+#[test]
+fn cache_keys_ignore_parameter_order() {
+    assert_eq!(
+        cache_key("https://api-eu.dhl.com/track/shipments?trackingNumber=1&language=de"),
+        cache_key("https://API-EU.dhl.com/track/shipments?language=de&trackingNumber=1#top"),
+    );
+    assert_ne!(
+        cache_key("https://api-eu.dhl.com/track/shipments?trackingNumber=1"),
+        cache_key("https://api-eu.dhl.com/track/shipments?trackingNumber=2"),
+    );
+    assert_eq!(
+        cache_key("https://api.dhl.com/location-finder/v1/locations/8003-4101479"),
+        "https://api.dhl.com/location-finder/v1/locations/8003-4101479"
+    );
+}
+
+#[test]
+fn policy_ttls_per_request_kind() {
+    let policy = CachePolicy::default();
+    assert_eq!(
+        policy.ttl_for(RequestKind::ShipmentTracking),
+        Duration::from_secs(5 * 60)
+    );
+    assert_eq!(
+        policy.ttl_for(RequestKind::LocationById),
+        Duration::from_secs(24 * 60 * 60)
+    );
+
+    let policy =
+        CachePolicy::new(Duration::from_secs(60)).ttl(RequestKind::LocationsByGeo, Duration::ZERO);
+    assert_eq!(policy.ttl_for(RequestKind::LocationsByGeo), Duration::ZERO);
+    assert_eq!(
+        policy.ttl_for(RequestKind::LocationByKeywordId),
+        Duration::from_secs(60)
+    );
+}
+
+#[tokio::test]
+async fn offline_only_cache_does_not_call_dhl() {
+    let api = ShipmentTrackingApi::new("your_api_token")
+        .cache(ResponseCache::memory(10).offline_only(true));
+
+    let result = api
+        .send(GetShipmentTracking::new("00340434161094042557"))
+        .await;
+    assert!(matches!(result, Err(DhlError::CacheMiss(_))));
+}
+
+/// Transport answering with a fixture after a short delay, counting the requests it gets.
+#[derive(Clone)]
+struct Counting {
+    fixture: &'static str,
+    requests: Arc<AtomicUsize>,
+}
+
+impl Counting {
+    fn new(fixture: &'static str) -> Self {
+        Counting {
+            fixture,
+            requests: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    fn requests(&self) -> usize {
+        self.requests.load(Ordering::SeqCst)
+    }
+}
+
+#[async_trait]
+impl Transport for Counting {
+    async fn execute(&self, _: HttpRequest) -> Result<HttpResponse, DhlError> {
+        self.requests.fetch_add(1, Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_millis(20)).await;
+
+        Ok(HttpResponse {
+            status: 200,
+            headers: Vec::new(),
+            body: std::fs::read(format!("tests/fixtures/{}", self.fixture)).unwrap(),
+        })
+    }
+}
+
+#[tokio::test]
+async fn serve_cached_responses_through_the_client() {
+    let transport = Counting::new("location_finder/location.json");
+    let client = DhlClient::new("your_api_token")
+        .transport(transport.clone())
+        .cache(ResponseCache::memory(10));
+
+    let first = client
+        .send(GetLocationById::new("8003-4101479"))
+        .await
+        .unwrap();
+    let second = client
+        .send(GetLocationById::new("8003-4101479"))
+        .await
+        .unwrap();
+
+    assert_eq!(first, second);
+    assert_eq!(transport.requests(), 1);
+}
+
+#[tokio::test]
+async fn serve_stale_responses_while_refreshing_once() {
+    let transport = Counting::new("location_finder/location.json");
+    let cache = ResponseCache::memory(10).policy(
+        CachePolicy::default()
+            .ttl(RequestKind::LocationById, Duration::from_millis(100))
+            .stale_while_revalidate(Duration::from_secs(60)),
+    );
+    let api = LocationFinderApi::new(ApiMode::Production, "your_api_token")
+        .transport(transport.clone())
+        .cache(cache);
+    let request = || GetLocationById::new("8003-4101479");
+
+    api.send(request()).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(150)).await;
+
+    // both are answered from the stale entry, only the first one starts a refresh
+    api.send(request()).await.unwrap();
+    api.send(request()).await.unwrap();
+    assert_eq!(transport.requests(), 1);
+
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert_eq!(transport.requests(), 2);
+
+    // the refreshed entry is fresh again
+    api.send(request()).await.unwrap();
+    assert_eq!(transport.requests(), 2);
+}
+
+#[tokio::test]
+async fn never_refetch_delivered_shipments() {
+    let policy =
+        || CachePolicy::default().ttl(RequestKind::ShipmentTracking, Duration::from_millis(50));
+    let request = || GetShipmentTracking::new("00340434161094042557");
+
+    let delivered = Counting::new("shipment_tracking/express.json");
+    let api = ShipmentTrackingApi::new("your_api_token")
+        .transport(delivered.clone())
+        .cache(ResponseCache::memory(10).policy(policy()));
+    for _ in 0..3 {
+        api.send(request()).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(60)).await;
+    }
+    assert_eq!(delivered.requests(), 1);
+
+    let in_transit = Counting::new("shipment_tracking/parcel-nl.json");
+    let api = ShipmentTrackingApi::new("your_api_token")
+        .transport(in_transit.clone())
+        .cache(ResponseCache::memory(10).policy(policy()));
+    for _ in 0..3 {
+        api.send(request()).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(60)).await;
+    }
+    assert_eq!(in_transit.requests(), 3);
+}
+
+#[tokio::test]
+async fn cache_bulk_locations_without_a_response_cache() {
+    let transport = Counting::new("location_finder/location.json");
+    let api =
+        LocationFinderApi::new(ApiMode::Production, "your_api_token").transport(transport.clone());
+
+    api.get_locations_by_ids(vec!["8003-4101479"]).await;
+    api.get_locations_by_ids(vec!["8003-4101479"]).await;
+
+    assert_eq!(transport.requests(), 1);
+}
@@ -0,0 +1,178 @@
+use async_trait::async_trait;
+use dhl_wrapper::error::DhlError;
+use dhl_wrapper::transport::{cassette::*, HttpRequest, HttpResponse, Transport};
+
+/// Upstream transport answering with the path of the requested URL.
+struct Upstream;
+
+#[async_trait]
+impl Transport for Upstream {
+    async fn execute(&self, request: HttpRequest) -> Result<HttpResponse, DhlError> {
+        Ok(HttpResponse {
+            status: 200,
+            headers: vec![("set-cookie".to_string(), "session=secret".to_string())],
+            body: format!(
+                "{{\"path\":\"{}\"}}",
+                request.url.split('?').next().unwrap()
+            )
+            .into_bytes(),
+        })
+    }
+}
+
+fn request(url: &str) -> HttpRequest {
+    HttpRequest {
+        method: "GET".to_string(),
+        url: url.to_string(),
+        headers: vec![("DHL-API-Key".to_string(), "muchsecretwow".to_string())],
+        body: None,
+    }
+}
+
+#[tokio::test]
+async fn record_and_replay() {
+    let path =
+        std::env::temp_dir().join(format!("dhl-wrapper-cassette-{}.json", std::process::id()));
+
+    let cassette = Cassette::record_with(&path, Upstream);
+    assert_eq!(cassette.mode(), CassetteMode::Record);
+    let recorded = cassette
+        .execute(request("https://api.dhl.com/a?x=1&y=2"))
+        .await
+        .unwrap();
+    cassette
+        .execute(request("https://api.dhl.com/b?apikey=muchsecretwow"))
+        .await
+        .unwrap();
+
+    let file = std::fs::read_to_string(&path).unwrap();
+    assert!(!file.contains("muchsecretwow"));
+    assert!(!file.contains("session=secret"));
+
+    let cassette = Cassette::replay(&path).unwrap();
+    assert_eq!(cassette.interactions().len(), 2);
+
+    // query parameter order does not matter
+    let replayed = cassette
+        .execute(request("https://api.dhl.com/a?y=2&x=1"))
+        .await
+        .unwrap();
+    assert_eq!(replayed.status, recorded.status);
+    assert_eq!(replayed.body, recorded.body);
+
+    let result = cassette.execute(request("https://api.dhl.com/c")).await;
+    assert!(matches!(result, Err(DhlError::Transport(_))));
+
+    std::fs::remove_file(path).unwrap();
+}
+
+/// Upstream transport answering like an OAuth2 token endpoint.
+struct TokenEndpoint;
+
+#[async_trait]
+impl Transport for TokenEndpoint {
+    async fn execute(&self, _request: HttpRequest) -> Result<HttpResponse, DhlError> {
+        Ok(HttpResponse {
+            status: 200,
+            headers: Vec::new(),
+            body: br#"{"access_token":"muchsecrettoken","expires_in":1799}"#.to_vec(),
+        })
+    }
+}
+
+#[tokio::test]
+async fn redact_token_requests() {
+    let path = std::env::temp_dir().join(format!(
+        "dhl-wrapper-cassette-token-{}.json",
+        std::process::id()
+    ));
+
+    let cassette = Cassette::record_with(&path, TokenEndpoint);
+    let request = HttpRequest {
+        method: "POST".to_string(),
+        url: "https://api.dhl.com/token".to_string(),
+        headers: vec![(
+            "Content-Type".to_string(),
+            "application/x-www-form-urlencoded".to_string(),
+        )],
+        body: Some(b"grant_type=client_credentials&client_secret=muchsecretwow".to_vec()),
+    };
+    cassette.execute(request).await.unwrap();
+
+    let interaction = Cassette::replay(&path).unwrap().interactions().remove(0);
+    assert_eq!(
+        String::from_utf8(interaction.request.body.unwrap()).unwrap(),
+        "grant_type=client_credentials&client_secret=%3Credacted%3E"
+    );
+    assert_eq!(interaction.response.body["access_token"], "<redacted>");
+    assert_eq!(interaction.response.body["expires_in"], 1799);
+
+    std::fs::remove_file(path).unwrap();
+}
+
+/// Upstream transport answering with the request body.
+struct Echo;
+
+#[async_trait]
+impl Transport for Echo {
+    async fn execute(&self, request: HttpRequest) -> Result<HttpResponse, DhlError> {
+        Ok(HttpResponse {
+            status: 200,
+            headers: Vec::new(),
+            body: request.body.unwrap_or_default(),
+        })
+    }
+}
+
+fn post(content_type: &str, body: &str) -> HttpRequest {
+    HttpRequest {
+        method: "POST".to_string(),
+        url: "https://api.dhl.com/upload".to_string(),
+        headers: vec![("Content-Type".to_string(), content_type.to_string())],
+        body: Some(body.as_bytes().to_vec()),
+    }
+}
+
+#[tokio::test]
+async fn match_requests_by_body() {
+    let path = std::env::temp_dir().join(format!(
+        "dhl-wrapper-cassette-body-{}.json",
+        std::process::id()
+    ));
+    let multipart = |boundary: &str| {
+        post(
+            &format!("multipart/form-data; boundary={}", boundary),
+            &format!("--{0}\r\n\r\nfirst\r\n--{0}--\r\n", boundary),
+        )
+    };
+
+    let cassette = Cassette::record_with(&path, Echo);
+    for request in [
+        post("text/plain", "first"),
+        post("text/plain", "second"),
+        multipart("recorded"),
+    ] {
+        cassette.execute(request).await.unwrap();
+    }
+
+    let cassette = Cassette::replay(&path).unwrap();
+    let second = cassette
+        .execute(post("text/plain", "second"))
+        .await
+        .unwrap();
+    assert_eq!(second.body, b"second");
+    let first = cassette.execute(post("text/plain", "first")).await.unwrap();
+    assert_eq!(first.body, b"first");
+
+    // multipart boundaries are random, so they do not matter
+    let replayed = cassette.execute(multipart("replayed")).await.unwrap();
+    assert_eq!(
+        replayed.body,
+        b"--recorded\r\n\r\nfirst\r\n--recorded--\r\n"
+    );
+
+    let result = cassette.execute(post("text/plain", "third")).await;
+    assert!(matches!(result, Err(DhlError::Transport(_))));
+
+    std::fs::remove_file(path).unwrap();
+}
@@ -0,0 +1,154 @@
+use async_trait::async_trait;
+use dhl_wrapper::api::location_finder::*;
+use dhl_wrapper::api::*;
+use dhl_wrapper::cache::{MemoryCache, ResponseCache};
+use dhl_wrapper::error::DhlError;
+use dhl_wrapper::transport::{HttpRequest, HttpResponse, Transport};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+/// Transport answering every request with `{"echoed": true}` and keeping what it was sent.
+#[derive(Clone, Default)]
+struct Recorder {
+    requests: Arc<Mutex<Vec<HttpRequest>>>,
+}
+
+impl Recorder {
+    fn requests(&self) -> Vec<HttpRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl Transport for Recorder {
+    async fn execute(&self, request: HttpRequest) -> Result<HttpResponse, DhlError> {
+        self.requests.lock().unwrap().push(request);
+
+        Ok(HttpResponse {
+            status: 200,
+            headers: Vec::new(),
+            body: br#"{"echoed": true}"#.to_vec(),
+        })
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Echo {
+    echoed: bool,
+}
+
+/// A request of an API this crate does not know about.
+struct PostEcho;
+
+impl DhlRequest for PostEcho {
+    type Response = Echo;
+
+    fn product(&self) -> ApiProduct {
+        ApiProduct::LocationFinder
+    }
+
+    fn method(&self) -> Method {
+        Method::Post
+    }
+
+    fn path(&self) -> String {
+        "/echo".to_string()
+    }
+
+    fn query(&self) -> Result<Vec<(String, String)>, DhlError> {
+        Ok(vec![("dryRun".to_string(), "true".to_string())])
+    }
+
+    fn body(&self) -> Result<Option<Body>, DhlError> {
+        Ok(Some(Body::new("text/plain", b"hello".to_vec())))
+    }
+
+    fn auth(&self) -> AuthScheme {
+        AuthScheme::None
+    }
+}
+
+#[tokio::test]
+async fn send_custom_request() {
+    let recorder = Recorder::default();
+    let client = DhlClient::new("muchsecretwow")
+        .base_url(ApiProduct::LocationFinder, "https://api.example.com/")
+        .transport(recorder.clone());
+
+    let response = client.send(PostEcho).await.unwrap();
+    assert!(response.echoed);
+
+    let requests = recorder.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, "POST");
+    assert_eq!(requests[0].url, "https://api.example.com/echo?dryRun=true");
+    assert_eq!(
+        requests[0].headers,
+        vec![("Content-Type".to_string(), "text/plain".to_string())]
+    );
+    assert_eq!(requests[0].body, Some(b"hello".to_vec()));
+}
+
+#[tokio::test]
+async fn send_requests_of_every_product() {
+    let recorder = Recorder::default();
+    let client = DhlClient::new("muchsecretwow")
+        .base_url(ApiProduct::ShipmentTracking, "https://tracking.example.com")
+        .transport(recorder.clone());
+
+    let request = GetLocationById::new("8003-4101479");
+    assert!(client.send(request).await.is_err());
+    let request = shipment_tracking::GetShipmentTracking::new("1234");
+    assert!(client.send(request).await.is_err());
+
+    let requests = recorder.requests();
+    assert_eq!(
+        requests.iter().map(|v| v.url.as_str()).collect::<Vec<_>>(),
+        vec![
+            "https://api.dhl.com/location-finder/v1/locations/8003-4101479",
+            "https://tracking.example.com/shipments?trackingNumber=1234",
+        ]
+    );
+    for request in requests {
+        assert_eq!(request.method, "GET");
+        assert_eq!(
+            request.headers,
+            vec![("DHL-API-Key".to_string(), "muchsecretwow".to_string())]
+        );
+        assert_eq!(request.body, None);
+    }
+}
+
+#[tokio::test]
+async fn only_cache_get_requests() {
+    let recorder = Recorder::default();
+    let client = DhlClient::new("muchsecretwow")
+        .cache(ResponseCache::new(MemoryCache::new(10)))
+        .transport(recorder.clone());
+
+    client.send(PostEcho).await.unwrap();
+    client.send(PostEcho).await.unwrap();
+
+    assert_eq!(recorder.requests().len(), 2);
+}
+
+#[test]
+fn percent_encode_query_parameters() {
+    let url = GetLocationsByAddress::new(CountryCode::De)
+        .address_locality(Some("Köln"))
+        .street_address(Some("Müller & Söhne #3"))
+        .url("https://api.dhl.com/location-finder/v1")
+        .unwrap();
+
+    assert_eq!(
+        url,
+        "https://api.dhl.com/location-finder/v1/find-by-address\
+         ?addressLocality=K%C3%B6ln&countryCode=DE&streetAddress=M%C3%BCller+%26+S%C3%B6hne+%233"
+    );
+
+    let (_, query) = url.split_once('?').unwrap();
+    let street = form_urlencoded::parse(query.as_bytes())
+        .find(|(k, _)| k == "streetAddress")
+        .map(|(_, v)| v.into_owned());
+    assert_eq!(street.as_deref(), Some("Müller & Söhne #3"));
+}
@@ -0,0 +1,318 @@
+//! Checks the request and response types against the OpenAPI specs in `openapi/`.
+//! Only the parameters and properties `skip`ped in `codegen/src/apis.rs` may be missing.
+
+use dhl_codegen::{Api, LOCATION_FINDER, SHIPMENT_TRACKING};
+use dhl_wrapper::api::location_finder::*;
+use dhl_wrapper::api::shipment_tracking::*;
+use dhl_wrapper::api::*;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::BTreeSet;
+
+const BASE_URL: &str = "https://api.example.com";
+
+fn spec(api: &Api) -> Value {
+    let json = std::fs::read_to_string(api.spec).unwrap();
+    serde_json::from_str(&json).unwrap()
+}
+
+/// Follows `$ref`s to schemas and parameters.
+fn resolve<'a>(spec: &'a Value, value: &'a Value) -> &'a Value {
+    match value["$ref"].as_str() {
+        Some(v) => {
+            let target = spec
+                .pointer(v.trim_start_matches('#'))
+                .unwrap_or_else(|| panic!("{} does not exist", v));
+            resolve(spec, target)
+        }
+        None => value,
+    }
+}
+
+/// Key of `schema` as used in the configuration of the generator: the name of the
+/// component it refers to, or the key of its parent and its property otherwise.
+fn key(schema: &Value, key: &str) -> String {
+    match schema["$ref"].as_str() {
+        Some(v) => v.rsplit('/').next().unwrap().to_string(),
+        None => key.to_string(),
+    }
+}
+
+fn enum_values(schema: &Value) -> &[Value] {
+    schema["enum"].as_array().map_or(&[], |v| v.as_slice())
+}
+
+/// Generates a document of `schema`, keyed `key`, with every property set but the skipped ones.
+/// Enums take their `n`th value, enums in arrays all of their values.
+fn example(api: &Api, spec: &Value, schema: &Value, key: &str, n: usize) -> Value {
+    let key = self::key(schema, key);
+    let schema = resolve(spec, schema);
+    if let Some(v) = schema.get("example") {
+        return v.clone();
+    }
+    let values = enum_values(schema);
+    if !values.is_empty() {
+        return values[n % values.len()].clone();
+    }
+
+    match schema["type"].as_str() {
+        Some("object") => Value::Object(
+            schema["properties"]
+                .as_object()
+                .unwrap()
+                .iter()
+                .map(|(k, v)| (k, v, format!("{}.{}", key, k)))
+                .filter(|(_, _, key)| !api.skip.contains(&key.as_str()))
+                .map(|(k, v, key)| (k.clone(), example(api, spec, v, &key, n)))
+                .collect(),
+        ),
+        Some("array") => match enum_values(resolve(spec, &schema["items"])) {
+            [] => Value::Array(vec![example(api, spec, &schema["items"], &key, n)]),
+            v => Value::Array(v.to_vec()),
+        },
+        Some("string") => Value::from(match schema["format"].as_str() {
+            Some("date-time") => "2023-04-05T10:00:00",
+            Some("date") => "2023-04-05",
+            Some("time") => "08:00:00",
+            _ => "x",
+        }),
+        Some("integer") => Value::from(1),
+        Some("number") => Value::from(1.5),
+        Some("boolean") => Value::from(true),
+        v => panic!("unsupported schema type {:?}", v),
+    }
+}
+
+/// Number of documents needed for every enum value to show up in one of them.
+fn max_enum_len(spec: &Value, schema: &Value, seen: &mut BTreeSet<String>) -> usize {
+    if let Some(v) = schema["$ref"].as_str() {
+        if !seen.insert(v.to_string()) {
+            return 1;
+        }
+    }
+    let schema = resolve(spec, schema);
+    let nested = match schema["type"].as_str() {
+        Some("object") => schema["properties"]
+            .as_object()
+            .unwrap()
+            .values()
+            .map(|v| max_enum_len(spec, v, seen))
+            .max()
+            .unwrap_or(1),
+        Some("array") => max_enum_len(spec, &schema["items"], seen),
+        _ => 1,
+    };
+
+    nested.max(enum_values(schema).len())
+}
+
+/// Lists where `actual` differs from `expected`, comparing numbers by value.
+fn diff(expected: &Value, actual: &Value, path: &str, diffs: &mut Vec<String>) {
+    match (expected, actual) {
+        (Value::Object(e), Value::Object(a)) => {
+            let keys = e.keys().chain(a.keys()).collect::<BTreeSet<_>>();
+            for key in keys {
+                let path = format!("{}.{}", path, key);
+                match (e.get(key), a.get(key)) {
+                    (Some(e), Some(a)) => diff(e, a, &path, diffs),
+                    (Some(_), None) => diffs.push(format!("{}: dropped", path)),
+                    (None, Some(_)) => diffs.push(format!("{}: not in the spec", path)),
+                    (None, None) => unreachable!(),
+                }
+            }
+        }
+        (Value::Array(e), Value::Array(a)) if e.len() == a.len() => {
+            for (i, (e, a)) in e.iter().zip(a).enumerate() {
+                diff(e, a, &format!("{}[{}]", path, i), diffs);
+            }
+        }
+        (Value::Number(e), Value::Number(a)) if e.as_f64() == a.as_f64() => {}
+        (e, a) if e == a => {}
+        (e, a) => diffs.push(format!("{}: expected {}, got {}", path, e, a)),
+    }
+}
+
+/// Round-trips documents generated from the 200 response schema of `path` through `T`.
+fn check_response<T: Serialize + DeserializeOwned>(api: &Api, spec: &Value, path: &str) {
+    let response = resolve(spec, &spec["paths"][path]["get"]["responses"]["200"]);
+    let schema = &response["content"]["application/json"]["schema"];
+    let documents = max_enum_len(spec, schema, &mut BTreeSet::new());
+    for n in 0..documents {
+        let document = example(api, spec, schema, "", n);
+        let decoded = serde_path_to_error::deserialize::<_, T>(&document)
+            .unwrap_or_else(|e| panic!("{} response #{} does not decode: {}", path, n, e));
+        let encoded = serde_json::to_value(&decoded).unwrap();
+
+        let mut diffs = Vec::new();
+        diff(&document, &encoded, "", &mut diffs);
+        assert!(diffs.is_empty(), "{} response #{}: {:#?}", path, n, diffs);
+    }
+}
+
+/// Decoded query parameters of `url`.
+fn query(url: &str) -> Vec<(String, String)> {
+    let (_, query) = url.split_once('?').unwrap_or((url, ""));
+    form_urlencoded::parse(query.as_bytes())
+        .into_owned()
+        .collect()
+}
+
+/// Parameters of `path` the generator does not skip.
+fn parameters<'a>(api: &Api, spec: &'a Value, path: &str) -> Vec<&'a Value> {
+    spec["paths"][path]["get"]["parameters"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|v| resolve(spec, v))
+        .filter(|v| {
+            let key = format!("{}?{}", path, v["name"].as_str().unwrap());
+            !api.skip.contains(&key.as_str())
+        })
+        .collect()
+}
+
+fn value<T: DeserializeOwned>(value: &Value) -> T {
+    serde_json::from_value(value.clone())
+        .unwrap_or_else(|e| panic!("{} does not decode: {}", value, e))
+}
+
+/// URL of a request to `path` with only the enum parameter `name` set to `v`, besides the required ones.
+fn url_with(path: &str, name: &str, v: &Value) -> String {
+    let by_address = || GetLocationsByAddress::new(CountryCode::De).postal_code(Some("53113"));
+    let by_geo = || GetLocationsByGeo::new(50.7, 7.1);
+    let url = match (path, name) {
+        ("/shipments", "service") => GetShipmentTracking::new("1234")
+            .service(Some(value(v)))
+            .url(BASE_URL),
+        ("/find-by-address", "providerType") => {
+            by_address().provider_type(Some(value(v))).url(BASE_URL)
+        }
+        ("/find-by-address", "locationType") => {
+            by_address().location_type(Some(value(v))).url(BASE_URL)
+        }
+        ("/find-by-address", "serviceType") => {
+            by_address().service_type(Some(value(v))).url(BASE_URL)
+        }
+        ("/find-by-geo", "providerType") => by_geo().provider_type(Some(value(v))).url(BASE_URL),
+        ("/find-by-geo", "locationType") => by_geo().location_type(Some(value(v))).url(BASE_URL),
+        ("/find-by-geo", "serviceType") => by_geo().service_type(Some(value(v))).url(BASE_URL),
+        _ => panic!("enum parameter {} of {} is not covered", name, path),
+    };
+
+    url.unwrap()
+}
+
+/// Checks the query parameter names of `url` and that every enum value is sent as specified.
+fn check_request(api: &Api, spec: &Value, path: &str, url: String) {
+    let (endpoint, _) = url.split_once('?').unwrap_or((&url, ""));
+    let template = path.split('{').next().unwrap();
+    assert!(
+        endpoint.starts_with(&format!("{}{}", BASE_URL, template)),
+        "{} does not match {}",
+        endpoint,
+        path
+    );
+
+    let expected = parameters(api, spec, path)
+        .iter()
+        .filter(|v| v["in"] == "query")
+        .map(|v| v["name"].as_str().unwrap().to_string())
+        .collect::<BTreeSet<_>>();
+    let actual = query(&url)
+        .into_iter()
+        .map(|(k, _)| k)
+        .collect::<BTreeSet<_>>();
+    assert_eq!(actual, expected, "query parameters of {}", path);
+
+    for parameter in parameters(api, spec, path) {
+        let name = parameter["name"].as_str().unwrap();
+        let schema = resolve(spec, &parameter["schema"]);
+        let values = match schema["type"].as_str() {
+            Some("array") => enum_values(resolve(spec, &schema["items"])),
+            _ => enum_values(schema),
+        };
+        for v in values {
+            let url = url_with(path, name, v);
+            let sent = query(&url).into_iter().find(|(k, _)| k == name);
+            assert_eq!(
+                sent,
+                Some((name.to_string(), v.as_str().unwrap().to_string())),
+                "{} of {}",
+                name,
+                path
+            );
+        }
+    }
+}
+
+#[test]
+fn shipment_tracking_conforms_to_spec() {
+    let api = SHIPMENT_TRACKING;
+    let spec = spec(&api);
+
+    let request = GetShipmentTracking::new("00340434161094042557")
+        .service(Some(Division::ParcelDe))
+        .requester_country_code(Some(CountryCode::De))
+        .origin_country_code(Some(CountryCode::De))
+        .recipient_postal_code(Some("53113"))
+        .language(Some(LanguageCode::En))
+        .offset(Some(0))
+        .limit(Some(5));
+    check_request(&api, &spec, "/shipments", request.url(BASE_URL).unwrap());
+
+    check_response::<GetShipmentTrackingResponse>(&api, &spec, "/shipments");
+}
+
+#[test]
+fn location_finder_conforms_to_spec() {
+    let api = LOCATION_FINDER;
+    let spec = spec(&api);
+
+    let request = GetLocationsByAddress::new(CountryCode::De)
+        .address_locality(Some("Bonn"))
+        .postal_code(Some("53113"))
+        .street_address(Some("Charles-de-Gaulle-Straße 20"))
+        .provider_type(Some(ProviderType::Parcel))
+        .location_type(Some(LocationType::Locker))
+        .service_type(Some(ServiceType::ParcelPickUp))
+        .radius(Some(500))
+        .limit(Some(5))
+        .hide_closed_locations(Some(true));
+    check_request(
+        &api,
+        &spec,
+        "/find-by-address",
+        request.url(BASE_URL).unwrap(),
+    );
+
+    let request = GetLocationsByGeo::new(50.7, 7.1)
+        .provider_type(Some(ProviderType::Express))
+        .location_type(Some(LocationType::Servicepoint))
+        .service_type(Some(ServiceType::ExpressPickUp))
+        .radius(Some(500))
+        .limit(Some(5))
+        .hide_closed_locations(Some(false));
+    check_request(&api, &spec, "/find-by-geo", request.url(BASE_URL).unwrap());
+
+    let request = GetLocationByKeywordId::new("101", CountryCode::De, "53113");
+    check_request(
+        &api,
+        &spec,
+        "/find-by-keyword-id",
+        request.url(BASE_URL).unwrap(),
+    );
+
+    let request = GetLocationById::new("8003-4101479");
+    check_request(
+        &api,
+        &spec,
+        "/locations/{id}",
+        request.url(BASE_URL).unwrap(),
+    );
+
+    check_response::<GetLocationsResponse>(&api, &spec, "/find-by-address");
+    check_response::<GetLocationsResponse>(&api, &spec, "/find-by-geo");
+    check_response::<GetLocationResponse>(&api, &spec, "/find-by-keyword-id");
+    check_response::<GetLocationResponse>(&api, &spec, "/locations/{id}");
+}
@@ -0,0 +1,195 @@
+use dhl_wrapper::api::location_finder::*;
+use dhl_wrapper::api::shipment_tracking::*;
+use dhl_wrapper::api::*;
+use dhl_wrapper::auth::*;
+use dhl_wrapper::error::DhlError;
+use std::path::PathBuf;
+
+/// Message of a [DhlError::MissingCredentials](DhlError::MissingCredentials).
+fn missing<T: std::fmt::Debug>(result: Result<T, DhlError>) -> String {
+    match result {
+        Err(DhlError::MissingCredentials(v)) => v,
+        v => panic!("expected missing credentials, got {:?}", v),
+    }
+}
+
+/// Writes `content` to a temporary file ending with `extension`.
+fn file(name: &str, extension: &str, content: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!(
+        "dhl-wrapper-{}-{}.{}",
+        name,
+        std::process::id(),
+        extension
+    ));
+    std::fs::write(&path, content).unwrap();
+
+    path
+}
+
+#[test]
+fn read_credentials_from_env() {
+    std::env::set_var("ENV_TEST_SHIPMENT_TRACKING_API_KEY", "muchsecretwow");
+    std::env::set_var("ENV_TEST_LOCATION_FINDER_USERNAME", "user");
+    std::env::set_var("ENV_TEST_LOCATION_FINDER_PASSWORD", "pass");
+    let provider = EnvCredentials::new().prefix("env_test_");
+
+    let credentials = provider
+        .credentials(ApiProduct::ShipmentTracking, ApiMode::Production)
+        .unwrap();
+    assert!(matches!(credentials, Credentials::ApiKey(v) if v.expose() == "muchsecretwow"));
+    let credentials = provider
+        .credentials(ApiProduct::LocationFinder, ApiMode::Production)
+        .unwrap();
+    assert!(matches!(
+        credentials,
+        Credentials::Basic { username, password } if username == "user" && password.expose() == "pass"
+    ));
+}
+
+#[test]
+fn name_missing_env_vars() {
+    let provider = EnvCredentials::new().prefix("MISSING_TEST_");
+    assert_eq!(
+        missing(provider.credentials(ApiProduct::ShipmentTracking, ApiMode::Production)),
+        "missing MISSING_TEST_SHIPMENT_TRACKING_API_KEY"
+    );
+
+    std::env::set_var("MISSING_TEST_LOCATION_FINDER_CLIENT_ID", "client");
+    std::env::set_var("MISSING_TEST_LOCATION_FINDER_TOKEN_URL", "  ");
+    assert_eq!(
+        missing(provider.credentials(ApiProduct::LocationFinder, ApiMode::Production)),
+        "missing MISSING_TEST_LOCATION_FINDER_CLIENT_SECRET, MISSING_TEST_LOCATION_FINDER_TOKEN_URL"
+    );
+
+    let result = ShipmentTrackingApi::from_provider(&provider);
+    assert_eq!(
+        missing(result.map(|_| ())),
+        "missing MISSING_TEST_SHIPMENT_TRACKING_API_KEY"
+    );
+}
+
+#[test]
+fn read_credentials_from_files() {
+    let toml = file(
+        "credentials",
+        "toml",
+        r#"
+        [shipment_tracking]
+        api_key = "muchsecretwow"
+
+        [location_finder]
+        client_id = "client"
+        client_secret = "secret"
+        token_url = "https://api.example.com/token"
+        username = "user"
+
+        [sandbox.shipment_tracking]
+        api_key = "sandboxsecret"
+        "#,
+    );
+    let provider = FileCredentials::load(&toml).unwrap();
+
+    let credentials = provider
+        .credentials(ApiProduct::ShipmentTracking, ApiMode::Production)
+        .unwrap();
+    assert!(matches!(credentials, Credentials::ApiKey(v) if v.expose() == "muchsecretwow"));
+    assert_eq!(
+        missing(provider.credentials(ApiProduct::LocationFinder, ApiMode::Production)),
+        format!("missing location_finder.password in {}", toml.display())
+    );
+    let credentials = provider
+        .credentials(ApiProduct::ShipmentTracking, ApiMode::Sandbox)
+        .unwrap();
+    assert!(matches!(credentials, Credentials::ApiKey(v) if v.expose() == "sandboxsecret"));
+    assert_eq!(
+        missing(provider.credentials(ApiProduct::LocationFinder, ApiMode::Sandbox)),
+        format!(
+            "missing sandbox.location_finder.api_key in {}",
+            toml.display()
+        )
+    );
+
+    let json = file(
+        "credentials",
+        "json",
+        r#"{ "location_finder": { "api_key": "muchsecretwow" } }"#,
+    );
+    let provider = FileCredentials::load(&json).unwrap();
+    let api = LocationFinderApi::from_provider(Production, &provider);
+    assert!(api.is_ok());
+    assert_eq!(
+        missing(provider.credentials(ApiProduct::ShipmentTracking, ApiMode::Production)),
+        format!("missing shipment_tracking.api_key in {}", json.display())
+    );
+
+    std::fs::remove_file(toml).unwrap();
+    std::fs::remove_file(json).unwrap();
+}
+
+#[test]
+fn reject_invalid_files() {
+    let yaml = file("credentials", "yaml", "shipment_tracking: {}");
+    assert!(matches!(
+        FileCredentials::load(&yaml),
+        Err(DhlError::Config(_))
+    ));
+
+    let toml = file("invalid", "toml", "[shipment_tracking");
+    assert!(matches!(
+        FileCredentials::load(&toml),
+        Err(DhlError::Config(_))
+    ));
+
+    let json = file("invalid", "json", "{\"shipment_tracking\":");
+    match FileCredentials::load(&json) {
+        Err(DhlError::Config(v)) => assert!(v.contains(&json.display().to_string())),
+        v => panic!("expected a config error, got {:?}", v),
+    }
+
+    std::fs::remove_file(yaml).unwrap();
+    std::fs::remove_file(toml).unwrap();
+    std::fs::remove_file(json).unwrap();
+}
+
+#[test]
+fn chain_providers() {
+    std::env::set_var("CHAIN_TEST_SHIPMENT_TRACKING_API_KEY", "muchsecretwow");
+    let provider = ChainedCredentials::new()
+        .with(EnvCredentials::new().prefix("CHAIN_TEST_FIRST_"))
+        .with(EnvCredentials::new().prefix("CHAIN_TEST_"));
+
+    let credentials = provider
+        .credentials(ApiProduct::ShipmentTracking, ApiMode::Production)
+        .unwrap();
+    assert!(matches!(credentials, Credentials::ApiKey(v) if v.expose() == "muchsecretwow"));
+    assert_eq!(
+        missing(provider.credentials(ApiProduct::LocationFinder, ApiMode::Production)),
+        "missing CHAIN_TEST_FIRST_LOCATION_FINDER_API_KEY; missing CHAIN_TEST_LOCATION_FINDER_API_KEY"
+    );
+}
+
+#[test]
+fn keep_sandbox_credentials_apart() {
+    std::env::set_var("SANDBOX_TEST_SHIPMENT_TRACKING_API_KEY", "muchsecretwow");
+    std::env::set_var(
+        "SANDBOX_TEST_SANDBOX_SHIPMENT_TRACKING_API_KEY",
+        "sandboxsecret",
+    );
+    let provider = EnvCredentials::new().prefix("SANDBOX_TEST_");
+
+    let credentials = provider
+        .credentials(ApiProduct::ShipmentTracking, ApiMode::Sandbox)
+        .unwrap();
+    assert!(matches!(credentials, Credentials::ApiKey(v) if v.expose() == "sandboxsecret"));
+    assert_eq!(
+        missing(provider.credentials(ApiProduct::LocationFinder, ApiMode::Sandbox)),
+        "missing SANDBOX_TEST_SANDBOX_LOCATION_FINDER_API_KEY"
+    );
+
+    assert!(ShipmentTrackingApi::from_provider_in(Sandbox, &provider).is_ok());
+    assert!(ShipmentTrackingApi::from_provider_in(Production, &provider).is_ok());
+    assert_eq!(
+        missing(LocationFinderApi::from_provider(Sandbox, &provider).map(|_| ())),
+        "missing SANDBOX_TEST_SANDBOX_LOCATION_FINDER_API_KEY"
+    );
+}
@@ -0,0 +1,141 @@
+use async_trait::async_trait;
+use dhl_wrapper::api::shipment_tracking::*;
+use dhl_wrapper::error::DhlError;
+use dhl_wrapper::transport::{HttpRequest, HttpResponse, Transport};
+use serde_json::Value;
+
+/// Transport answering every request with the same body.
+struct Respond(u16, Vec<u8>);
+
+#[async_trait]
+impl Transport for Respond {
+    async fn execute(&self, _request: HttpRequest) -> Result<HttpResponse, DhlError> {
+        Ok(HttpResponse {
+            status: self.0,
+            headers: Vec::new(),
+            body: self.1.clone(),
+        })
+    }
+}
+
+fn parcel_de() -> Value {
+    let json = std::fs::read_to_string("tests/fixtures/shipment_tracking/parcel-de.json").unwrap();
+    serde_json::from_str(&json).unwrap()
+}
+
+async fn track(status: u16, body: Vec<u8>) -> DhlError {
+    ShipmentTrackingApi::new("muchsecretwow")
+        .transport(Respond(status, body))
+        .send(GetShipmentTracking::new("00340434161094042557"))
+        .await
+        .unwrap_err()
+}
+
+#[tokio::test]
+async fn report_path_of_decode_errors() {
+    let mut body = parcel_de();
+    body["shipments"][0]["details"]["receiver"]["givenName"] = Value::from(42);
+
+    let error = track(200, serde_json::to_vec(&body).unwrap()).await;
+    let message = error.to_string();
+    match error {
+        DhlError::Decode {
+            path,
+            message,
+            url,
+            status,
+            snippet,
+        } => {
+            assert_eq!(path, "shipments[0].details.receiver.givenName");
+            assert!(message.contains("invalid type: integer `42`"));
+            assert!(url.ends_with("/shipments?trackingNumber=00340434161094042557"));
+            assert_eq!(status, 200);
+            assert!(snippet.starts_with("{\"shipments\":[{"));
+        }
+        v => panic!("expected a decode error, got {:?}", v),
+    }
+    assert!(!message.contains("muchsecretwow"));
+    // the body may hold personal data
+    assert!(!message.contains("shipments\":"), "{}", message);
+
+    let mut body = parcel_de();
+    body["shipments"][0]["status"]
+        .as_object_mut()
+        .unwrap()
+        .remove("statusCode");
+    match track(200, serde_json::to_vec(&body).unwrap()).await {
+        DhlError::Decode { path, message, .. } => {
+            assert_eq!(path, "shipments[0].status");
+            assert!(message.contains("missing field `statusCode`"));
+        }
+        v => panic!("expected a decode error, got {:?}", v),
+    }
+}
+
+#[tokio::test]
+async fn truncate_body_of_decode_errors() {
+    let body = format!("<html>{}</html>", "ü".repeat(1000));
+    match track(502, body.into_bytes()).await {
+        DhlError::Decode {
+            path,
+            status,
+            snippet,
+            ..
+        } => {
+            assert_eq!(path, ".");
+            assert_eq!(status, 502);
+            assert!(snippet.starts_with("<html>üü"));
+            assert!(snippet.ends_with('…'));
+            assert_eq!(snippet.chars().count(), 301);
+        }
+        v => panic!("expected a decode error, got {:?}", v),
+    }
+}
+
+#[tokio::test]
+async fn decode_leniently() {
+    let mut body = parcel_de();
+    let shipment = body["shipments"][0].clone();
+    let mut broken = shipment.clone();
+    broken["details"]["receiver"]["givenName"] = Value::from(42);
+    let mut broken_event = shipment.clone();
+    broken_event["events"][1]["timestamp"] = Value::from("yesterday");
+    body["shipments"] = Value::Array(vec![shipment, broken.clone(), broken_event.clone()]);
+    let body = serde_json::to_vec(&body).unwrap();
+
+    let error = track(200, body.clone()).await;
+    assert!(matches!(error, DhlError::Decode { .. }));
+
+    let response = ShipmentTrackingApi::new("muchsecretwow")
+        .transport(Respond(200, body))
+        .lenient(true)
+        .send(GetShipmentTracking::new("00340434161094042557"))
+        .await
+        .unwrap();
+
+    assert_eq!(response.shipments.len(), 2);
+    let events = broken_event["events"].as_array().unwrap().len();
+    assert_eq!(response.shipments[0].events.len(), events);
+    assert_eq!(response.shipments[1].events.len(), events - 1);
+
+    assert_eq!(response.warnings.len(), 2);
+    let warning = &response.warnings[0];
+    assert_eq!(warning.path, "shipments[1].details.receiver.givenName");
+    assert!(warning.message.contains("invalid type: integer `42`"));
+    assert_eq!(warning.raw, broken);
+    let warning = &response.warnings[1];
+    assert_eq!(warning.path, "shipments[2].events[1].timestamp");
+    assert_eq!(warning.raw, broken_event["events"][1]);
+
+    // lenient decoding still fails on responses which are not tracking data at all
+    let error = ShipmentTrackingApi::new("muchsecretwow")
+        .transport(Respond(200, b"{\"shipments\":{}}".to_vec()))
+        .lenient(true)
+        .send(GetShipmentTracking::new("00340434161094042557"))
+        .await
+        .unwrap_err();
+    match error {
+        DhlError::Decode { path, .. } => assert_eq!(path, "shipments"),
+        v => panic!("expected a decode error, got {:?}", v),
+    }
+}
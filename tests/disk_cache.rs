@@ -0,0 +1,78 @@
+#![cfg(feature = "disk-cache")]
+
+use dhl_wrapper::cache::*;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+fn cache_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("dhl-wrapper-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+
+    dir
+}
+
+fn response(body: &str, stored_at: SystemTime) -> CachedResponse {
+    CachedResponse {
+        status: 200,
+        headers: vec![("content-type".to_string(), "application/json".to_string())],
+        body: body.as_bytes().to_vec(),
+        stored_at,
+        expires_at: Some(stored_at + Duration::from_secs(60)),
+    }
+}
+
+#[test]
+fn persists_responses_across_instances() {
+    let dir = cache_dir("persist");
+    let now = SystemTime::now();
+
+    let cache = DiskCache::new(&dir).unwrap();
+    cache.put("https://api.dhl.com/a", response("{\"a\":1}", now));
+    drop(cache);
+
+    let cache = DiskCache::new(&dir).unwrap();
+    let cached = cache.get("https://api.dhl.com/a").unwrap();
+    assert_eq!(cached.body, b"{\"a\":1}");
+    assert_eq!(cached.headers, response("", now).headers);
+    assert_eq!(cached.status, 200);
+    assert!(cache.get("https://api.dhl.com/b").is_none());
+
+    cache.remove("https://api.dhl.com/a");
+    assert!(cache.get("https://api.dhl.com/a").is_none());
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn evicts_oldest_responses_beyond_max_size() {
+    let dir = cache_dir("evict");
+    let now = SystemTime::now();
+    let body = "x".repeat(1000);
+
+    let cache = DiskCache::new(&dir).unwrap().max_size(2500);
+    cache.put("old", response(&body, now - Duration::from_secs(10)));
+    cache.put("new", response(&body, now));
+    cache.put("newest", response(&body, now + Duration::from_secs(10)));
+
+    assert!(cache.get("old").is_none());
+    assert!(cache.get("new").is_some());
+    assert!(cache.get("newest").is_some());
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn purges_expired_responses() {
+    let dir = cache_dir("purge");
+    let long_ago = SystemTime::now() - Duration::from_secs(3600);
+
+    let cache = DiskCache::new(&dir).unwrap();
+    cache.put("expired", response("{}", long_ago));
+    cache.put("fresh", response("{}", SystemTime::now()));
+    cache.purge_expired().unwrap();
+
+    assert!(cache.get("expired").is_none());
+    assert!(cache.get("fresh").is_some());
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,87 @@
+use async_trait::async_trait;
+use dhl_wrapper::api::location_finder::*;
+use dhl_wrapper::api::shipment_tracking::*;
+use dhl_wrapper::api::*;
+use dhl_wrapper::error::DhlError;
+use dhl_wrapper::transport::{HttpRequest, HttpResponse, Transport};
+use std::sync::{Arc, Mutex};
+
+/// Transport remembering the URLs it is asked for, answering with an empty list.
+#[derive(Clone, Default)]
+struct Urls(Arc<Mutex<Vec<String>>>);
+
+impl Urls {
+    fn last(&self) -> String {
+        self.0.lock().unwrap().last().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl Transport for Urls {
+    async fn execute(&self, request: HttpRequest) -> Result<HttpResponse, DhlError> {
+        self.0.lock().unwrap().push(request.url);
+
+        Ok(HttpResponse {
+            status: 200,
+            headers: Vec::new(),
+            body: br#"{"locations": [], "shipments": []}"#.to_vec(),
+        })
+    }
+}
+
+/// Only takes clients calling production.
+async fn find_in_production(
+    api: &LocationFinderApi<Production>,
+) -> Result<GetLocationsResponse, DhlError> {
+    api.find_by_geo(GetLocationsByGeo::new(53.575264, 9.954053))
+        .await
+}
+
+#[tokio::test]
+async fn call_the_environment_of_the_type() {
+    let urls = Urls::default();
+
+    let api = LocationFinderApi::new(Production, "muchsecretwow").transport(urls.clone());
+    find_in_production(&api).await.unwrap();
+    assert_eq!(api.api_mode(), ApiMode::Production);
+    assert!(urls.last().starts_with(LOCATION_FINDER_BASE_URL));
+
+    let api = LocationFinderApi::new(Sandbox, "muchsecretwow").transport(urls.clone());
+    api.send(GetLocationsByGeo::new(53.575264, 9.954053))
+        .await
+        .unwrap();
+    assert_eq!(api.api_mode(), ApiMode::Sandbox);
+    assert!(urls.last().starts_with(LOCATION_FINDER_SANDBOX_URL));
+
+    let api = LocationFinderApi::new(ApiMode::Sandbox, "muchsecretwow").transport(urls.clone());
+    api.send(GetLocationsByGeo::new(53.575264, 9.954053))
+        .await
+        .unwrap();
+    assert!(urls.last().starts_with(LOCATION_FINDER_SANDBOX_URL));
+}
+
+#[tokio::test]
+async fn call_the_environment_tracking_is_created_for() {
+    let urls = Urls::default();
+    let request = || GetShipmentTracking::new("00340434161094042557");
+
+    let api = ShipmentTrackingApi::new("muchsecretwow").transport(urls.clone());
+    api.send(request()).await.unwrap();
+    assert_eq!(api.api_mode(), ApiMode::Production);
+    assert!(urls.last().starts_with(SHIPMENT_TRACKING_BASE_URL));
+
+    let api = ShipmentTrackingApi::new_in(Production, "muchsecretwow").transport(urls.clone());
+    api.send(request()).await.unwrap();
+    assert!(urls.last().starts_with(SHIPMENT_TRACKING_BASE_URL));
+
+    let api = ShipmentTrackingApi::new_in(Sandbox, "muchsecretwow").transport(urls.clone());
+    api.track(request()).await.unwrap();
+    assert_eq!(api.api_mode(), ApiMode::Sandbox);
+    assert!(urls.last().starts_with(SHIPMENT_TRACKING_SANDBOX_URL));
+
+    let api = ShipmentTrackingApi::new_in(ApiMode::Sandbox, "muchsecretwow")
+        .base_url("http://localhost:8080")
+        .transport(urls.clone());
+    api.send(request()).await.unwrap();
+    assert!(urls.last().starts_with("http://localhost:8080/shipments"));
+}
@@ -0,0 +1,155 @@
+use dhl_wrapper::api::location_finder::{recommendation::*, *};
+use dhl_wrapper::api::shipment_tracking::*;
+use dhl_wrapper::api::*;
+use dhl_wrapper::error::DhlError;
+use dhl_wrapper::fake::*;
+use dhl_wrapper::transport::cassette::Cassette;
+
+fn shipments() -> Vec<Shipment> {
+    let cassette = Cassette::replay("tests/cassettes/shipment_tracking.json").unwrap();
+
+    cassette
+        .interactions()
+        .into_iter()
+        .flat_map(|v| {
+            serde_json::from_value::<GetShipmentTrackingResponse>(v.response.body)
+                .unwrap()
+                .shipments
+        })
+        .collect()
+}
+
+fn locations() -> Vec<ServicePoint> {
+    let cassette = Cassette::replay("tests/cassettes/location_finder.json").unwrap();
+    let body = cassette.interactions().remove(0).response.body;
+
+    serde_json::from_value::<GetLocationsResponse>(body)
+        .unwrap()
+        .locations
+}
+
+/// Code under test, depending on the trait rather than the API struct.
+async fn status(api: &dyn TrackShipments, tracking_number: &str) -> Option<String> {
+    let response = api
+        .track(GetShipmentTracking::new(tracking_number))
+        .await
+        .ok()?;
+
+    response.shipments.first().map(|v| v.status.status.clone())
+}
+
+#[tokio::test]
+async fn fake_shipment_tracking() {
+    let shipments = shipments();
+    let in_transit = shipments[1].clone();
+    let mut delivered = shipments[0].clone();
+    delivered.id = in_transit.id.clone();
+
+    let fake = FakeShipmentTracking::new()
+        .journey("1234567890", vec![in_transit.clone(), delivered.clone()])
+        .fail("00340434161094042557", 429);
+
+    assert_eq!(
+        status(&fake, "1234567890").await,
+        Some(in_transit.status.status)
+    );
+    assert_eq!(
+        status(&fake, "1234567890").await,
+        Some(delivered.status.status.clone())
+    );
+    assert_eq!(
+        status(&fake, "1234567890").await,
+        Some(delivered.status.status)
+    );
+    assert_eq!(status(&fake, "00340434161094042557").await, None);
+    assert_eq!(status(&fake, "unknown").await, None);
+
+    let result = fake
+        .track(GetShipmentTracking::new("unknown").service(Some(Division::Express)))
+        .await;
+    assert!(matches!(
+        result,
+        Err(DhlError::ResponseNotOk { status: 404, .. })
+    ));
+
+    let requests = fake.requests();
+    assert_eq!(requests.len(), 6);
+    assert_eq!(requests[0], GetShipmentTracking::new("1234567890"));
+    assert_eq!(
+        requests[5],
+        GetShipmentTracking::new("unknown").service(Some(Division::Express))
+    );
+}
+
+#[tokio::test]
+async fn fake_location_finder() {
+    let fake = locations()
+        .into_iter()
+        .fold(FakeLocationFinder::new(), |fake, v| fake.location(v));
+
+    let request = GetLocationsByAddress::new(CountryCode::De).postal_code(Some("20357"));
+    let response = fake.find_by_address(request).await.unwrap();
+    assert_eq!(response.locations.len(), 2);
+
+    let request = GetLocationsByAddress::new(CountryCode::At).address_locality(Some("Wien"));
+    let response = fake.find_by_address(request).await.unwrap();
+    assert!(response.locations.is_empty());
+
+    let request = GetLocationsByGeo::new(53.575264, 9.954053)
+        .location_type(Some(LocationType::Servicepoint))
+        .service_type(Some(ServiceType::ParcelPickUp))
+        .radius(Some(3000));
+    let response = fake.find_by_geo(request).await.unwrap();
+    assert_eq!(response.locations.len(), 1);
+    assert_eq!(response.locations[0].name, "Paketshop 751");
+    assert!(response.locations[0].distance.is_some());
+
+    let request = GetLocationsByGeo::new(53.575264, 9.954053).limit(Some(2));
+    let response = fake.find_by_geo(request).await.unwrap();
+    let names = response
+        .locations
+        .iter()
+        .map(|v| v.name.as_str())
+        .collect::<Vec<&str>>();
+    assert_eq!(names, vec!["Packstation 433", "Paketshop 751"]);
+
+    let request = GetLocationByKeywordId::new("433", CountryCode::De, "20357");
+    let response = fake.find_by_keyword_id(request).await.unwrap();
+    assert_eq!(response.name, "Packstation 433");
+
+    let request = GetLocationById::new("8003-0000000");
+    let result = fake.location_by_id(request).await;
+    assert!(matches!(
+        result,
+        Err(DhlError::ResponseNotOk { status: 404, .. })
+    ));
+
+    fake.fail_next(500);
+    let request = GetLocationById::new("8003-4101479");
+    assert!(fake.location_by_id(request.clone()).await.is_err());
+    assert!(fake.location_by_id(request.clone()).await.is_ok());
+
+    let requests = fake.requests();
+    assert_eq!(requests.len(), 8);
+    assert_eq!(requests[7], LocationFinderCall::LocationById(request));
+}
+
+#[tokio::test]
+async fn recommend_with_fake_location_finder() {
+    let fake = locations()
+        .into_iter()
+        .fold(FakeLocationFinder::new(), |fake, v| fake.location(v));
+
+    let request = GetLocationsByAddress::new(CountryCode::De).address_locality(Some("Hamburg"));
+    let recommendations = LocationRecommender::new()
+        .recommend(&fake, request.clone(), Weekday::Mon)
+        .await
+        .unwrap();
+
+    // the post office is left out, since only lockers and service points are recommended by default
+    assert_eq!(recommendations.len(), 2);
+    assert_eq!(
+        fake.requests(),
+        vec![LocationFinderCall::FindByAddress(request)]
+    );
+}
@@ -0,0 +1,178 @@
+use dhl_wrapper::api::location_finder::*;
+use dhl_wrapper::api::shipment_tracking::*;
+use dhl_wrapper::redact::RedactionPolicy;
+use dhl_wrapper::transport::cassette::CASSETTE_MODE_VAR;
+use dotenv::dotenv;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+use tokio::time::{sleep, Duration};
+
+/// Divisions with a fixture in `tests/fixtures/shipment_tracking`.
+const DIVISIONS: [&str; 9] = [
+    "express",
+    "parcel-de",
+    "ecommerce",
+    "dgf",
+    "parcel-uk",
+    "parcel-nl",
+    "parcel-pl",
+    "freight",
+    "sameday",
+];
+
+/// Parses a fixture and checks that serializing and parsing it again gives the same value.
+fn parse<T: Serialize + DeserializeOwned>(path: &Path) -> T {
+    let json = fs::read_to_string(path).unwrap();
+    let value = serde_json::from_str::<T>(&json)
+        .unwrap_or_else(|e| panic!("failed to parse {}: {}", path.display(), e));
+
+    let serialized = serde_json::to_value(&value).unwrap();
+    let reparsed = serde_json::from_value::<T>(serialized.clone()).unwrap();
+    assert_eq!(serde_json::to_value(&reparsed).unwrap(), serialized);
+
+    value
+}
+
+fn shipment(division: &str) -> Shipment {
+    let path = Path::new("tests/fixtures/shipment_tracking").join(format!("{}.json", division));
+    let mut response = parse::<GetShipmentTrackingResponse>(&path);
+
+    assert_eq!(response.shipments.len(), 1);
+    response.shipments.remove(0)
+}
+
+/// With `DHL_CASSETTE_MODE=record`, replaces the fixtures of the divisions the tracking numbers
+/// in `.env` belong to with their responses, run through a `RedactionPolicy` keyed with
+/// `FIXTURE_REDACTION_KEY`.
+#[tokio::test]
+async fn record_shipment_tracking_fixtures() -> Result<(), Box<dyn Error>> {
+    dotenv().ok();
+    if !dotenv::var(CASSETTE_MODE_VAR).is_ok_and(|v| v.eq_ignore_ascii_case("record")) {
+        return Ok(());
+    }
+
+    let api = ShipmentTrackingApi::from_env()?;
+    let policy = RedactionPolicy::new(dotenv::var("FIXTURE_REDACTION_KEY")?);
+    for tracking_number in dotenv::var("VALID_SHIPMENT_TRACKING_NUMBERS")?.split(',') {
+        sleep(Duration::from_secs(1)).await;
+        let response = api.send(GetShipmentTracking::new(tracking_number)).await?;
+
+        for shipment in &response.shipments {
+            let division = serde_json::to_value(shipment.service)?;
+            let path = Path::new("tests/fixtures/shipment_tracking")
+                .join(format!("{}.json", division.as_str().unwrap_or_default()));
+            let fixture = GetShipmentTrackingResponse {
+                shipments: vec![policy.apply(shipment)],
+                ..response.clone()
+            };
+            fs::write(path, serde_json::to_string_pretty(&fixture)? + "\n")?;
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn parse_shipment_tracking_fixtures() {
+    let shipments = DIVISIONS
+        .iter()
+        .map(|v| (v, shipment(v)))
+        .collect::<Vec<(&&str, Shipment)>>();
+
+    for (division, shipment) in &shipments {
+        let service = serde_json::to_value(shipment.service).unwrap();
+        assert_eq!(&service, *division);
+    }
+
+    // every optional part of the model shows up in at least one fixture
+    let any = |f: fn(&Shipment) -> bool| shipments.iter().any(|(_, v)| f(v));
+    assert!(any(|v| v.origin.is_none()));
+    assert!(any(|v| v.destination.is_none()));
+    assert!(any(|v| v.estimated_time_of_delivery.is_some()));
+    assert!(any(|v| v.estimated_delivery_time_frame.is_some()));
+    assert!(any(|v| v.events.is_empty()));
+    assert!(any(|v| v.events.iter().any(|v| v.location.is_none())));
+    assert!(any(|v| v.events.iter().any(|v| v.description.is_empty())));
+    assert!(any(|v| v
+        .origin
+        .as_ref()
+        .and_then(|v| v.service_point.as_ref())
+        .is_some()));
+    assert!(any(|v| v.details.carrier.is_some()));
+    assert!(any(|v| v
+        .details
+        .receiver
+        .as_ref()
+        .is_some_and(|v| v.family_name.is_none())));
+    assert!(any(|v| v.details.sender.is_some()));
+    assert!(any(|v| v.details.proof_of_delivery.as_ref().is_some_and(
+        |v| v.signature_url.is_none() && v.signed.is_some()
+    )));
+    assert!(any(|v| v.details.dimensions.is_some()));
+    assert!(any(|v| v.details.loading_meters.is_some()));
+    assert!(any(|v| v.details.references.is_some()));
+    assert!(any(|v| v.details.dgf_routes.is_some()));
+}
+
+#[test]
+fn parse_timestamps_of_all_divisions() {
+    let express = shipment("express");
+    // offsets are dropped, the local time is kept
+    assert_eq!(express.status.timestamp.to_string(), "2023-03-14 10:44:00");
+
+    let dgf = shipment("dgf");
+    assert_eq!(
+        dgf.estimated_time_of_delivery.unwrap().to_string(),
+        "2023-04-05 00:00:00"
+    );
+    let route = &dgf.details.dgf_routes.unwrap()[0];
+    assert!(route.dgf_vessel_name.is_none());
+    assert!(route.dgf_estimated_arrival_date.is_some());
+
+    let parcel_nl = shipment("parcel-nl");
+    assert_eq!(
+        parcel_nl.status.timestamp.to_string(),
+        "2023-06-01 16:05:00"
+    );
+
+    let sameday = shipment("sameday");
+    assert_eq!(
+        sameday.events[0].timestamp.to_string(),
+        "2023-09-05 11:18:32.512"
+    );
+}
+
+#[test]
+fn parse_location_finder_fixtures() {
+    let response =
+        parse::<GetLocationsResponse>(Path::new("tests/fixtures/location_finder/locations.json"));
+    assert_eq!(response.locations.len(), 3);
+
+    let locker = &response.locations[0];
+    assert_eq!(locker.location.r#type, ServicePointLocationType::Locker);
+    assert_eq!(locker.average_capacity_day_of_week.len(), 7);
+    assert_eq!(
+        locker.average_capacity_day_of_week[3].capacity,
+        Capacity::VeryLow
+    );
+    assert_eq!(locker.available_capacity, Some(Capacity::Low));
+
+    let post_office = &response.locations[1];
+    assert!(post_office.place.contained_in_place.is_some());
+    assert_eq!(post_office.closure_periods.len(), 1);
+
+    // express service points have no keyword and no capacity
+    let express = &response.locations[2];
+    assert!(express.location.keyword.is_empty());
+    assert!(express.closure_periods.is_empty());
+    assert!(express.average_capacity_day_of_week.is_empty());
+    assert_eq!(express.service_types.len(), 5);
+
+    let location =
+        parse::<GetLocationResponse>(Path::new("tests/fixtures/location_finder/location.json"));
+    assert_eq!(location.name, "Packstation 101");
+    assert!(location.distance.is_none());
+}
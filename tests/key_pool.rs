@@ -0,0 +1,169 @@
+use async_trait::async_trait;
+use dhl_wrapper::api::shipment_tracking::*;
+use dhl_wrapper::auth::*;
+use dhl_wrapper::error::DhlError;
+use dhl_wrapper::transport::{HttpRequest, HttpResponse, Transport};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Transport answering with the response set for the key a request is sent with,
+/// or with an empty tracking response.
+#[derive(Clone, Default)]
+struct Server {
+    limited: Arc<Mutex<HashMap<String, HttpResponse>>>,
+    keys: Arc<Mutex<Vec<String>>>,
+}
+
+impl Server {
+    fn limit(&self, key: &str, status: u16, headers: &[(&str, &str)], detail: &str) {
+        let response = HttpResponse {
+            status,
+            headers: headers
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+            body: format!(
+                r#"{{"status": {}, "title": "Too many requests", "detail": "{}"}}"#,
+                status, detail
+            )
+            .into_bytes(),
+        };
+        self.limited
+            .lock()
+            .unwrap()
+            .insert(key.to_string(), response);
+    }
+
+    fn keys(&self) -> Vec<String> {
+        self.keys.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl Transport for Server {
+    async fn execute(&self, request: HttpRequest) -> Result<HttpResponse, DhlError> {
+        let key = request
+            .headers
+            .iter()
+            .find(|(k, _)| k == "DHL-API-Key")
+            .map(|(_, v)| v.clone())
+            .unwrap();
+        self.keys.lock().unwrap().push(key.clone());
+
+        match self.limited.lock().unwrap().get(&key) {
+            Some(v) => Ok(v.clone()),
+            None => Ok(HttpResponse {
+                status: 200,
+                headers: Vec::new(),
+                body: br#"{"shipments": []}"#.to_vec(),
+            }),
+        }
+    }
+}
+
+async fn track(api: &ShipmentTrackingApi) -> Result<GetShipmentTrackingResponse, DhlError> {
+    api.send(GetShipmentTracking::new("00340434161094042557"))
+        .await
+}
+
+#[tokio::test]
+async fn take_turns_round_robin() {
+    let server = Server::default();
+    let pool = KeyPool::new(["key-aaaa", "key-bbbb", "", "key-cccc"]);
+    let api = ShipmentTrackingApi::with_key_pool(pool.clone()).transport(server.clone());
+
+    for _ in 0..4 {
+        track(&api).await.unwrap();
+    }
+
+    assert_eq!(
+        server.keys(),
+        vec!["key-aaaa", "key-bbbb", "key-cccc", "key-aaaa"]
+    );
+    let usage = pool.usage();
+    assert_eq!(
+        usage.iter().map(|v| v.key.as_str()).collect::<Vec<_>>(),
+        vec!["…aaaa", "…bbbb", "…cccc"]
+    );
+    assert_eq!(
+        usage.iter().map(|v| v.requests).collect::<Vec<_>>(),
+        vec![2, 1, 1]
+    );
+}
+
+#[tokio::test]
+async fn spread_requests_over_least_used_keys() {
+    let server = Server::default();
+    let pool =
+        KeyPool::new(["key-aaaa", "key-bbbb", "key-cccc"]).selection(KeySelection::LeastUsed);
+    let api = ShipmentTrackingApi::with_key_pool(pool.clone()).transport(server.clone());
+
+    for _ in 0..9 {
+        track(&api).await.unwrap();
+    }
+
+    assert!(pool.usage().iter().all(|v| v.requests == 3));
+}
+
+#[tokio::test]
+async fn bench_rate_limited_keys() {
+    let server = Server::default();
+    server.limit(
+        "key-aaaa",
+        429,
+        &[("Retry-After", "3600")],
+        "Rate limit exceeded.",
+    );
+    let pool = KeyPool::new(["key-aaaa", "key-bbbb"]);
+    let api = ShipmentTrackingApi::with_key_pool(pool.clone()).transport(server.clone());
+
+    track(&api).await.unwrap();
+    track(&api).await.unwrap();
+
+    assert_eq!(server.keys(), vec!["key-aaaa", "key-bbbb", "key-bbbb"]);
+    let usage = pool.usage();
+    assert_eq!(usage[0].rate_limited, 1);
+    assert!(usage[0].benched_for.unwrap() > Duration::from_secs(3500));
+    assert_eq!(usage[1].benched_for, None);
+}
+
+#[tokio::test]
+async fn bench_keys_exceeding_their_quota() {
+    let server = Server::default();
+    server.limit("key-aaaa", 403, &[], "Daily quota exceeded.");
+    let pool = KeyPool::new(["key-aaaa", "key-bbbb"]).bench_duration(Duration::from_secs(600));
+    let api = ShipmentTrackingApi::with_key_pool(pool.clone()).transport(server.clone());
+
+    track(&api).await.unwrap();
+
+    assert_eq!(server.keys(), vec!["key-aaaa", "key-bbbb"]);
+    let benched_for = pool.usage()[0].benched_for.unwrap();
+    assert!(benched_for > Duration::from_secs(590) && benched_for <= Duration::from_secs(600));
+}
+
+#[tokio::test]
+async fn fail_once_every_key_is_benched() {
+    let server = Server::default();
+    server.limit("key-aaaa", 429, &[], "Rate limit exceeded.");
+    server.limit(
+        "key-bbbb",
+        429,
+        &[("Retry-After", "30")],
+        "Rate limit exceeded.",
+    );
+    let pool = KeyPool::new(["key-aaaa", "key-bbbb"]);
+    let api = ShipmentTrackingApi::with_key_pool(pool.clone()).transport(server.clone());
+
+    let result = track(&api).await;
+    assert!(matches!(
+        result,
+        Err(DhlError::ResponseNotOk { status: 429, .. })
+    ));
+
+    match track(&api).await {
+        Err(DhlError::RateLimited(v)) => assert!(v > Duration::from_secs(25)),
+        v => panic!("expected every key to be benched, got {:?}", v),
+    }
+    assert_eq!(server.keys().len(), 2);
+}
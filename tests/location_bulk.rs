@@ -0,0 +1,113 @@
+use async_trait::async_trait;
+use dhl_wrapper::api::location_finder::*;
+use dhl_wrapper::error::DhlError;
+use dhl_wrapper::transport::{HttpRequest, HttpResponse, Transport};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Transport answering every id with the location fixture, except `8003-0000000`,
+/// and tracking how many requests are in flight.
+#[derive(Clone, Default)]
+struct Server {
+    urls: Arc<Mutex<Vec<String>>>,
+    in_flight: Arc<Mutex<(usize, usize)>>,
+}
+
+impl Server {
+    fn requests(&self) -> usize {
+        self.urls.lock().unwrap().len()
+    }
+
+    fn max_in_flight(&self) -> usize {
+        self.in_flight.lock().unwrap().1
+    }
+}
+
+#[async_trait]
+impl Transport for Server {
+    async fn execute(&self, request: HttpRequest) -> Result<HttpResponse, DhlError> {
+        self.urls.lock().unwrap().push(request.url.clone());
+        {
+            let mut in_flight = self.in_flight.lock().unwrap();
+            in_flight.0 += 1;
+            in_flight.1 = in_flight.1.max(in_flight.0);
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        self.in_flight.lock().unwrap().0 -= 1;
+
+        if request.url.ends_with("/8003-0000000") {
+            return Ok(HttpResponse {
+                status: 404,
+                headers: Vec::new(),
+                body: br#"{"status": 404, "title": "Not Found", "detail": "No location found."}"#
+                    .to_vec(),
+            });
+        }
+
+        Ok(HttpResponse {
+            status: 200,
+            headers: Vec::new(),
+            body: std::fs::read("tests/fixtures/location_finder/location.json").unwrap(),
+        })
+    }
+}
+
+fn location_finder(server: &Server) -> LocationFinderApi {
+    LocationFinderApi::new(ApiMode::Production, "muchsecretwow").transport(server.clone())
+}
+
+#[tokio::test]
+async fn request_repeated_ids_once() {
+    let server = Server::default();
+    let api = location_finder(&server);
+
+    let result = api
+        .get_locations_by_ids(vec![
+            "8003-4101479",
+            "8003-4050201",
+            "8003-4101479",
+            "8003-0000000",
+        ])
+        .await;
+
+    assert_eq!(server.requests(), 3);
+    assert_eq!(result.locations.len(), 2);
+    assert!(matches!(
+        result.failures.get("8003-0000000"),
+        Some(DhlError::ResponseNotOk { status: 404, .. })
+    ));
+}
+
+#[tokio::test]
+async fn limit_concurrent_requests() {
+    let server = Server::default();
+    let api = location_finder(&server).max_concurrent_requests(2);
+    let ids = (0..8).map(|v| format!("8003-410000{}", v));
+
+    let result = api.get_locations_by_ids(ids).await;
+
+    assert_eq!(result.locations.len(), 8);
+    assert_eq!(server.max_in_flight(), 2);
+}
+
+#[tokio::test]
+async fn cache_locations_until_their_ttl_expires() {
+    let server = Server::default();
+    let api = location_finder(&server).location_cache_ttl(Duration::from_millis(200));
+    let ids = vec!["8003-4101479", "8003-0000000"];
+
+    api.get_locations_by_ids(ids.clone()).await;
+    let result = api.get_locations_by_ids(ids.clone()).await;
+    assert_eq!(result.locations.len(), 1);
+    // failures are not cached
+    assert_eq!(server.requests(), 3);
+
+    tokio::time::sleep(Duration::from_millis(250)).await;
+    api.get_locations_by_ids(ids).await;
+    assert_eq!(server.requests(), 5);
+
+    let uncached = location_finder(&server).location_cache_ttl(Duration::ZERO);
+    uncached.get_locations_by_ids(vec!["8003-4101479"]).await;
+    uncached.get_locations_by_ids(vec!["8003-4101479"]).await;
+    assert_eq!(server.requests(), 7);
+}
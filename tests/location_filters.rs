@@ -0,0 +1,55 @@
+use dhl_wrapper::api::location_finder::*;
+use dhl_wrapper::api::*;
+use dhl_wrapper::fake::*;
+
+#[tokio::test]
+async fn filter_by_multiple_types() {
+    let json = std::fs::read_to_string("tests/fixtures/location_finder/locations.json").unwrap();
+    let fake = serde_json::from_str::<GetLocationsResponse>(&json)
+        .unwrap()
+        .locations
+        .into_iter()
+        .fold(FakeLocationFinder::new(), |fake, v| fake.location(v));
+    let names = |response: GetLocationsResponse| {
+        response
+            .locations
+            .into_iter()
+            .map(|v| v.name)
+            .collect::<Vec<String>>()
+    };
+
+    // locations offering all of the services
+    let request = GetLocationsByAddress::new(CountryCode::De)
+        .address_locality(Some("Bonn"))
+        .service_types(Some(vec![
+            ServiceType::ParcelPickUp,
+            ServiceType::AgeVerification,
+        ]));
+    let response = fake.find_by_address(request).await.unwrap();
+    assert_eq!(names(response), vec!["Postfiliale 502"]);
+
+    // locations of any of the types
+    let request = GetLocationsByGeo::new(50.7299, 7.0983)
+        .location_types(Some(vec![LocationType::Locker, LocationType::Postoffice]));
+    let response = fake.find_by_geo(request).await.unwrap();
+    assert_eq!(names(response), vec!["Packstation 101", "Postfiliale 502"]);
+
+    let url = GetLocationsByGeo::new(50.7299, 7.0983)
+        .location_types(Some(vec![LocationType::Locker, LocationType::Postoffice]))
+        .service_types(Some(vec![
+            ServiceType::ParcelDropOff,
+            ServiceType::HandicappedAccess,
+            ServiceType::ParcelDropOff,
+        ]))
+        .url("https://api.dhl.com/location-finder/v1")
+        .unwrap();
+    assert!(url.contains("locationType=locker%2Cpostoffice"));
+    assert!(url.ends_with("serviceType=parcel%3Adrop-off%2Chandicapped-access"));
+
+    // an empty list means no filter at all
+    let url = GetLocationsByGeo::new(50.7299, 7.0983)
+        .service_types(Some(Vec::new()))
+        .url("https://api.dhl.com/location-finder/v1")
+        .unwrap();
+    assert!(!url.contains("serviceType"));
+}
@@ -1,17 +1,32 @@
+#![allow(clippy::bool_assert_comparison)]
+
 use dhl_wrapper::api::{location_finder::*, *};
+use dhl_wrapper::transport::cassette::Cassette;
 use dotenv::dotenv;
 use std::error::Error;
 use tokio::time::{sleep, Duration};
 
+/// Pauses between requests while recording, to stay below DHL's rate limit.
+async fn pause(cassette: &Cassette) {
+    if cassette.is_recording() {
+        sleep(Duration::from_secs(1)).await;
+    }
+}
+
+/// Records with `DHL_CASSETTE_MODE=record` and the key from `.env`, replays the cassette otherwise.
 #[tokio::test]
 async fn get_service_point_locations() -> Result<(), Box<dyn Error>> {
     dotenv().ok();
-    let api_key = dotenv::var("LOCATION_FINDER_API_KEY").expect("LOCATION_FINDER_API_KEY");
-    let api = LocationFinderApi::new(ApiMode::Production, api_key);
+    let cassette = Cassette::from_env("tests/cassettes/location_finder.json")?;
+    let api = match cassette.is_recording() {
+        true => LocationFinderApi::from_env(ApiMode::Production)?,
+        false => LocationFinderApi::new(ApiMode::Production, "replayed"),
+    };
+    let api = api.transport(cassette.clone());
 
     // by address
 
-    sleep(Duration::from_secs(1)).await;
+    pause(&cassette).await;
     let request = GetLocationsByAddress::new(CountryCode::De)
         .address_locality(Some("Hamburg"))
         .postal_code(Some("20355"))
@@ -21,12 +36,12 @@ async fn get_service_point_locations() -> Result<(), Box<dyn Error>> {
 
     // by geo
 
-    sleep(Duration::from_secs(1)).await;
+    pause(&cassette).await;
     let request = GetLocationsByGeo::new(53.575264, 9.954053);
     let response = api.send(request).await.unwrap();
     assert_eq!(response.locations.is_empty(), false);
 
-    sleep(Duration::from_secs(1)).await;
+    pause(&cassette).await;
     let request = GetLocationsByGeo::new(53.575264, 9.954053)
         .provider_type(Some(ProviderType::Parcel))
         .location_type(Some(LocationType::Servicepoint))
@@ -39,14 +54,14 @@ async fn get_service_point_locations() -> Result<(), Box<dyn Error>> {
 
     // by keyword id
 
-    sleep(Duration::from_secs(1)).await;
+    pause(&cassette).await;
     let request = GetLocationByKeywordId::new("433", CountryCode::De, "20357");
     let response = api.send(request).await.unwrap();
     assert_eq!(response.opening_hours.is_empty(), false);
 
     // by id
 
-    sleep(Duration::from_secs(1)).await;
+    pause(&cassette).await;
     let request = GetLocationById::new("8003-4101479");
     let response = api.send(request).await.unwrap();
     assert_eq!(response.opening_hours.is_empty(), false);
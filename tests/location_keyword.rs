@@ -0,0 +1,91 @@
+use dhl_wrapper::api::location_finder::keyword::*;
+use dhl_wrapper::api::CountryCode;
+
+#[test]
+fn parse_keyword_references() {
+    let reference = KeywordReference::parse("Packstation 433, 20357 Hamburg").unwrap();
+    assert_eq!(
+        reference,
+        KeywordReference {
+            keyword: Keyword::Packstation,
+            keyword_id: "433".to_string(),
+            country_code: CountryCode::De,
+            postal_code: Some("20357".to_string()),
+            address_locality: Some("Hamburg".to_string()),
+        }
+    );
+
+    let reference = KeywordReference::parse("Postfiliale 502").unwrap();
+    assert_eq!(reference.keyword, Keyword::Postfiliale);
+    assert_eq!(reference.keyword_id, "502");
+    assert_eq!(reference.postal_code, None);
+    assert_eq!(reference.address_locality, None);
+
+    let reference = KeywordReference::parse("20357 Hamburg, DHL Pack-Station Nr. 433").unwrap();
+    assert_eq!(reference.keyword, Keyword::Packstation);
+    assert_eq!(reference.keyword_id, "433");
+    assert_eq!(reference.postal_code.as_deref(), Some("20357"));
+    assert_eq!(reference.address_locality.as_deref(), Some("Hamburg"));
+
+    let reference = KeywordReference::parse("PACK STATION #117 10115 Berlin Mitte").unwrap();
+    assert_eq!(reference.keyword_id, "117");
+    assert_eq!(reference.address_locality.as_deref(), Some("Berlin Mitte"));
+
+    let reference =
+        KeywordReference::parse_with_country("Paketshop 12, 1010 Wien", CountryCode::At).unwrap();
+    assert_eq!(reference.keyword, Keyword::Paketshop);
+    assert_eq!(reference.country_code, CountryCode::At);
+    assert_eq!(reference.postal_code.as_deref(), Some("1010"));
+}
+
+#[test]
+fn skip_street_addresses_in_references() {
+    let reference = KeywordReference::parse("Postfiliale 502, Hauptstr. 5, 20357 Hamburg").unwrap();
+    assert_eq!(reference.keyword_id, "502");
+    assert_eq!(reference.postal_code.as_deref(), Some("20357"));
+    assert_eq!(reference.address_locality.as_deref(), Some("Hamburg"));
+
+    let reference = KeywordReference::parse("Packstation 433 Hauptstr. 5 20357 Hamburg").unwrap();
+    assert_eq!(reference.postal_code.as_deref(), Some("20357"));
+    assert_eq!(reference.address_locality.as_deref(), Some("Hamburg"));
+
+    let reference = KeywordReference::parse("Postfiliale 502, Hauptstr. 5").unwrap();
+    assert_eq!(reference.postal_code, None);
+    assert_eq!(reference.address_locality, None);
+
+    let reference = KeywordReference::parse_with_country(
+        "Paketshop 12, Damrak 70, 1012 LM Amsterdam",
+        CountryCode::Nl,
+    )
+    .unwrap();
+    assert_eq!(reference.postal_code.as_deref(), Some("1012 LM"));
+    assert_eq!(reference.address_locality.as_deref(), Some("Amsterdam"));
+}
+
+#[test]
+fn reject_unparsable_references() {
+    assert!(matches!(
+        KeywordReference::parse("Hauptstraße 1, 20357 Hamburg"),
+        Err(KeywordReferenceError::MissingKeyword(_))
+    ));
+    assert!(matches!(
+        KeywordReference::parse("Packstation, 20357 Hamburg"),
+        Err(KeywordReferenceError::MissingKeywordId(_))
+    ));
+    assert_eq!(
+        KeywordReference::parse("Packstation 433, 2035 Hamburg"),
+        Err(KeywordReferenceError::InvalidPostalCode("2035".to_string()))
+    );
+}
+
+#[test]
+fn build_requests_from_references() {
+    let reference = KeywordReference::parse("Postfiliale 502").unwrap();
+    assert_eq!(
+        reference.to_request().unwrap_err(),
+        KeywordReferenceError::MissingPostalCode
+    );
+
+    let reference = reference.postal_code(Some("20357"));
+    assert!(reference.to_request().is_ok());
+}
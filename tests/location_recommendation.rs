@@ -0,0 +1,141 @@
+use dhl_wrapper::api::location_finder::{recommendation::*, *};
+use serde_json::{json, Value};
+
+fn service_point(
+    id: &str,
+    r#type: &str,
+    distance: u32,
+    opening_hours: Value,
+    capacity: &str,
+    service_types: Value,
+) -> ServicePoint {
+    serde_json::from_value(json!({
+        "url": format!("/locations/{}", id),
+        "location": {
+            "ids": [{ "locationId": id, "provider": "parcel" }],
+            "keyword": "Packstation",
+            "keywordId": "433",
+            "type": r#type
+        },
+        "name": id,
+        "distance": distance,
+        "place": {
+            "address": {
+                "countryCode": "DE",
+                "postalCode": "20357",
+                "addressLocality": "Hamburg",
+                "streetAddress": "Schäferkampsallee 27"
+            },
+            "geo": { "latitude": 53.57, "longitude": 9.95 }
+        },
+        "openingHours": opening_hours,
+        "closurePeriods": [],
+        "serviceTypes": service_types,
+        "averageCapacityDayOfWeek": [
+            { "dayOfWeek": "http://schema.org/Monday", "capacity": capacity }
+        ],
+        "availableCapacity": "unknown"
+    }))
+    .unwrap()
+}
+
+/// Scores are sums of weighted floats, so they are compared with a tolerance.
+fn assert_close(actual: f64, expected: f64) {
+    assert!(
+        (actual - expected).abs() < 1e-9,
+        "expected {}, got {}",
+        expected,
+        actual
+    );
+}
+
+fn all_day_monday() -> Value {
+    json!([{ "opens": "00:00:00", "closes": "23:59:00", "dayOfWeek": "http://schema.org/Monday" }])
+}
+
+#[test]
+fn ranks_by_combined_score() {
+    let near_but_full = service_point(
+        "near",
+        "locker",
+        100,
+        all_day_monday(),
+        "very-low",
+        json!(["parcel:pick-up"]),
+    );
+    let far_but_empty = service_point(
+        "far",
+        "locker",
+        1500,
+        all_day_monday(),
+        "high",
+        json!(["parcel:pick-up", "handicapped-access", "parking"]),
+    );
+
+    let recommendations = LocationRecommender::new().rank(
+        vec![near_but_full.clone(), far_but_empty.clone()],
+        Weekday::Mon,
+    );
+    assert_eq!(recommendations[0].location.name, "far");
+    assert_close(recommendations[0].breakdown.accessibility, 1.0);
+    assert_close(recommendations[0].breakdown.opening_hours, 1.0);
+
+    let recommendations = LocationRecommender::new()
+        .weights(ScoreWeights {
+            distance: 1.0,
+            capacity: 0.0,
+            opening_hours: 0.0,
+            accessibility: 0.0,
+        })
+        .rank(vec![near_but_full, far_but_empty], Weekday::Mon);
+    assert_eq!(recommendations[0].location.name, "near");
+    assert_close(recommendations[0].score, 0.98);
+}
+
+#[test]
+fn uses_capacity_and_opening_hours_of_delivery_weekday() {
+    let location = service_point(
+        "shop",
+        "servicepoint",
+        0,
+        json!([
+            { "opens": "08:00:00", "closes": "20:00:00", "dayOfWeek": "http://schema.org/Monday" },
+            { "opens": "08:00:00", "closes": "14:00:00", "dayOfWeek": "http://schema.org/Saturday" }
+        ]),
+        "high",
+        json!([]),
+    );
+
+    assert_eq!(expected_capacity(&location, Weekday::Mon), Capacity::High);
+    assert_eq!(
+        expected_capacity(&location, Weekday::Sat),
+        Capacity::Unknown
+    );
+
+    let monday = LocationRecommender::new().rank(vec![location.clone()], Weekday::Mon);
+    let saturday = LocationRecommender::new().rank(vec![location.clone()], Weekday::Sat);
+    let sunday = LocationRecommender::new().rank(vec![location], Weekday::Sun);
+    assert!(monday[0].breakdown.opening_hours > saturday[0].breakdown.opening_hours);
+    assert_close(sunday[0].breakdown.opening_hours, 0.0);
+    assert!(monday[0].score > saturday[0].score);
+}
+
+#[test]
+fn drops_location_types_not_asked_for() {
+    let post_office = service_point(
+        "office",
+        "postoffice",
+        10,
+        all_day_monday(),
+        "high",
+        json!([]),
+    );
+
+    let recommender = LocationRecommender::new();
+    assert!(recommender
+        .rank(vec![post_office.clone()], Weekday::Mon)
+        .is_empty());
+
+    let recommender = recommender.location_types(vec![ServicePointLocationType::Postoffice]);
+    assert_eq!(recommender.rank(vec![post_office], Weekday::Mon).len(), 1);
+}
@@ -0,0 +1,189 @@
+#![cfg(feature = "mock-server")]
+
+use dhl_wrapper::api::location_finder::{keyword::*, *};
+use dhl_wrapper::api::shipment_tracking::*;
+use dhl_wrapper::api::*;
+use dhl_wrapper::cache::ResponseCache;
+use dhl_wrapper::error::DhlError;
+use dhl_wrapper::mock::*;
+use dhl_wrapper::transport::cassette::Cassette;
+use std::time::{Duration, Instant};
+
+/// Builds fixtures from the responses on the test cassettes.
+fn fixtures() -> Fixtures {
+    let mut fixtures = Fixtures::new();
+
+    let cassette = Cassette::replay("tests/cassettes/shipment_tracking.json").unwrap();
+    for interaction in cassette.interactions() {
+        let response: GetShipmentTrackingResponse =
+            serde_json::from_value(interaction.response.body).unwrap();
+        fixtures.shipments.extend(response.shipments);
+    }
+
+    let cassette = Cassette::replay("tests/cassettes/location_finder.json").unwrap();
+    for interaction in cassette.interactions() {
+        if let Ok(response) =
+            serde_json::from_value::<GetLocationsResponse>(interaction.response.body)
+        {
+            for location in response.locations {
+                if !fixtures.locations.iter().any(|v| v.url == location.url) {
+                    fixtures.locations.push(location);
+                }
+            }
+        }
+    }
+
+    fixtures
+}
+
+#[tokio::test]
+async fn track_shipments() {
+    let server = MockServer::start(fixtures()).await.unwrap();
+    let api = ShipmentTrackingApi::new(server.api_key()).base_url(server.shipment_tracking_url());
+
+    let response = api
+        .send(GetShipmentTracking::new("00340434161094042557"))
+        .await
+        .unwrap();
+    assert_eq!(response.shipments[0].id, "00340434161094042557");
+
+    let result = api.send(GetShipmentTracking::new("unknown")).await;
+    assert!(matches!(
+        result,
+        Err(DhlError::ResponseNotOk { status: 404, .. })
+    ));
+
+    let api = ShipmentTrackingApi::new("wrong-key").base_url(server.shipment_tracking_url());
+    let result = api
+        .send(GetShipmentTracking::new("00340434161094042557"))
+        .await;
+    assert!(matches!(
+        result,
+        Err(DhlError::ResponseNotOk { status: 401, .. })
+    ));
+}
+
+#[tokio::test]
+async fn inject_errors_and_latency() {
+    let server = MockServer::start(fixtures()).await.unwrap();
+    let api = ShipmentTrackingApi::new(server.api_key()).base_url(server.shipment_tracking_url());
+    let request = GetShipmentTracking::new("1234567890");
+
+    server.fail_next(InjectedError::InternalServerError);
+    server.fail_next(InjectedError::TooManyRequests);
+    let result = api.send(request.clone()).await;
+    assert!(matches!(
+        result,
+        Err(DhlError::ResponseNotOk { status: 500, .. })
+    ));
+    let result = api.send(request.clone()).await;
+    assert!(matches!(
+        result,
+        Err(DhlError::ResponseNotOk { status: 429, .. })
+    ));
+    assert!(api.send(request.clone()).await.is_ok());
+
+    server.fail_always(Some(InjectedError::NotFound));
+    assert!(api.send(request.clone()).await.is_err());
+    assert!(api.send(request.clone()).await.is_err());
+    server.fail_always(None);
+
+    server.latency(Duration::from_millis(200));
+    let start = Instant::now();
+    assert!(api.send(request).await.is_ok());
+    assert!(start.elapsed() >= Duration::from_millis(200));
+}
+
+#[tokio::test]
+async fn find_locations() {
+    let server = MockServer::start(fixtures()).await.unwrap();
+    let api = LocationFinderApi::new(ApiMode::Production, server.api_key())
+        .base_url(server.location_finder_url());
+
+    let request = GetLocationsByAddress::new(CountryCode::De).postal_code(Some("20357"));
+    let response = api.send(request).await.unwrap();
+    assert_eq!(response.locations.len(), 2);
+
+    let request = GetLocationsByGeo::new(53.575264, 9.954053).radius(Some(800));
+    let response = api.send(request).await.unwrap();
+    assert_eq!(response.locations.len(), 1);
+    assert_eq!(response.locations[0].name, "Packstation 433");
+
+    let request = GetLocationsByGeo::new(53.575264, 9.954053)
+        .location_type(Some(LocationType::Postoffice))
+        .limit(Some(1));
+    let response = api.send(request).await.unwrap();
+    assert_eq!(response.locations.len(), 1);
+    assert_eq!(response.locations[0].name, "Postfiliale 502");
+
+    let request =
+        GetLocationsByGeo::new(53.575264, 9.954053).provider_type(Some(ProviderType::Express));
+    assert!(api.send(request).await.unwrap().locations.is_empty());
+
+    let request = GetLocationByKeywordId::new("433", CountryCode::De, "20357");
+    assert_eq!(api.send(request).await.unwrap().name, "Packstation 433");
+
+    let request = GetLocationById::new("8003-4050201");
+    assert_eq!(api.send(request).await.unwrap().name, "Postfiliale 502");
+}
+
+#[tokio::test]
+async fn resolve_keyword_references() {
+    let server = MockServer::start(fixtures()).await.unwrap();
+    let api = LocationFinderApi::new(ApiMode::Production, server.api_key())
+        .base_url(server.location_finder_url());
+
+    let reference = KeywordReference::parse("Packstation 433, 20357 Hamburg").unwrap();
+    let service_point = api.resolve_keyword_reference(&reference).await.unwrap();
+    assert_eq!(service_point.location.keyword_id, "433");
+
+    let reference = KeywordReference::parse("Packstation 999, 20357 Hamburg").unwrap();
+    let result = api.resolve_keyword_reference(&reference).await;
+    assert!(matches!(result, Err(KeywordResolutionError::NotFound)));
+
+    // DHL's keyword for locker 433 is Packstation, not Postfiliale
+    let reference = KeywordReference::parse("Postfiliale 433, 20357 Hamburg").unwrap();
+    let result = api.resolve_keyword_reference(&reference).await;
+    assert!(matches!(
+        result,
+        Err(KeywordResolutionError::KeywordMismatch { .. })
+    ));
+
+    let reference = KeywordReference::parse("Postfiliale 502").unwrap();
+    let result = api.resolve_keyword_reference(&reference).await;
+    assert!(matches!(
+        result,
+        Err(KeywordResolutionError::InvalidReference(
+            KeywordReferenceError::MissingPostalCode
+        ))
+    ));
+}
+
+#[tokio::test]
+async fn get_locations_by_ids_in_bulk() {
+    let server = MockServer::start(fixtures()).await.unwrap();
+    let api = LocationFinderApi::new(ApiMode::Production, server.api_key())
+        .base_url(server.location_finder_url())
+        .cache(ResponseCache::memory(100))
+        .max_concurrent_requests(2);
+
+    let ids = vec![
+        "8003-4101479",
+        "8003-4050201",
+        "8003-4101479",
+        "8003-0000000",
+    ];
+    let result = api.get_locations_by_ids(ids.clone()).await;
+    assert_eq!(result.locations.len(), 2);
+    assert_eq!(result.failures.len(), 1);
+    assert!(matches!(
+        result.failures.get("8003-0000000"),
+        Some(DhlError::ResponseNotOk { status: 404, .. })
+    ));
+    assert_eq!(server.requests().len(), 3);
+
+    // successful lookups are cached, failed ones are not
+    let result = api.get_locations_by_ids(ids).await;
+    assert_eq!(result.locations.len(), 2);
+    assert_eq!(server.requests().len(), 4);
+}
@@ -0,0 +1,268 @@
+use dhl_wrapper::api::shipment_tracking::*;
+use dhl_wrapper::api::Address;
+use dhl_wrapper::auth::*;
+use dhl_wrapper::redact::{RedactionPolicy, Secret, Treatment, Unredacted};
+use dhl_wrapper::transport::{HttpRequest, HttpResponse};
+use serde_json::json;
+
+/// Personal data in the models of the second test.
+const PERSONAL_DATA: [&str; 6] = [
+    "53113",
+    "Bonn",
+    "Acme",
+    "Mustermann",
+    "Erika",
+    "example.com",
+];
+
+#[test]
+fn hide_secrets() {
+    let secret = Secret::new("muchsecretwow");
+    assert_eq!(format!("{:?}", secret), "<redacted>");
+    assert_eq!(secret.to_string(), "<redacted>");
+    assert_eq!(secret.expose(), "muchsecretwow");
+
+    let credentials = [
+        Credentials::api_key("muchsecretwow"),
+        Credentials::basic("user", "muchsecretwow"),
+        Credentials::OAuth2(
+            OAuth2::client_credentials("https://api.example.com/token", "client", "muchsecretwow")
+                .password("user", "muchsecretwow"),
+        ),
+        Credentials::ApiKeyPool(KeyPool::new(["muchsecretwow"])),
+    ];
+    for v in credentials {
+        let debug = format!("{:?}", v);
+        assert!(!debug.contains("muchsecretwow"), "{}", debug);
+    }
+
+    let request = HttpRequest {
+        method: "POST".to_string(),
+        url: "https://api.example.com/token?api_key=muchsecretwow".to_string(),
+        headers: vec![("DHL-API-Key".to_string(), "muchsecretwow".to_string())],
+        body: Some(b"client_secret=muchsecretwow".to_vec()),
+    };
+    let debug = format!("{:?}", request);
+    assert!(!debug.contains("muchsecretwow"), "{}", debug);
+    assert!(debug.contains("27 bytes"));
+
+    let response = HttpResponse {
+        status: 200,
+        headers: vec![(
+            "Set-Cookie".to_string(),
+            "session=muchsecretwow".to_string(),
+        )],
+        body: br#"{"name":"Erika Mustermann"}"#.to_vec(),
+    };
+    let debug = format!("{:?}", Unredacted(&response));
+    assert!(!debug.contains("muchsecretwow"), "{}", debug);
+    assert!(!debug.contains("Mustermann"), "{}", debug);
+    assert!(debug.contains("27 bytes"));
+}
+
+#[test]
+fn hide_personal_data_unless_unredacted() {
+    let address = Address {
+        country_code: Some("DE".to_string()),
+        postal_code: Some("53113".to_string()),
+        address_locality: Some("Bonn".to_string()),
+        street_address: None,
+    };
+    let party: ShipmentParty = serde_json::from_value(json!({
+        "@type": "Person",
+        "organizationName": "Acme",
+        "familyName": "Mustermann",
+        "givenName": "Erika",
+    }))
+    .unwrap();
+    let proof: ShipmentProofOfDelivery = serde_json::from_value(json!({
+        "timestamp": "2018-08-03T00:00:00Z",
+        "signatureUrl": "https://example.com/signature/erika",
+        "signed": { "@type": "Person", "name": "Erika Mustermann" },
+    }))
+    .unwrap();
+    let values = (address, party, proof);
+
+    let redacted = format!("{:?}", values);
+    for v in PERSONAL_DATA {
+        assert!(!redacted.contains(v), "{} in {}", v, redacted);
+    }
+    assert!(redacted.contains(r#"country_code: Some("DE")"#));
+    assert!(redacted.contains("street_address: None"));
+    assert!(redacted.contains(r#"type: "Person""#));
+
+    let plain = format!("{:?}", Unredacted(&values));
+    for v in PERSONAL_DATA {
+        assert!(plain.contains(v), "{} not in {}", v, plain);
+    }
+
+    // only the wrapped value is shown
+    assert_eq!(format!("{:?}", values), redacted);
+    let mixed = format!("{:?} {:?}", Unredacted(&values.0), values.1);
+    assert!(mixed.contains("Bonn"));
+    assert!(!mixed.contains("Mustermann"));
+}
+
+#[test]
+fn hide_names_in_event_texts() {
+    let shipment = shipment();
+    let debug = format!("{:?} {:?}", shipment.status, shipment.events);
+    assert!(!debug.contains("MUSTER"), "{}", debug);
+    assert!(debug.contains("description: <redacted>"));
+    assert!(debug.contains(r#"status: "delivered""#));
+
+    let debug = format!("{:?}", Unredacted(&shipment.status));
+    assert!(debug.contains("Signed for by: A. MUSTER"), "{}", debug);
+}
+
+/// The express fixture shipment, which has parties, a signer and proof of delivery URLs.
+fn shipment() -> Shipment {
+    let json = std::fs::read_to_string("tests/fixtures/shipment_tracking/express.json").unwrap();
+    let mut response: GetShipmentTrackingResponse = serde_json::from_str(&json).unwrap();
+    let mut shipment = response.shipments.remove(0);
+    shipment.origin.as_mut().unwrap().address.street_address =
+        Some("Charles-de-Gaulle-Straße 20".to_string());
+
+    shipment
+}
+
+#[test]
+fn pseudonymize_shipments() {
+    let shipment = shipment();
+    let redacted = RedactionPolicy::new("muchsecretwow").apply(&shipment);
+
+    let json = serde_json::to_string(&redacted).unwrap();
+    for v in [
+        "MUSTER",
+        "EXAMPLE GMBH",
+        "Charles-de-Gaulle",
+        "proofofdelivery",
+    ] {
+        assert!(!json.contains(v), "{} in {}", v, json);
+    }
+
+    let details = &redacted.details;
+    let receiver = details.receiver.as_ref().unwrap().name.as_ref().unwrap();
+    let signer = details
+        .proof_of_delivery
+        .as_ref()
+        .unwrap()
+        .signed
+        .as_ref()
+        .unwrap();
+    assert_eq!(receiver.len(), 64);
+    assert_eq!(signer.name.as_ref(), Some(receiver));
+    assert_eq!(
+        details.proof_of_delivery.as_ref().unwrap().signature_url,
+        None
+    );
+    assert_eq!(details.carrier, shipment.details.carrier);
+    assert_eq!(
+        redacted.events[0].description,
+        format!("Delivered - Signed for by: {}", receiver)
+    );
+    assert_eq!(redacted.events[1..], shipment.events[1..]);
+    assert_eq!(
+        redacted.origin.as_ref().unwrap().address.postal_code,
+        shipment.origin.as_ref().unwrap().address.postal_code
+    );
+
+    let other_key = RedactionPolicy::new("othersecret").apply(&shipment);
+    assert_ne!(other_key.details.receiver, details.receiver);
+    assert_eq!(
+        RedactionPolicy::new("muchsecretwow").apply(&shipment),
+        redacted
+    );
+}
+
+#[test]
+fn treat_personal_data_as_the_policy_says() {
+    let shipment = shipment();
+    let redacted = RedactionPolicy::new("muchsecretwow")
+        .party_names(Treatment::Strip)
+        .signer_names(Treatment::Keep)
+        .street_addresses(Treatment::Strip)
+        .proof_of_delivery_urls(Treatment::Keep)
+        .apply(&shipment);
+
+    let details = &redacted.details;
+    let sender = details.sender.as_ref().unwrap();
+    assert_eq!(sender.r#type, "Organization");
+    assert_eq!(sender.organization_name, None);
+    assert_eq!(details.receiver.as_ref().unwrap().name, None);
+    assert_eq!(
+        details.proof_of_delivery,
+        shipment.details.proof_of_delivery
+    );
+    assert_eq!(redacted.origin.unwrap().address.street_address, None);
+    assert_eq!(
+        redacted.status.description,
+        "Delivered - Signed for by: <redacted>"
+    );
+}
+
+#[test]
+fn replace_names_in_any_casing() {
+    let json = std::fs::read_to_string("tests/fixtures/redaction/casing.json").unwrap();
+    let mut response: GetShipmentTrackingResponse = serde_json::from_str(&json).unwrap();
+    let shipment = response.shipments.remove(0);
+
+    let redacted = RedactionPolicy::new("muchsecretwow").apply(&shipment);
+    let json = serde_json::to_string(&redacted).unwrap().to_lowercase();
+    assert!(!json.contains("muster"), "{}", json);
+
+    // the longer name is replaced first, leaving the given name alone
+    let name = redacted.details.receiver.as_ref().unwrap().name.clone();
+    assert_eq!(
+        redacted.events[1].description,
+        format!("Out for delivery to {}", name.unwrap())
+    );
+    let signer = redacted.details.proof_of_delivery.unwrap().signed.unwrap();
+    assert_eq!(
+        redacted.status.description,
+        format!("Delivered - Signed for by: {}", signer.name.unwrap())
+    );
+
+    let stripped = RedactionPolicy::new("muchsecretwow")
+        .party_names(Treatment::Strip)
+        .apply(&shipment);
+    assert_eq!(
+        stripped.status.remark.as_deref(),
+        Some("Handed over to <redacted> at the door.")
+    );
+}
+
+#[test]
+fn replace_whole_names_only() {
+    let json = std::fs::read_to_string("tests/fixtures/redaction/casing.json").unwrap();
+    let mut response: GetShipmentTrackingResponse = serde_json::from_str(&json).unwrap();
+    let mut shipment = response.shipments.remove(0);
+    let receiver = shipment.details.receiver.as_mut().unwrap();
+    receiver.organization_name = Some("Ed".to_string());
+    receiver.family_name = Some("Ng".to_string());
+    shipment.events[0].description = "Delivered to NG, signed for by Ed's neighbour".to_string();
+
+    let stripped = RedactionPolicy::new("muchsecretwow")
+        .party_names(Treatment::Strip)
+        .signer_names(Treatment::Strip)
+        .apply(&shipment);
+    assert_eq!(
+        stripped.events[0].description,
+        "Delivered to <redacted>, signed for by <redacted>'s neighbour"
+    );
+    assert_eq!(
+        stripped.status.description,
+        "Delivered - Signed for by: <redacted>"
+    );
+
+    let hashed = RedactionPolicy::new("muchsecretwow").apply(&shipment);
+    let receiver = hashed.details.receiver.unwrap();
+    assert_eq!(
+        hashed.events[0].description,
+        format!(
+            "Delivered to {}, signed for by {}'s neighbour",
+            receiver.family_name.unwrap(),
+            receiver.organization_name.unwrap()
+        )
+    );
+}
@@ -0,0 +1,263 @@
+use async_trait::async_trait;
+use dhl_wrapper::api::*;
+use dhl_wrapper::error::DhlError;
+use dhl_wrapper::transport::retry::Retry;
+use dhl_wrapper::transport::{HttpRequest, HttpResponse, Transport};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Transport answering with the given statuses in order, keeping what it was sent.
+#[derive(Clone, Default)]
+struct Scripted {
+    statuses: Arc<Mutex<VecDeque<u16>>>,
+    requests: Arc<Mutex<Vec<HttpRequest>>>,
+}
+
+impl Scripted {
+    fn new(statuses: &[u16]) -> Self {
+        Scripted {
+            statuses: Arc::new(Mutex::new(statuses.iter().copied().collect())),
+            requests: Arc::default(),
+        }
+    }
+
+    fn requests(&self) -> Vec<HttpRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl Transport for Scripted {
+    async fn execute(&self, request: HttpRequest) -> Result<HttpResponse, DhlError> {
+        self.requests.lock().unwrap().push(request);
+        let status = self.statuses.lock().unwrap().pop_front().unwrap_or(200);
+        let body = match status {
+            200 => br#"{"shipmentNo": "340434161094042557"}"#.to_vec(),
+            v => format!(
+                r#"{{"status": {}, "title": "Unavailable", "detail": "later"}}"#,
+                v
+            )
+            .into_bytes(),
+        };
+
+        Ok(HttpResponse {
+            status,
+            headers: Vec::new(),
+            body,
+        })
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BookingResponse {
+    shipment_no: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CreateBooking {
+    pieces: u32,
+    #[serde(skip)]
+    idempotency_key: Option<String>,
+}
+
+impl DhlRequest for CreateBooking {
+    type Response = BookingResponse;
+
+    fn product(&self) -> ApiProduct {
+        ApiProduct::ShipmentTracking
+    }
+
+    fn method(&self) -> Method {
+        Method::Post
+    }
+
+    fn path(&self) -> String {
+        "/bookings".to_string()
+    }
+
+    fn body(&self) -> Result<Option<Body>, DhlError> {
+        Body::json(self).map(Some)
+    }
+
+    fn idempotency_key(&self) -> Option<String> {
+        self.idempotency_key.clone()
+    }
+}
+
+fn header<'a>(request: &'a HttpRequest, name: &str) -> Option<&'a str> {
+    request
+        .headers
+        .iter()
+        .find(|(k, _)| k == name)
+        .map(|(_, v)| v.as_str())
+}
+
+fn retry<T: Transport>(transport: T) -> Retry<T> {
+    Retry::new(transport).backoff(Duration::ZERO)
+}
+
+#[tokio::test]
+async fn send_json_body_with_idempotency_key() {
+    let transport = Scripted::new(&[]);
+    let client = DhlClient::new("muchsecretwow").transport(transport.clone());
+
+    let key = new_idempotency_key();
+    assert_eq!(key.len(), 32);
+    assert_ne!(key, new_idempotency_key());
+    let request = CreateBooking {
+        pieces: 2,
+        idempotency_key: Some(key.clone()),
+    };
+    let response = client.send(request).await.unwrap();
+    assert_eq!(response.shipment_no, "340434161094042557");
+
+    let requests = transport.requests();
+    assert_eq!(requests[0].method, "POST");
+    assert_eq!(requests[0].url, "https://api-eu.dhl.com/track/bookings");
+    assert_eq!(
+        header(&requests[0], "Content-Type"),
+        Some("application/json")
+    );
+    assert_eq!(header(&requests[0], "Idempotency-Key"), Some(key.as_str()));
+    assert_eq!(requests[0].body, Some(br#"{"pieces":2}"#.to_vec()));
+}
+
+#[test]
+fn idempotency_keys_are_unique() {
+    let first = new_idempotency_key();
+    let second = new_idempotency_key();
+
+    assert_eq!(first.len(), 32);
+    assert_ne!(first, second);
+}
+
+#[test]
+fn encode_multipart_body() {
+    let form = Multipart::new().text("documentType", "INVOICE").file(
+        "file",
+        "invoice.pdf",
+        "application/pdf",
+        b"%PDF-1.7".to_vec(),
+    );
+    let body = Body::multipart(form);
+
+    let boundary = body
+        .content_type
+        .strip_prefix("multipart/form-data; boundary=")
+        .unwrap();
+    let expected = format!(
+        "--{b}\r\n\
+         Content-Disposition: form-data; name=\"documentType\"\r\n\
+         \r\n\
+         INVOICE\r\n\
+         --{b}\r\n\
+         Content-Disposition: form-data; name=\"file\"; filename=\"invoice.pdf\"\r\n\
+         Content-Type: application/pdf\r\n\
+         \r\n\
+         %PDF-1.7\r\n\
+         --{b}--\r\n",
+        b = boundary
+    );
+    assert_eq!(String::from_utf8(body.bytes).unwrap(), expected);
+}
+
+#[test]
+fn escape_multipart_names() {
+    let form = Multipart::new().file(
+        "file\"; name=\"other",
+        "in\r\nvoice\".pdf",
+        "application/pdf",
+        Vec::new(),
+    );
+    let body = String::from_utf8(Body::multipart(form).bytes).unwrap();
+
+    assert!(body.contains(
+        "Content-Disposition: form-data; name=\"file%22; name=%22other\"; \
+         filename=\"in%0D%0Avoice%22.pdf\"\r\n"
+    ));
+}
+
+#[test]
+fn parse_methods() {
+    for method in [
+        Method::Get,
+        Method::Post,
+        Method::Put,
+        Method::Patch,
+        Method::Delete,
+    ] {
+        assert_eq!(method.as_str().parse::<Method>().unwrap(), method);
+    }
+    assert!("get".parse::<Method>().is_err());
+    assert!("OPTIONS".parse::<Method>().is_err());
+}
+
+#[tokio::test]
+async fn retry_idempotent_requests() {
+    let transport = Scripted::new(&[503, 502]);
+    let client = DhlClient::new("muchsecretwow").transport(retry(transport.clone()));
+
+    let request = CreateBooking {
+        pieces: 1,
+        idempotency_key: Some(new_idempotency_key()),
+    };
+    assert!(client.send(request).await.is_ok());
+
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 3);
+    assert!(requests.windows(2).all(|v| v[0] == v[1]));
+}
+
+#[tokio::test]
+async fn do_not_retry_post_without_idempotency_key() {
+    let transport = Scripted::new(&[503]);
+    let client = DhlClient::new("muchsecretwow").transport(retry(transport.clone()));
+
+    let request = CreateBooking {
+        pieces: 1,
+        idempotency_key: None,
+    };
+    let result = client.send(request).await;
+
+    assert!(matches!(
+        result,
+        Err(DhlError::ResponseNotOk { status: 503, .. })
+    ));
+    assert_eq!(transport.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn give_up_after_max_retries() {
+    let transport = Scripted::new(&[503, 503, 503, 503]);
+    let client = DhlClient::new("muchsecretwow").transport(retry(transport.clone()).max_retries(2));
+
+    let request = CreateBooking {
+        pieces: 1,
+        idempotency_key: Some(new_idempotency_key()),
+    };
+
+    assert!(client.send(request).await.is_err());
+    assert_eq!(transport.requests().len(), 3);
+}
+
+#[tokio::test]
+async fn cap_the_backoff() {
+    let transport = Scripted::new(&[503; 40]);
+    let retry = Retry::new(transport.clone())
+        .max_retries(40)
+        .backoff(Duration::from_millis(1))
+        .max_backoff(Duration::from_millis(1));
+    let client = DhlClient::new("muchsecretwow").transport(retry);
+
+    let request = CreateBooking {
+        pieces: 1,
+        idempotency_key: Some(new_idempotency_key()),
+    };
+
+    assert!(client.send(request).await.is_ok());
+    assert_eq!(transport.requests().len(), 41);
+}
@@ -0,0 +1,119 @@
+use async_trait::async_trait;
+use dhl_wrapper::api::location_finder::*;
+use dhl_wrapper::api::shipment_tracking::*;
+use dhl_wrapper::drift::*;
+use dhl_wrapper::error::DhlError;
+use dhl_wrapper::transport::{HttpRequest, HttpResponse, Transport};
+use serde_json::Value;
+use std::sync::{Arc, Mutex};
+
+/// Transport answering every request with the same body.
+struct Respond(Vec<u8>);
+
+#[async_trait]
+impl Transport for Respond {
+    async fn execute(&self, _request: HttpRequest) -> Result<HttpResponse, DhlError> {
+        Ok(HttpResponse {
+            status: 200,
+            headers: Vec::new(),
+            body: self.0.clone(),
+        })
+    }
+}
+
+fn fixture(path: &str) -> Value {
+    let json = std::fs::read_to_string(format!("tests/fixtures/{}", path)).unwrap();
+    serde_json::from_str(&json).unwrap()
+}
+
+async fn track(body: &Value) -> Vec<SchemaDrift> {
+    let observed = Arc::new(Mutex::new(Vec::new()));
+    let sink = observed.clone();
+    ShipmentTrackingApi::new("muchsecretwow")
+        .transport(Respond(serde_json::to_vec(body).unwrap()))
+        .drift_observer(move |drift: &SchemaDrift| sink.lock().unwrap().push(drift.clone()))
+        .send(GetShipmentTracking::new("00340434161094042557"))
+        .await
+        .unwrap();
+
+    let observed = observed.lock().unwrap().clone();
+    observed
+}
+
+fn drift(kind: DriftKind, type_name: &'static str, path: &str) -> SchemaDrift {
+    SchemaDrift {
+        kind,
+        type_name,
+        path: path.to_string(),
+    }
+}
+
+#[tokio::test]
+async fn observe_tracking_drift() {
+    let mut body = fixture("shipment_tracking/parcel-de.json");
+    body["possibleAdditionalShipmentsUrl"] = Value::Array(Vec::new());
+    assert!(track(&body).await.is_empty());
+
+    let shipment = &mut body["shipments"][0];
+    shipment["details"]["co2Emission"] = Value::from(1.5);
+    shipment["status"]["statusCode"] = Value::from("held-at-depot");
+    shipment["details"]
+        .as_object_mut()
+        .unwrap()
+        .remove("pieceIds");
+
+    assert_eq!(
+        track(&body).await,
+        vec![
+            drift(
+                DriftKind::UnknownField,
+                "ShipmentDetail",
+                "shipments[0].details.co2Emission"
+            ),
+            drift(
+                DriftKind::UnknownEnumValue("held-at-depot".to_string()),
+                "ShipmentStatusCode",
+                "shipments[0].status.statusCode"
+            ),
+            drift(
+                DriftKind::MissingField,
+                "ShipmentDetail",
+                "shipments[0].details.pieceIds"
+            ),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn observe_location_finder_drift() {
+    let mut body = fixture("location_finder/locations.json");
+    let locker = &mut body["locations"][0];
+    locker["averageCapacityDayOfWeek"][3]["capacity"] = Value::from("full");
+    locker["location"]["parkingSpots"] = Value::from(4);
+
+    let observed = Arc::new(Mutex::new(Vec::new()));
+    let sink = observed.clone();
+    let response = LocationFinderApi::new(ApiMode::Production, "muchsecretwow")
+        .transport(Respond(serde_json::to_vec(&body).unwrap()))
+        .drift_observer(move |drift: &SchemaDrift| sink.lock().unwrap().push(drift.clone()))
+        .send(GetLocationsByGeo::new(53.575264, 9.954053))
+        .await
+        .unwrap();
+
+    // unknown capacities decode to the catch-all
+    assert_eq!(
+        response.locations[0].average_capacity_day_of_week[3].capacity,
+        Capacity::Unknown
+    );
+    let observed = observed.lock().unwrap().clone();
+    assert!(observed.contains(&drift(
+        DriftKind::UnknownEnumValue("full".to_string()),
+        "Capacity",
+        "locations[0].averageCapacityDayOfWeek[3].capacity"
+    )));
+    assert!(observed.contains(&drift(
+        DriftKind::UnknownField,
+        "ServicePointLocation",
+        "locations[0].location.parkingSpots"
+    )));
+}
@@ -0,0 +1,148 @@
+use dhl_wrapper::api::shipment_tracking::{generator::*, *};
+use dhl_wrapper::api::*;
+use dhl_wrapper::fake::FakeShipmentTracking;
+
+const SCENARIOS: [Scenario; 5] = [
+    Scenario::Normal,
+    Scenario::Delayed,
+    Scenario::CustomsHold,
+    Scenario::Returned,
+    Scenario::FailedDelivery,
+];
+
+fn to_json(shipments: &[Shipment]) -> String {
+    serde_json::to_string(shipments).unwrap()
+}
+
+#[test]
+fn generate_reproducible_shipments() {
+    let a = ShipmentGenerator::new(7)
+        .take(20)
+        .collect::<Vec<Shipment>>();
+    let b = ShipmentGenerator::new(7)
+        .take(20)
+        .collect::<Vec<Shipment>>();
+    let c = ShipmentGenerator::new(8)
+        .take(20)
+        .collect::<Vec<Shipment>>();
+
+    assert_eq!(to_json(&a), to_json(&b));
+    assert_ne!(to_json(&a), to_json(&c));
+
+    // generated shipments look like real responses
+    let json = to_json(&a);
+    let parsed = serde_json::from_str::<Vec<Shipment>>(&json).unwrap();
+    assert_eq!(to_json(&parsed), json);
+}
+
+#[test]
+fn generate_coherent_timelines() {
+    for scenario in SCENARIOS {
+        for division in [Division::ParcelDe, Division::Express, Division::Ecommerce] {
+            let generator = ShipmentGenerator::new(1)
+                .division(division)
+                .origin(CountryCode::De)
+                .destination(CountryCode::Fr)
+                .scenario(scenario);
+
+            for shipment in generator.take(10) {
+                // latest event first, just like DHL
+                let timestamps = shipment
+                    .events
+                    .iter()
+                    .map(|v| v.timestamp)
+                    .collect::<Vec<_>>();
+                assert!(timestamps.windows(2).all(|v| v[0] > v[1]));
+                assert_eq!(shipment.status.timestamp, timestamps[0]);
+                assert_eq!(
+                    shipment.events[0].status_code,
+                    Some(shipment.status.status_code)
+                );
+
+                assert_eq!(
+                    shipment.details.piece_ids.len() as u32,
+                    shipment.details.total_number_of_pieces
+                );
+                let origin = shipment.origin.as_ref().unwrap();
+                assert_eq!(origin.address.country_code.as_deref(), Some("DE"));
+                let destination = shipment.destination.as_ref().unwrap();
+                assert_eq!(destination.address.country_code.as_deref(), Some("FR"));
+                // international shipments pass customs
+                assert!(shipment
+                    .events
+                    .iter()
+                    .any(|v| v.description.contains("customs")));
+            }
+        }
+    }
+
+    let shipment = ShipmentGenerator::new(1).generate();
+    assert!(shipment.id.starts_with("00340434") && shipment.id.len() == 20);
+    assert_eq!(shipment.details.piece_ids, vec![shipment.id.clone()]);
+
+    let shipment = ShipmentGenerator::new(1)
+        .division(Division::Express)
+        .generate();
+    assert_eq!(shipment.id.len(), 10);
+}
+
+#[test]
+fn generate_scenarios() {
+    let generator = |scenario| {
+        ShipmentGenerator::new(3)
+            .origin(CountryCode::De)
+            .destination(CountryCode::At)
+            .scenario(scenario)
+    };
+
+    let shipment = generator(Scenario::Normal).generate();
+    assert_eq!(shipment.status.status_code, ShipmentStatusCode::Delivered);
+    assert_eq!(Some(shipment.status.location), shipment.destination);
+    assert!(shipment.estimated_time_of_delivery.is_none());
+
+    let journey = generator(Scenario::Delayed).journey();
+    let delivered = journey.last().unwrap();
+    assert_eq!(delivered.status.status_code, ShipmentStatusCode::Delivered);
+    let delayed = journey
+        .iter()
+        .position(|v| v.status.status == "DELAYED")
+        .unwrap();
+    let planned = journey[1].estimated_time_of_delivery.unwrap();
+    let postponed = journey[delayed].estimated_time_of_delivery.unwrap();
+    assert!(postponed > planned);
+    assert!(journey[delayed].estimated_time_of_delivery_remark.is_some());
+    assert_eq!(postponed.date(), delivered.status.timestamp.date());
+
+    let shipment = generator(Scenario::CustomsHold).generate();
+    assert_eq!(shipment.status.status, "HELD AT CUSTOMS");
+    assert!(shipment.status.next_steps.is_some());
+    assert!(shipment.estimated_time_of_delivery.is_none());
+
+    let shipment = generator(Scenario::Returned).generate();
+    assert_eq!(shipment.status.status_code, ShipmentStatusCode::Delivered);
+    assert_eq!(Some(shipment.status.location), shipment.origin);
+    assert!(shipment
+        .events
+        .iter()
+        .any(|v| v.status_code == Some(ShipmentStatusCode::Failure)));
+
+    let shipment = generator(Scenario::FailedDelivery).generate();
+    assert_eq!(shipment.status.status_code, ShipmentStatusCode::Failure);
+    assert!(shipment.status.next_steps.is_some());
+}
+
+#[tokio::test]
+async fn replay_generated_journey() {
+    let journey = ShipmentGenerator::new(5).journey();
+    let tracking_number = journey[0].id.clone();
+    let fake = FakeShipmentTracking::new().journey(tracking_number.clone(), journey.clone());
+
+    for expected in &journey {
+        let response = fake
+            .track(GetShipmentTracking::new(tracking_number.clone()))
+            .await
+            .unwrap();
+        assert_eq!(response.shipments[0].events.len(), expected.events.len());
+        assert_eq!(response.shipments[0].status.status, expected.status.status);
+    }
+}
@@ -1,21 +1,51 @@
+#![allow(clippy::bool_assert_comparison)]
+
 use dhl_wrapper::api::shipment_tracking::*;
+use dhl_wrapper::transport::cassette::Cassette;
 use dotenv::dotenv;
+use reqwest::Url;
 use std::error::Error;
 use tokio::time::{sleep, Duration};
 
+/// Records with `DHL_CASSETTE_MODE=record` and the keys from `.env`, replays the cassette otherwise.
 #[tokio::test]
 async fn get_shipment_tracking() -> Result<(), Box<dyn Error>> {
     dotenv().ok();
-    let api_key = dotenv::var("SHIPMENT_TRACKING_API_KEY").expect("SHIPMENT_TRACKING_API_KEY");
-    let tracking_numbers_str =
-        dotenv::var("VALID_SHIPMENT_TRACKING_NUMBERS").expect("VALID_SHIPMENT_TRACKING_NUMBERS");
-    assert_eq!(tracking_numbers_str.is_empty(), false);
+    let cassette = Cassette::from_env("tests/cassettes/shipment_tracking.json")?;
+
+    let (api, tracking_numbers) = if cassette.is_recording() {
+        let api = ShipmentTrackingApi::from_env()?;
+        let tracking_numbers_str = dotenv::var("VALID_SHIPMENT_TRACKING_NUMBERS")
+            .expect("VALID_SHIPMENT_TRACKING_NUMBERS");
+        let tracking_numbers = tracking_numbers_str
+            .split(',')
+            .map(|v| v.to_string())
+            .collect::<Vec<String>>();
+
+        (api, tracking_numbers)
+    } else {
+        let tracking_numbers = cassette
+            .interactions()
+            .iter()
+            .filter_map(|v| {
+                Url::parse(&v.request.url)
+                    .ok()?
+                    .query_pairs()
+                    .find(|(k, _)| k == "trackingNumber")
+                    .map(|(_, v)| v.into_owned())
+            })
+            .collect::<Vec<String>>();
+
+        (ShipmentTrackingApi::new("replayed"), tracking_numbers)
+    };
+    assert_eq!(tracking_numbers.is_empty(), false);
 
-    let tracking_numbers = tracking_numbers_str.split(",");
-    let api = ShipmentTrackingApi::new(api_key);
+    let api = api.transport(cassette.clone());
 
     for tracking_number in tracking_numbers {
-        sleep(Duration::from_secs(1)).await;
+        if cassette.is_recording() {
+            sleep(Duration::from_secs(1)).await;
+        }
         let request = GetShipmentTracking::new(tracking_number);
         let response = api.send(request).await.unwrap();
         assert_eq!(response.shipments.is_empty(), false);
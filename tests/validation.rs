@@ -0,0 +1,123 @@
+use async_trait::async_trait;
+use dhl_wrapper::api::location_finder::*;
+use dhl_wrapper::api::shipment_tracking::*;
+use dhl_wrapper::api::*;
+use dhl_wrapper::error::{DhlError, Violation};
+use dhl_wrapper::transport::{HttpRequest, HttpResponse, Transport};
+
+/// Transport failing the test if anything is sent.
+struct Unreachable;
+
+#[async_trait]
+impl Transport for Unreachable {
+    async fn execute(&self, request: HttpRequest) -> Result<HttpResponse, DhlError> {
+        panic!("invalid request was sent: {}", request.url);
+    }
+}
+
+/// Fields of the violated constraints.
+fn violations(result: Result<(), DhlError>) -> Vec<&'static str> {
+    match result {
+        Ok(()) => Vec::new(),
+        Err(DhlError::InvalidRequest(v)) => v.iter().map(|v| v.field).collect(),
+        Err(e) => panic!("expected an invalid request, got {:?}", e),
+    }
+}
+
+#[test]
+fn validate_location_finder_requests() {
+    let request = GetLocationsByGeo::new(53.575264, 9.954053)
+        .radius(Some(MAX_RADIUS))
+        .limit(Some(MAX_LIMIT));
+    assert!(violations(request.validate()).is_empty());
+
+    let request = GetLocationsByGeo::new(999.0, 9.95)
+        .radius(Some(MAX_RADIUS + 1))
+        .limit(Some(51));
+    assert_eq!(
+        violations(request.validate()),
+        vec!["latitude", "radius", "limit"]
+    );
+    let request = GetLocationsByGeo::new(f64::NAN, -180.5).limit(Some(0));
+    assert_eq!(
+        violations(request.validate()),
+        vec!["latitude", "longitude", "limit"]
+    );
+
+    let request = GetLocationsByAddress::new(CountryCode::De).street_address(Some("Kohlhöfen 16"));
+    assert_eq!(violations(request.validate()), vec!["address_locality"]);
+    let request = GetLocationsByAddress::new(CountryCode::De).postal_code(Some("2035"));
+    assert_eq!(violations(request.validate()), vec!["postal_code"]);
+    let request = GetLocationsByAddress::new(CountryCode::Nl).postal_code(Some("1012AB"));
+    assert!(violations(request.validate()).is_empty());
+
+    let request = GetLocationByKeywordId::new(" ", CountryCode::Pl, "00950");
+    assert_eq!(
+        violations(request.validate()),
+        vec!["keyword_id", "postal_code"]
+    );
+    let request = GetLocationById::new("8003-4101479/../..");
+    assert_eq!(violations(request.validate()), vec!["id"]);
+}
+
+#[test]
+fn validate_shipment_tracking_requests() {
+    let request = GetShipmentTracking::new("00340434161094042557").limit(Some(5));
+    assert!(violations(request.validate()).is_empty());
+
+    let request = GetShipmentTracking::new("").limit(Some(0));
+    assert_eq!(
+        violations(request.validate()),
+        vec!["tracking_number", "limit"]
+    );
+    let request = GetShipmentTracking::new("0034 0434").recipient_postal_code(Some(""));
+    assert_eq!(
+        violations(request.validate()),
+        vec!["tracking_number", "recipient_postal_code"]
+    );
+}
+
+#[test]
+fn validate_postal_codes() {
+    for (country, valid, invalid) in [
+        (CountryCode::De, "20357", "203570"),
+        (CountryCode::Gb, "SW1A 1AA", "SW1A-1AA"),
+        (CountryCode::Gb, "m11ae", "11AE"),
+        (CountryCode::Pl, "00-950", "00950"),
+        (CountryCode::Se, "114 55", "1145"),
+        (CountryCode::Us, "10001-0001", "1000"),
+        (CountryCode::Ca, "K1A 0B1", "K1A 0B"),
+        (CountryCode::Ar, "C1425", "C1425;"),
+    ] {
+        assert!(country.is_valid_postal_code(valid), "{}", valid);
+        assert!(!country.is_valid_postal_code(invalid), "{}", invalid);
+    }
+}
+
+#[tokio::test]
+async fn send_validates_requests() {
+    let api = LocationFinderApi::new(ApiMode::Production, "muchsecretwow").transport(Unreachable);
+    let error = api
+        .send(GetLocationsByGeo::new(999.0, 9.95).limit(Some(51)))
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "InvalidRequest Error: latitude must be between -90 and 90, got 999, \
+         limit must be between 1 and 50, got 51"
+    );
+    match error {
+        DhlError::InvalidRequest(v) => assert_eq!(
+            v[1],
+            Violation {
+                field: "limit",
+                message: "must be between 1 and 50, got 51".to_string(),
+            }
+        ),
+        v => panic!("expected an invalid request, got {:?}", v),
+    }
+
+    let api = ShipmentTrackingApi::new("muchsecretwow").transport(Unreachable);
+    let result = api.send(GetShipmentTracking::new(" ")).await;
+    assert!(matches!(result, Err(DhlError::InvalidRequest(_))));
+}